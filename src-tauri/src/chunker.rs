@@ -1,109 +1,961 @@
-use text_splitter::TextSplitter;
-use log::{debug, info};
+use text_splitter::{ChunkConfig, ChunkSizer, CodeSplitter, MarkdownSplitter, TextSplitter};
+use tokenizers::Tokenizer;
+use log::{debug, info, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+use crate::extractor::{detect_language, DetectedLanguage, DocumentMetadata, PAGE_BREAK};
+
 /// Default chunk size range (in characters)
 /// We use a range to allow flexibility in chunk boundaries
 /// Min: 500 characters (about 100 tokens)
 /// Max: 1500 characters (about 300-350 tokens)
 const DEFAULT_CHUNK_SIZE_RANGE: std::ops::Range<usize> = 500..1000;
 
+/// Default chunk size range when sizing by model tokens
+const DEFAULT_TOKEN_CHUNK_SIZE_RANGE: std::ops::Range<usize> = 128..256;
+
 /// Maximum number of chunks we want to extract and process
 /// This is to prevent excessive processing for very large documents
 const MAX_CHUNKS: usize = 100;
 
+/// Share of the chunk budget reserved for the summary of overflowing chunks
+const SUMMARY_BUDGET_DIVISOR: usize = 10;
+
+/// How long a tokenizer that could not be found is not looked for again
+const TOKENIZER_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Error, Debug)]
 pub enum ChunkerError {
     #[error("Failed to split text into chunks: {0}")]
     SplittingError(String),
+    #[error("Invalid chunking configuration: {0}")]
+    InvalidConfig(String),
+}
+
+/// How a document is split into chunks before embedding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChunkingStrategy {
+    /// Split on semantic boundaries (paragraphs, sentences) sized by characters
+    Character,
+    /// Split on semantic boundaries sized by the embedding model's tokenizer
+    Token,
+    /// Split on Markdown structure (headings, lists, code blocks)
+    Markdown,
+    /// Split on syntax tree boundaries for recognised source code
+    Code,
+}
+
+/// Embedding model whose tokenizer sizes `ChunkingStrategy::Token` chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TokenizerModel {
+    /// The default model, which embeds English and undetected text
+    #[default]
+    English,
+    /// The multilingual E5 model, which embeds every other language
+    Multilingual,
+}
+
+impl TokenizerModel {
+    /// The model that embeds text in `language`
+    pub fn for_language(language: &DetectedLanguage) -> Self {
+        if language.uses_multilingual_model() {
+            TokenizerModel::Multilingual
+        } else {
+            TokenizerModel::English
+        }
+    }
+}
+
+/// What to do with chunks past `ChunkingConfig::max_chunks`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverflowPolicy {
+    /// Keep an evenly spaced sample of chunks across the whole document
+    Sample,
+    /// Keep the leading chunks and condense the rest into summary chunks
+    Summarize,
+}
+
+/// User-configurable chunking settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkingConfig {
+    pub strategy: ChunkingStrategy,
+    /// Minimum chunk size (characters, or tokens for `ChunkingStrategy::Token`)
+    pub min_size: usize,
+    /// Maximum chunk size (characters, or tokens for `ChunkingStrategy::Token`)
+    pub max_size: usize,
+    /// Amount of content shared between neighbouring chunks
+    pub overlap: usize,
+    /// Upper bound on chunks per document. `None` keeps every chunk.
+    pub max_chunks: Option<usize>,
+    pub overflow: OverflowPolicy,
+    /// Model the chunks will be embedded with; not a user setting, it is picked per
+    /// document with `for_language` or `for_text`
+    #[serde(skip)]
+    pub tokenizer: TokenizerModel,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        ChunkingConfig {
+            strategy: ChunkingStrategy::Character,
+            min_size: DEFAULT_CHUNK_SIZE_RANGE.start,
+            max_size: DEFAULT_CHUNK_SIZE_RANGE.end,
+            overlap: 0,
+            max_chunks: Some(MAX_CHUNKS),
+            overflow: OverflowPolicy::Sample,
+            tokenizer: TokenizerModel::English,
+        }
+    }
+}
+
+impl ChunkingConfig {
+    /// Default configuration for token-based chunking
+    pub fn token_default() -> Self {
+        ChunkingConfig {
+            strategy: ChunkingStrategy::Token,
+            min_size: DEFAULT_TOKEN_CHUNK_SIZE_RANGE.start,
+            max_size: DEFAULT_TOKEN_CHUNK_SIZE_RANGE.end,
+            ..ChunkingConfig::default()
+        }
+    }
+
+    /// This configuration sizing token chunks for the model that embeds `language`
+    pub fn for_language(&self, language: &DetectedLanguage) -> Self {
+        ChunkingConfig { tokenizer: TokenizerModel::for_language(language), ..self.clone() }
+    }
+
+    /// This configuration sizing token chunks for the model that embeds the language
+    /// detected in `text`
+    pub fn for_text(&self, text: &str) -> Self {
+        self.for_language(&detect_language(text))
+    }
+
+    /// Checks the configuration for values the splitters would reject
+    pub fn validate(&self) -> Result<(), ChunkerError> {
+        if self.max_size == 0 {
+            return Err(ChunkerError::InvalidConfig("max_size must be greater than 0".to_string()));
+        }
+        if self.min_size > self.max_size {
+            return Err(ChunkerError::InvalidConfig(format!(
+                "min_size ({}) must not exceed max_size ({})",
+                self.min_size, self.max_size
+            )));
+        }
+        if self.overlap >= self.max_size {
+            return Err(ChunkerError::InvalidConfig(format!(
+                "overlap ({}) must be smaller than max_size ({})",
+                self.overlap, self.max_size
+            )));
+        }
+        if self.max_chunks == Some(0) {
+            return Err(ChunkerError::InvalidConfig("max_chunks must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+
+    fn size_range(&self) -> std::ops::Range<usize> {
+        // An empty range would make the splitter emit single characters
+        self.min_size..self.max_size.max(self.min_size + 1)
+    }
+
+    /// Size of `text` in the unit of `min_size`/`max_size`: model tokens for
+    /// `ChunkingStrategy::Token` (while the tokenizer is available), characters otherwise
+    pub fn measure(&self, text: &str) -> usize {
+        match self.strategy {
+            ChunkingStrategy::Token => match model_tokenizer(self.tokenizer) {
+                Some(tokenizer) => tokenizer.size(text),
+                None => text.chars().count(),
+            },
+            _ => text.chars().count(),
+        }
+    }
+}
+
+/// A chunk of document text together with where it came from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextChunk {
+    pub text: String,
+    /// Heading path of the section the chunk belongs to, e.g. `Guide > Install > Linux`
+    pub heading_path: Option<String>,
+    /// 1-based page the chunk was taken from, for paged documents such as PDFs
+    pub page_number: Option<u32>,
+    /// Worksheet the chunk was taken from, for spreadsheets
+    pub sheet_name: Option<String>,
+    /// First and last spreadsheet row covered by the chunk (1-based, inclusive): the row in
+    /// the file for CSV/TSV, the data row below the header for workbooks
+    pub row_start: Option<u32>,
+    pub row_end: Option<u32>,
+    /// Top-level symbol (function, struct, class, ...) the chunk was taken from, for source code
+    pub symbol_name: Option<String>,
+    /// First and last source line (1-based, inclusive) covered by the chunk
+    pub line_start: Option<u32>,
+    pub line_end: Option<u32>,
+    /// 1-based position of the message inside a mail archive (mbox)
+    pub message_index: Option<u32>,
+    /// Subject, sender, recipients and date of the email message the chunk was taken from
+    pub message_subject: Option<String>,
+    pub message_from: Option<String>,
+    pub message_to: Option<String>,
+    pub message_date: Option<String>,
+    /// Title, author, creation date and tags of the document the chunk belongs to
+    pub metadata: DocumentMetadata,
+    /// Language detected for this chunk and the detector's confidence; decides the embedding model
+    pub language: Option<DetectedLanguage>,
+    pub language_confidence: Option<f32>,
+}
+
+impl TextChunk {
+    pub fn new(text: String) -> Self {
+        TextChunk { text, ..TextChunk::default() }
+    }
+
+    /// Text handed to the embedding model: the heading breadcrumb, sheet name or symbol name
+    /// (under the email subject for messages) followed by the chunk body
+    pub fn embedding_text(&self) -> String {
+        let location = self.heading_path.as_ref().or(self.sheet_name.as_ref()).or(self.symbol_name.as_ref());
+        let context = match (self.message_subject.as_ref(), location) {
+            (Some(subject), Some(location)) => Some(format!("{} > {}", subject, location)),
+            (Some(subject), None) => Some(subject.clone()),
+            (None, location) => location.cloned(),
+        };
+        match context {
+            Some(context) => format!("{}\n\n{}", context, self.text),
+            None => self.text.clone(),
+        }
+    }
+
+    /// Copy of this chunk's location metadata holding different text
+    pub fn with_text(&self, text: String) -> Self {
+        TextChunk { text, ..self.clone() }
+    }
+}
+
+// Active chunking configuration used by the indexing pipeline
+static CHUNKING_CONFIG: Lazy<RwLock<ChunkingConfig>> =
+    Lazy::new(|| RwLock::new(ChunkingConfig::default()));
+
+// Tokenizers of the embedding models, loaded on first use of `ChunkingStrategy::Token`
+static ENGLISH_TOKENIZER: OnceCell<Tokenizer> = OnceCell::new();
+static MULTILINGUAL_TOKENIZER: OnceCell<Tokenizer> = OnceCell::new();
+
+// When each tokenizer was last looked for in vain. The model may still be downloaded, so a
+// miss is only remembered for `TOKENIZER_RETRY_INTERVAL`.
+static TOKENIZER_MISSES: Lazy<Mutex<HashMap<TokenizerModel, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The tokenizer of `model`, or `None` while its model has not been downloaded
+fn model_tokenizer(model: TokenizerModel) -> Option<&'static Tokenizer> {
+    let cell = match model {
+        TokenizerModel::English => &ENGLISH_TOKENIZER,
+        TokenizerModel::Multilingual => &MULTILINGUAL_TOKENIZER,
+    };
+    if let Some(tokenizer) = cell.get() {
+        return Some(tokenizer);
+    }
+
+    let mut misses = TOKENIZER_MISSES.lock().unwrap();
+    if misses.get(&model).is_some_and(|missed_at| missed_at.elapsed() < TOKENIZER_RETRY_INTERVAL) {
+        return None;
+    }
+    let path = crate::embedder::model_tokenizer_path(model == TokenizerModel::Multilingual);
+    let loaded = path.and_then(|path| match Tokenizer::from_file(&path) {
+        Ok(tokenizer) => {
+            info!("Loaded {:?} chunking tokenizer from {}", model, path.display());
+            Some(tokenizer)
+        }
+        Err(e) => {
+            warn!("Failed to load tokenizer from {}: {}", path.display(), e);
+            None
+        }
+    });
+    match loaded {
+        Some(tokenizer) => {
+            misses.remove(&model);
+            Some(cell.get_or_init(|| tokenizer))
+        }
+        None => {
+            misses.insert(model, Instant::now());
+            None
+        }
+    }
+}
+
+/// Get the active chunking configuration
+pub fn get_chunking_config() -> ChunkingConfig {
+    CHUNKING_CONFIG.read().unwrap().clone()
+}
+
+/// Replace the active chunking configuration
+pub fn set_chunking_config(config: ChunkingConfig) -> Result<(), ChunkerError> {
+    config.validate()?;
+    info!("Updating chunking configuration: {:?}", config);
+    *CHUNKING_CONFIG.write().unwrap() = config;
+    Ok(())
 }
 
 /// Splits the given text into semantically meaningful chunks.
-/// Uses the TextSplitter from the text-splitter crate.
-/// 
+/// Uses the active chunking configuration, sizing token chunks for the model that embeds
+/// the language of the text.
+///
 /// # Arguments
 /// * `text` - The text to split into chunks
-/// 
+/// * `extension` - The source file extension, used to pick a grammar for `ChunkingStrategy::Code`
+/// * `prefix` - Text prepended to every chunk (e.g. a model instruction)
+///
 /// # Returns
 /// * `Result<Vec<String>, ChunkerError>` - A vector of text chunks or an error
-pub fn chunk_text(text: &str, prefix: &str) -> Result<Vec<String>, ChunkerError> {
-    debug!("Chunking text of length {} characters", text.len());
-    
-    if text.is_empty() {
+pub fn chunk_text(text: &str, extension: Option<&str>, prefix: &str) -> Result<Vec<String>, ChunkerError> {
+    let mut chunks = chunk_document(text, extension, &get_chunking_config().for_text(text))?;
+
+    if !prefix.is_empty() {
+        chunks.iter_mut().for_each(|chunk| {
+            chunk.insert_str(0, prefix);
+        });
+    }
+
+    Ok(chunks)
+}
+
+/// Splits a document into chunks according to `config`.
+///
+/// # Arguments
+/// * `text` - The document text
+/// * `extension` - The source file extension, used to pick a grammar for `ChunkingStrategy::Code`
+/// * `config` - The chunking configuration to apply
+pub fn chunk_document(
+    text: &str,
+    extension: Option<&str>,
+    config: &ChunkingConfig,
+) -> Result<Vec<String>, ChunkerError> {
+    debug!("Chunking text of length {} characters with {:?}", text.len(), config.strategy);
+
+    if text.trim().is_empty() {
         debug!("Input text is empty, returning empty chunk list");
         return Ok(Vec::new());
     }
-    
-    // Use TextSplitter with character count for chunking
-    // This uses semantic boundaries (sentences, paragraphs) when possible
-    let splitter = TextSplitter::new(DEFAULT_CHUNK_SIZE_RANGE);
-    
-    // Collect each chunk as String
-    let mut chunks: Vec<String> = splitter.chunks(text)
-        .map(|s| s.to_string())
+
+    config.validate()?;
+
+    let chunks = split_text(text, extension, config)?
+        .into_iter()
+        .map(TextChunk::new)
         .collect();
-    
-    // Limit the number of chunks if necessary
-    if chunks.len() > MAX_CHUNKS {
-        info!("Limiting chunks from {} to {}", chunks.len(), MAX_CHUNKS);
-        chunks = chunks.into_iter().take(MAX_CHUNKS).collect();
-    }
-    
-    if prefix != "" {
-        chunks.iter_mut().for_each(|chunk| {
-            chunk.insert_str(0, prefix);
+
+    Ok(apply_overflow(chunks, config)
+        .into_iter()
+        .map(|chunk| chunk.text)
+        .collect())
+}
+
+/// Splits a document into chunks that remember their position in the document.
+///
+/// Markdown files are split on heading boundaries first so every chunk carries
+/// its heading breadcrumb; text with page breaks is split page by page so every
+/// chunk carries its page number; other files are split with `chunk_document`.
+pub fn chunk_sections(
+    text: &str,
+    extension: Option<&str>,
+    config: &ChunkingConfig,
+) -> Result<Vec<TextChunk>, ChunkerError> {
+    if text.contains(PAGE_BREAK) {
+        return chunk_pages(text, extension, config);
+    }
+
+    match extension.map(|ext| ext.to_lowercase()).as_deref() {
+        Some("md") | Some("markdown") => chunk_markdown(text, config),
+        _ => Ok(chunk_document(text, extension, config)?
+            .into_iter()
+            .map(TextChunk::new)
+            .collect()),
+    }
+}
+
+/// Splits text whose pages are separated by `PAGE_BREAK` into page-numbered chunks.
+///
+/// Paged documents are indexed completely, so `config.max_chunks` is not applied.
+pub fn chunk_pages(
+    text: &str,
+    extension: Option<&str>,
+    config: &ChunkingConfig,
+) -> Result<Vec<TextChunk>, ChunkerError> {
+    let mut chunks = Vec::new();
+    for (index, page) in text.split(PAGE_BREAK).enumerate() {
+        chunks.extend(chunk_page(page, index as u32 + 1, extension, config)?);
+    }
+    Ok(chunks)
+}
+
+/// Chunks the text of a single page, tagging every chunk with `page_number`.
+/// No chunk cap is applied; callers streaming long documents page by page rely on this.
+pub fn chunk_page(
+    text: &str,
+    page_number: u32,
+    extension: Option<&str>,
+    config: &ChunkingConfig,
+) -> Result<Vec<TextChunk>, ChunkerError> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    config.validate()?;
+
+    Ok(split_text(text, extension, config)?
+        .into_iter()
+        .map(|text| TextChunk {
+            text,
+            page_number: Some(page_number),
+            ..TextChunk::default()
+        })
+        .collect())
+}
+
+/// Chunks sections produced by a structure-aware extractor (e.g. spreadsheet row groups).
+///
+/// Every section keeps its location metadata; sections larger than the configured
+/// chunk size are split further. Like paged documents, every section is indexed.
+pub fn chunk_structured(
+    sections: &[TextChunk],
+    extension: Option<&str>,
+    config: &ChunkingConfig,
+) -> Result<Vec<TextChunk>, ChunkerError> {
+    config.validate()?;
+
+    let mut chunks = Vec::new();
+    for section in sections.iter().filter(|section| !section.text.trim().is_empty()) {
+        if config.measure(&section.text) <= config.max_size {
+            chunks.push(section.clone());
+            continue;
+        }
+        for text in split_text(&section.text, extension, config)? {
+            chunks.push(section.with_text(text));
+        }
+    }
+    Ok(chunks)
+}
+
+/// Splits a Markdown document on its headings and chunks each section separately.
+/// Every chunk records the path of headings above it (`Guide > Install > Linux`).
+pub fn chunk_markdown(text: &str, config: &ChunkingConfig) -> Result<Vec<TextChunk>, ChunkerError> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    config.validate()?;
+
+    let mut chunks = Vec::new();
+    for section in markdown_sections(text) {
+        let heading_path = if section.headings.is_empty() {
+            None
+        } else {
+            Some(section.headings.join(" > "))
+        };
+        for chunk_text in split_text(&section.body, Some("md"), config)? {
+            chunks.push(TextChunk {
+                text: chunk_text,
+                heading_path: heading_path.clone(),
+                ..TextChunk::default()
+            });
+        }
+    }
+
+    Ok(apply_overflow(chunks, config))
+}
+
+/// A run of Markdown body text and the headings it is nested under
+struct MarkdownSection {
+    headings: Vec<String>,
+    body: String,
+}
+
+/// Groups Markdown lines into sections delimited by ATX headings (`#` .. `######`).
+/// Lines inside fenced code blocks are never treated as headings.
+fn markdown_sections(text: &str) -> Vec<MarkdownSection> {
+    let mut sections = Vec::new();
+    let mut heading_stack: Vec<(usize, String)> = Vec::new();
+    let mut body = String::new();
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+        } else if let Some((level, title)) = parse_atx_heading(trimmed) {
+            if !body.trim().is_empty() {
+                sections.push(MarkdownSection {
+                    headings: heading_stack.iter().map(|(_, title)| title.clone()).collect(),
+                    body: std::mem::take(&mut body),
+                });
+            }
+            // Drop blank lines between consecutive headings
+            body.clear();
+            while heading_stack.last().map_or(false, |(open_level, _)| *open_level >= level) {
+                heading_stack.pop();
+            }
+            heading_stack.push((level, title));
+            continue;
+        }
+
+        body.push_str(line);
+        body.push('\n');
+    }
+
+    if !body.trim().is_empty() {
+        sections.push(MarkdownSection {
+            headings: heading_stack.into_iter().map(|(_, title)| title).collect(),
+            body,
         });
     }
-    
+
+    sections
+}
+
+/// Parses `## Title ##` into `(2, "Title")`
+fn parse_atx_heading(line: &str) -> Option<(usize, String)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') && !rest.starts_with('\t') {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some((level, title.to_string()))
+}
+
+/// Splits text with the configured strategy without applying the chunk cap
+fn split_text(text: &str, extension: Option<&str>, config: &ChunkingConfig) -> Result<Vec<String>, ChunkerError> {
+    let chunks = match config.strategy {
+        ChunkingStrategy::Character => split_by_characters(text, config)?,
+        ChunkingStrategy::Token => match model_tokenizer(config.tokenizer) {
+            Some(tokenizer) => {
+                let chunk_config = ChunkConfig::new(config.size_range())
+                    .with_sizer(tokenizer)
+                    .with_overlap(config.overlap)
+                    .map_err(|e| ChunkerError::InvalidConfig(e.to_string()))?;
+                collect_chunks(TextSplitter::new(chunk_config).chunks(text))
+            }
+            None => {
+                warn!("Model tokenizer unavailable, falling back to character chunking");
+                split_by_characters(text, config)?
+            }
+        },
+        ChunkingStrategy::Markdown => {
+            let chunk_config = character_chunk_config(config)?;
+            collect_chunks(MarkdownSplitter::new(chunk_config).chunks(text))
+        }
+        ChunkingStrategy::Code => match extension.and_then(code_language_for_extension) {
+            Some(language) => {
+                let chunk_config = character_chunk_config(config)?;
+                let splitter = CodeSplitter::new(language, chunk_config)
+                    .map_err(|e| ChunkerError::SplittingError(e.to_string()))?;
+                collect_chunks(splitter.chunks(text))
+            }
+            None => {
+                debug!("No grammar for extension {:?}, using character chunking", extension);
+                split_by_characters(text, config)?
+            }
+        },
+    };
+
     Ok(chunks)
 }
 
+/// Fits a chunk list into `config.max_chunks` using the configured overflow policy
+fn apply_overflow(chunks: Vec<TextChunk>, config: &ChunkingConfig) -> Vec<TextChunk> {
+    match config.max_chunks {
+        Some(max_chunks) if chunks.len() > max_chunks => {
+            info!(
+                "Document produced {} chunks, applying {:?} to fit {}",
+                chunks.len(),
+                config.overflow,
+                max_chunks
+            );
+            match config.overflow {
+                OverflowPolicy::Sample => sample_chunks(chunks, max_chunks),
+                OverflowPolicy::Summarize => summarize_overflow(chunks, max_chunks, config),
+            }
+        }
+        _ => chunks,
+    }
+}
+
+fn character_chunk_config(config: &ChunkingConfig) -> Result<ChunkConfig<text_splitter::Characters>, ChunkerError> {
+    ChunkConfig::new(config.size_range())
+        .with_overlap(config.overlap)
+        .map_err(|e| ChunkerError::InvalidConfig(e.to_string()))
+}
+
+fn split_by_characters(text: &str, config: &ChunkingConfig) -> Result<Vec<String>, ChunkerError> {
+    // This uses semantic boundaries (sentences, paragraphs) when possible
+    let splitter = TextSplitter::new(character_chunk_config(config)?);
+    Ok(collect_chunks(splitter.chunks(text)))
+}
+
+fn collect_chunks<'a>(chunks: impl Iterator<Item = &'a str>) -> Vec<String> {
+    chunks
+        .filter(|chunk| !chunk.trim().is_empty())
+        .map(|chunk| chunk.to_string())
+        .collect()
+}
+
+/// Maps a file extension to the tree-sitter grammar used for code-aware splitting
+pub(crate) fn code_language_for_extension(extension: &str) -> Option<tree_sitter::Language> {
+    match extension.to_lowercase().as_str() {
+        "rs" => Some(tree_sitter_rust::LANGUAGE.into()),
+        "py" => Some(tree_sitter_python::LANGUAGE.into()),
+        "js" | "jsx" => Some(tree_sitter_javascript::LANGUAGE.into()),
+        "ts" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        "tsx" => Some(tree_sitter_typescript::LANGUAGE_TSX.into()),
+        "go" => Some(tree_sitter_go::LANGUAGE.into()),
+        _ => None,
+    }
+}
+
+/// Keeps `max_chunks` evenly spaced chunks, always including the first and last one
+fn sample_chunks<T>(chunks: Vec<T>, max_chunks: usize) -> Vec<T> {
+    if max_chunks == 1 {
+        return chunks.into_iter().take(1).collect();
+    }
+
+    let last = chunks.len() - 1;
+    let mut wanted = (0..max_chunks)
+        .map(|i| (i * last + (max_chunks - 1) / 2) / (max_chunks - 1))
+        .peekable();
+
+    chunks
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, chunk)| {
+            if wanted.peek() == Some(&index) {
+                // Skip duplicate indices produced by rounding
+                while wanted.peek() == Some(&index) {
+                    wanted.next();
+                }
+                Some(chunk)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Keeps the leading chunks and condenses everything past the budget into
+/// extractive summary chunks built from the first sentence of each dropped chunk.
+/// A summary chunk takes its location (heading, page, rows, ...) from the first
+/// chunk it condenses and is sized with the configured sizer.
+fn summarize_overflow(chunks: Vec<TextChunk>, max_chunks: usize, config: &ChunkingConfig) -> Vec<TextChunk> {
+    let summary_budget = (max_chunks / SUMMARY_BUDGET_DIVISOR).max(1);
+    if max_chunks <= summary_budget {
+        return sample_chunks(chunks, max_chunks);
+    }
+
+    let keep = max_chunks - summary_budget;
+    let mut kept = chunks;
+    let overflow = kept.split_off(keep);
+
+    let mut summary_chunks: Vec<TextChunk> = Vec::new();
+    let mut current: Option<TextChunk> = None;
+    for chunk in &overflow {
+        let sentence = lead_sentence(&chunk.text);
+        if let Some(summary) = current.as_mut() {
+            let candidate = format!("{} {}", summary.text, sentence);
+            if config.measure(&candidate) <= config.max_size {
+                summary.text = candidate;
+                continue;
+            }
+        }
+        summary_chunks.extend(current.replace(chunk.with_text(sentence.to_string())));
+    }
+    summary_chunks.extend(current);
+
+    if summary_chunks.len() > summary_budget {
+        kept.extend(sample_chunks(summary_chunks, summary_budget));
+    } else {
+        kept.extend(summary_chunks);
+    }
+    kept
+}
+
+/// Returns the first sentence of a chunk (or the whole chunk if it has no sentence break)
+fn lead_sentence(chunk: &str) -> &str {
+    let trimmed = chunk.trim();
+    trimmed
+        .char_indices()
+        .find(|(_, c)| matches!(c, '.' | '!' | '?' | '።'))
+        .map(|(i, c)| &trimmed[..i + c.len_utf8()])
+        .unwrap_or(trimmed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_chunk_text_empty() {
-        let result = chunk_text("", "");
+        let result = chunk_text("", None, "");
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
-    
+
     #[test]
     fn test_chunk_text_small() {
         let small_text = "This is a small piece of text that should fit in a single chunk.";
-        let result = chunk_text(small_text, "").unwrap();
+        let result = chunk_text(small_text, None, "").unwrap();
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], small_text);
     }
-    
+
     #[test]
     fn test_chunk_text_large() {
         // Create a text that's definitely larger than our chunk size
         let large_text = "This is paragraph one.\n\n".repeat(100);
-        let result = chunk_text(&large_text, "").unwrap();
-        
+        let result = chunk_text(&large_text, None, "").unwrap();
+
         // Should be split into multiple chunks
         assert!(result.len() > 1);
-        
+
         // Each chunk should be smaller than max chunk size
         for chunk in &result {
             assert!(chunk.len() <= DEFAULT_CHUNK_SIZE_RANGE.end);
         }
-        
+
         // Total text in chunks should equal original (except for potential whitespace differences)
         let total_chars: usize = result.iter().map(|s| s.len()).sum();
         // Allow for some margin due to potential boundary adjustments
         assert!(total_chars > large_text.len() * 9 / 10 && total_chars <= large_text.len() * 11 / 10);
     }
-    
+
     #[test]
     fn test_chunk_text_respects_max_chunks() {
         // Create an extremely large text to test MAX_CHUNKS limit
         let huge_text = "This is paragraph one.\n\n".repeat(1000);
-        let result = chunk_text(&huge_text, "").unwrap();
-        
+        let result = chunk_text(&huge_text, None, "").unwrap();
+
         // Should not exceed MAX_CHUNKS
         assert!(result.len() <= MAX_CHUNKS);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_chunk_text_applies_prefix() {
+        let result = chunk_text("Short text.", None, "passage: ").unwrap();
+        assert_eq!(result, vec!["passage: Short text.".to_string()]);
+    }
+
+    #[test]
+    fn test_sampling_keeps_end_of_document() {
+        let text: String = (0..1000).map(|i| format!("Paragraph number {}.\n\n", i)).collect();
+        let config = ChunkingConfig { max_chunks: Some(10), ..ChunkingConfig::default() };
+        let result = chunk_document(&text, None, &config).unwrap();
+
+        assert_eq!(result.len(), 10);
+        assert!(result[0].contains("Paragraph number 0."));
+        assert!(result[9].contains("Paragraph number 999."));
+    }
+
+    #[test]
+    fn test_summarize_condenses_overflow() {
+        let text: String = (0..1000)
+            .map(|i| format!("Topic {} starts here. Followed by filler text for the topic.\n\n", i))
+            .collect();
+        let config = ChunkingConfig {
+            max_chunks: Some(20),
+            overflow: OverflowPolicy::Summarize,
+            ..ChunkingConfig::default()
+        };
+        let result = chunk_document(&text, None, &config).unwrap();
+
+        assert!(result.len() <= 20);
+        // The tail of the document is represented by its lead sentences
+        let last_topic = result
+            .iter()
+            .flat_map(|chunk| chunk.split("Topic ").skip(1))
+            .filter_map(|rest| rest.split_whitespace().next()?.parse::<usize>().ok())
+            .max()
+            .unwrap();
+        assert!(last_topic > 900, "last topic kept was {}", last_topic);
+    }
+
+    #[test]
+    fn test_summaries_keep_location_of_first_condensed_chunk() {
+        let text: String = (0..60)
+            .map(|i| format!("# Section {}\n\nSection {} begins. {}\n\n", i, i, "Filler words here. ".repeat(40)))
+            .collect();
+        let config = ChunkingConfig {
+            max_chunks: Some(20),
+            overflow: OverflowPolicy::Summarize,
+            ..ChunkingConfig::default()
+        };
+        let chunks = chunk_markdown(&text, &config).unwrap();
+
+        assert!(chunks.len() <= 20);
+        let summaries = &chunks[18..];
+        assert!(summaries.iter().all(|chunk| chunk.heading_path.is_some()));
+        for summary in summaries {
+            let section = summary.heading_path.as_deref().unwrap().trim_start_matches("Section ");
+            assert!(summary.text.starts_with(&format!("Section {} begins.", section)));
+            assert!(config.measure(&summary.text) <= config.max_size);
+        }
+    }
+
+    #[test]
+    fn test_overlap_repeats_content() {
+        let text: String = (0..200).map(|i| format!("Sentence {}. ", i)).collect();
+        let config = ChunkingConfig { min_size: 50, max_size: 100, overlap: 40, ..ChunkingConfig::default() };
+        let with_overlap = chunk_document(&text, None, &config).unwrap();
+        let without_overlap = chunk_document(&text, None, &ChunkingConfig { overlap: 0, ..config.clone() }).unwrap();
+
+        let overlapped_len: usize = with_overlap.iter().map(|c| c.len()).sum();
+        let plain_len: usize = without_overlap.iter().map(|c| c.len()).sum();
+        assert!(overlapped_len > plain_len);
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let config = ChunkingConfig { overlap: 2000, ..ChunkingConfig::default() };
+        assert!(matches!(config.validate(), Err(ChunkerError::InvalidConfig(_))));
+        assert!(set_chunking_config(config).is_err());
+    }
+
+    #[test]
+    fn test_token_chunks_are_sized_for_the_embedding_model() {
+        let config = ChunkingConfig::token_default();
+        assert_eq!(config.for_language(&DetectedLanguage::Amharic).tokenizer, TokenizerModel::Multilingual);
+        assert_eq!(config.for_language(&DetectedLanguage::English).tokenizer, TokenizerModel::English);
+        assert_eq!(config.for_language(&DetectedLanguage::Other).tokenizer, TokenizerModel::English);
+
+        // The model is picked per document, never saved with the user's settings
+        let multilingual = config.for_language(&DetectedLanguage::Amharic);
+        let json = serde_json::to_string(&multilingual).unwrap();
+        assert!(!json.contains("tokenizer"));
+        assert_eq!(serde_json::from_str::<ChunkingConfig>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn test_markdown_strategy_splits_on_structure() {
+        let text = format!(
+            "# First\n\n{}\n\n# Second\n\n{}",
+            "Alpha text. ".repeat(60),
+            "Beta text. ".repeat(60)
+        );
+        let config = ChunkingConfig { strategy: ChunkingStrategy::Markdown, ..ChunkingConfig::default() };
+        let result = chunk_document(&text, Some("md"), &config).unwrap();
+
+        assert!(result.len() >= 2);
+        assert!(result.iter().any(|chunk| chunk.starts_with("# Second")));
+    }
+
+    #[test]
+    fn test_markdown_chunks_carry_heading_path() {
+        let text = "# Guide\n\nIntro text.\n\n## Install\n\n### Linux\n\nRun the installer.\n\n## Usage\n\nOpen the app.\n";
+        let chunks = chunk_markdown(text, &ChunkingConfig::default()).unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].heading_path.as_deref(), Some("Guide"));
+        assert_eq!(chunks[1].heading_path.as_deref(), Some("Guide > Install > Linux"));
+        assert_eq!(chunks[1].text, "Run the installer.");
+        assert_eq!(chunks[2].heading_path.as_deref(), Some("Guide > Usage"));
+        assert_eq!(chunks[1].embedding_text(), "Guide > Install > Linux\n\nRun the installer.");
+    }
+
+    #[test]
+    fn test_markdown_ignores_headings_in_code_fences() {
+        let text = "# Setup\n\n```bash\n# not a heading\necho hi\n```\n";
+        let chunks = chunk_markdown(text, &ChunkingConfig::default()).unwrap();
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].heading_path.as_deref(), Some("Setup"));
+        assert!(chunks[0].text.contains("# not a heading"));
+    }
+
+    #[test]
+    fn test_markdown_text_before_first_heading_has_no_breadcrumb() {
+        let chunks = chunk_sections("Preamble.\n\n# Title\n\nBody.", Some("md"), &ChunkingConfig::default()).unwrap();
+
+        assert_eq!(chunks[0].heading_path, None);
+        assert_eq!(chunks[1].heading_path.as_deref(), Some("Title"));
+    }
+
+    #[test]
+    fn test_code_strategy_falls_back_for_unknown_extension() {
+        let config = ChunkingConfig { strategy: ChunkingStrategy::Code, ..ChunkingConfig::default() };
+        let result = chunk_document("plain words", Some("unknown"), &config).unwrap();
+        assert_eq!(result, vec!["plain words".to_string()]);
+    }
+
+    #[test]
+    fn test_paged_text_keeps_page_numbers() {
+        let text = ["First page text.", "", "Third page text."].join(&PAGE_BREAK.to_string());
+        let chunks = chunk_sections(&text, Some("pdf"), &ChunkingConfig::default()).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].page_number, Some(1));
+        assert_eq!(chunks[1].page_number, Some(3));
+        assert_eq!(chunks[1].text, "Third page text.");
+    }
+
+    #[test]
+    fn test_paged_text_is_not_capped() {
+        let page = "This is paragraph one.\n\n".repeat(100);
+        let text = vec![page; 50].join(&PAGE_BREAK.to_string());
+        let config = ChunkingConfig::default();
+        let chunks = chunk_sections(&text, Some("pdf"), &config).unwrap();
+
+        assert!(chunks.len() > config.max_chunks.unwrap());
+        assert_eq!(chunks.last().unwrap().page_number, Some(50));
+    }
+
+    #[test]
+    fn test_structured_sections_keep_location() {
+        let sections = vec![
+            TextChunk {
+                text: "Team: Marketing; Quarter: Q3; Budget: 12000".to_string(),
+                sheet_name: Some("Budget".to_string()),
+                row_start: Some(2),
+                row_end: Some(2),
+                ..TextChunk::default()
+            },
+            TextChunk {
+                text: "Row. ".repeat(400),
+                sheet_name: Some("Notes".to_string()),
+                row_start: Some(3),
+                row_end: Some(9),
+                ..TextChunk::default()
+            },
+            TextChunk { sheet_name: Some("Empty".to_string()), ..TextChunk::default() },
+        ];
+        let chunks = chunk_structured(&sections, Some("xlsx"), &ChunkingConfig::default()).unwrap();
+
+        assert_eq!(chunks[0], sections[0]);
+        assert!(chunks.len() > 2);
+        assert!(chunks[1..].iter().all(|chunk| chunk.sheet_name.as_deref() == Some("Notes")
+            && chunk.row_start == Some(3)
+            && chunk.row_end == Some(9)));
+        assert!(chunks[0].embedding_text().starts_with("Budget\n\n"));
+    }
+
+    #[test]
+    fn test_message_chunks_embed_subject() {
+        let mut chunk = TextChunk {
+            text: "Budget review moved to Friday.".to_string(),
+            message_subject: Some("Planning".to_string()),
+            ..TextChunk::default()
+        };
+        assert_eq!(chunk.embedding_text(), "Planning\n\nBudget review moved to Friday.");
+
+        chunk.heading_path = Some("notes.txt".to_string());
+        assert_eq!(chunk.embedding_text(), "Planning > notes.txt\n\nBudget review moved to Friday.");
+    }
+}
@@ -3,6 +3,8 @@ use crate::core::{
     file_system::list_directory, // Your core function
     models::FileInfo,            // The return data structure
 };
+use crate::extractor::{extract_archive_member, is_archive_member_path};
+use crate::filename_index;
 use directories_next::UserDirs;
 use hostname;
 use std::{
@@ -197,9 +199,33 @@ pub enum OpenError {
 }
 
 /// Attempts to open the given path (file or directory) using the system's default application.
+///
+/// When `page` is given for a PDF, a viewer that accepts a start page is tried first so the
+/// document opens at the matching page; otherwise the default application is used.
+/// Archive members (`bundle.zip!/docs/spec.pdf`) are extracted to a temporary file first.
 #[tauri::command]
-pub async fn open_path_command(path: String) -> Result<(), OpenError> {
-    tracing::info!("Attempting to open path: {}", path);
+pub async fn open_path_command(path: String, page: Option<u32>) -> Result<(), OpenError> {
+    tracing::info!("Attempting to open path: {} (page: {:?})", path, page);
+    // Files opened often rank higher in filename search
+    filename_index::record_open(&path);
+    let path = if is_archive_member_path(&path) {
+        let member_path = path.clone();
+        let extracted = tokio::task::spawn_blocking(move || extract_archive_member(&member_path))
+            .await
+            .map_err(|e| OpenError::IoError { path: path.clone(), message: e.to_string() })?
+            .map_err(|e| {
+                tracing::error!("Failed to extract archive member '{}': {}", path, e);
+                OpenError::IoError { path: path.clone(), message: e.to_string() }
+            })?;
+        extracted.to_string_lossy().to_string()
+    } else {
+        path
+    };
+    if let Some(page) = page.filter(|page| *page > 0) {
+        if is_pdf(&path) && open_pdf_at_page(&path, page) {
+            return Ok(());
+        }
+    }
     opener::open(&path).map_err(|e| {
         tracing::error!("Failed to open path '{}': {}", path, e);
         OpenError::IoError {
@@ -209,6 +235,52 @@ pub async fn open_path_command(path: String) -> Result<(), OpenError> {
     })
 }
 
+fn is_pdf(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+/// PDF viewers that can be started at a given page, with the arguments to do so
+fn pdf_page_viewers(path: &str, page: u32) -> Vec<(&'static str, Vec<String>)> {
+    let page = page.to_string();
+    if cfg!(target_os = "windows") {
+        vec![
+            ("SumatraPDF", vec!["-reuse-instance".into(), "-page".into(), page.clone(), path.into()]),
+            ("AcroRd32", vec!["/A".into(), format!("page={}", page), path.into()]),
+        ]
+    } else if cfg!(target_os = "macos") {
+        // Preview has no page argument; Skim does when it is installed
+        vec![("/Applications/Skim.app/Contents/MacOS/Skim", vec![path.into(), "-page".into(), page])]
+    } else {
+        vec![
+            // `--page-label` would match printed labels (e.g. roman numerals), not positions
+            ("evince", vec!["--page-index".into(), page.clone(), path.into()]),
+            ("okular", vec!["-p".into(), page.clone(), path.into()]),
+            ("zathura", vec!["-P".into(), page, path.into()]),
+        ]
+    }
+}
+
+/// Tries to open a PDF at `page` (1-based). Returns false when no suitable viewer could be started.
+fn open_pdf_at_page(path: &str, page: u32) -> bool {
+    for (viewer, args) in pdf_page_viewers(path, page) {
+        match std::process::Command::new(viewer).args(&args).spawn() {
+            Ok(mut child) => {
+                tracing::info!("Opened '{}' at page {} with {}", path, page, viewer);
+                // Reap the viewer when it exits so it does not linger as a zombie
+                std::thread::spawn(move || {
+                    let _ = child.wait();
+                });
+                return true;
+            }
+            Err(e) => tracing::debug!("PDF viewer {} unavailable: {}", viewer, e),
+        }
+    }
+    false
+}
+
 // Helper function to get a specific user directory path as String
 fn get_user_dir_path<F>(dir_fn: F) -> Result<String, ConfigError>
 where
@@ -1,9 +1,17 @@
 // src-tauri/src/commands/indexing_commands.rs
 
-use crate::core::indexer::{index_downloads_folder, index_folder, get_last_indexing_stats};
+use crate::core::indexer::{downloads_folder, index_downloads_folder, index_folder, get_last_indexing_stats, FailedFile};
 use crate::db::{connect_db, TABLE_NAME, clear_data};
-use log::{info, error};
+use crate::chunker::{get_chunking_config, set_chunking_config, ChunkingConfig};
+use crate::embedder::{get_cross_lingual_config, set_cross_lingual_config, CrossLingualConfig};
+use crate::extractor::{
+    get_archive_config, get_ocr_config, is_ocr_available, set_archive_config, set_ocr_config, ArchiveConfig, OcrConfig,
+};
+use crate::settings::save_settings;
+use crate::watcher::record_indexed_root;
+use log::{info, error, warn};
 use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 
 /// Response model for indexing operations
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,7 +24,7 @@ pub struct IndexingResponse {
     pub success: bool,
     pub message: String,
     pub indexed_files: Vec<String>,
-    pub failed_files: Vec<String>,
+    pub failed_files: Vec<FailedFile>,
 }
 
 /// Generic operation response
@@ -26,14 +34,24 @@ pub struct OperationResponse {
     pub message: String,
 }
 
+/// Records an indexed folder as an indexed root, so it is watched and reconciled at startup
+async fn keep_folder_indexed(folder_path: &str, app_handle: &AppHandle) {
+    if let Err(e) = record_indexed_root(folder_path, app_handle).await {
+        warn!("Failed to record {} as an indexed root: {}", folder_path, e);
+    }
+}
+
 /// Tauri command to manually index the Downloads folder
 #[tauri::command]
-pub async fn index_downloads_command() -> Result<IndexingResponse, String> {
+pub async fn index_downloads_command(app_handle: AppHandle) -> Result<IndexingResponse, String> {
     info!("Manual Downloads folder indexing requested");
     
     match index_downloads_folder().await {
         Ok(stats) => {
             info!("Downloads folder indexing completed successfully");
+            if let Ok(downloads_dir) = downloads_folder() {
+                keep_folder_indexed(&downloads_dir.to_string_lossy(), &app_handle).await;
+            }
             
             Ok(IndexingResponse {
                 files_processed: stats.files_processed,
@@ -70,12 +88,13 @@ pub async fn index_downloads_command() -> Result<IndexingResponse, String> {
 
 /// Tauri command to index a specific folder
 #[tauri::command]
-pub async fn index_folder_command(folder_path: String) -> Result<IndexingResponse, String> {
+pub async fn index_folder_command(folder_path: String, app_handle: AppHandle) -> Result<IndexingResponse, String> {
     info!("Manual indexing of folder requested: {}", folder_path);
     
     match index_folder(&folder_path).await {
         Ok(stats) => {
             info!("Folder indexing completed successfully: {}", folder_path);
+            keep_folder_indexed(&folder_path, &app_handle).await;
             
             Ok(IndexingResponse {
                 files_processed: stats.files_processed,
@@ -180,6 +199,101 @@ pub async fn clear_index_command() -> Result<OperationResponse, String> {
     }
 }
 
+/// Tauri command to get the active chunking configuration
+#[tauri::command]
+pub fn get_chunking_config_command() -> Result<ChunkingConfig, String> {
+    Ok(get_chunking_config())
+}
+
+/// Tauri command to change how documents are chunked on the next indexing run
+#[tauri::command]
+pub async fn set_chunking_config_command(config: ChunkingConfig, app_handle: AppHandle) -> Result<OperationResponse, String> {
+    info!("Request to update chunking configuration: {:?}", config);
+
+    if let Err(e) = set_chunking_config(config) {
+        error!("Rejected chunking configuration: {}", e);
+        return Err(e.to_string());
+    }
+    persist_settings(&app_handle).await;
+    Ok(OperationResponse {
+        success: true,
+        message: "Chunking configuration updated. Re-index folders to apply it.".to_string(),
+    })
+}
+
+/// Tauri command to get the limits used when indexing archive members
+#[tauri::command]
+pub fn get_archive_config_command() -> Result<ArchiveConfig, String> {
+    Ok(get_archive_config())
+}
+
+/// Tauri command to change which archives are opened and how deep
+#[tauri::command]
+pub async fn set_archive_config_command(config: ArchiveConfig, app_handle: AppHandle) -> Result<OperationResponse, String> {
+    info!("Request to update archive configuration: {:?}", config);
+
+    if let Err(e) = set_archive_config(config) {
+        error!("Rejected archive configuration: {}", e);
+        return Err(e.to_string());
+    }
+    persist_settings(&app_handle).await;
+    Ok(OperationResponse {
+        success: true,
+        message: "Archive configuration updated. Re-index folders to apply it.".to_string(),
+    })
+}
+
+/// Tauri command to get the OCR configuration
+#[tauri::command]
+pub fn get_ocr_config_command() -> Result<OcrConfig, String> {
+    Ok(get_ocr_config())
+}
+
+/// Tauri command to enable or disable OCR and choose its languages
+#[tauri::command]
+pub async fn set_ocr_config_command(config: OcrConfig, app_handle: AppHandle) -> Result<OperationResponse, String> {
+    info!("Request to update OCR configuration: {:?}", config);
+    let enabled = config.enabled;
+
+    if let Err(e) = set_ocr_config(config) {
+        error!("Rejected OCR configuration: {}", e);
+        return Err(e.to_string());
+    }
+    persist_settings(&app_handle).await;
+    let message = if enabled && !is_ocr_available() {
+        "OCR configuration saved, but Tesseract was not found on the PATH. Install it to enable OCR."
+    } else {
+        "OCR configuration updated. Re-index folders to apply it."
+    };
+    Ok(OperationResponse { success: true, message: message.to_string() })
+}
+
+/// Tauri command to get the cross-lingual indexing configuration
+#[tauri::command]
+pub fn get_cross_lingual_config_command() -> Result<CrossLingualConfig, String> {
+    Ok(get_cross_lingual_config())
+}
+
+/// Tauri command to choose whether English documents are also indexed for cross-lingual search
+#[tauri::command]
+pub async fn set_cross_lingual_config_command(config: CrossLingualConfig, app_handle: AppHandle) -> Result<OperationResponse, String> {
+    info!("Request to update cross-lingual configuration: {:?}", config);
+    set_cross_lingual_config(config);
+    persist_settings(&app_handle).await;
+    Ok(OperationResponse {
+        success: true,
+        message: "Cross-lingual configuration updated. Re-index folders to apply it.".to_string(),
+    })
+}
+
+/// Saves the configurations after a change so they still apply after a restart. A failure
+/// only loses the change on restart, so it is logged rather than returned.
+async fn persist_settings(app_handle: &AppHandle) {
+    if let Err(e) = save_settings(app_handle).await {
+        error!("Failed to save settings: {}", e);
+    }
+}
+
 /// Response model for vector database statistics
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VectorDbStatsResponse {
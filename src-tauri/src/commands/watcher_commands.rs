@@ -1,3 +1,6 @@
 // src-tauri/src/commands/watcher_commands.rs
 
-pub use crate::watcher::{get_watcher_status, start_watcher_command, stop_watcher_command};
\ No newline at end of file
+pub use crate::watcher::{
+    add_watched_root_command, get_watched_roots_command, get_watcher_config_command, get_watcher_status,
+    remove_watched_root_command, set_watcher_config_command, start_watcher_command, stop_watcher_command,
+};
@@ -1,6 +1,6 @@
 // src-tauri/src/core/indexer.rs
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use log::{info, warn, error, debug};
 use serde::{Serialize, Deserialize};
 use crate::db::{
@@ -8,22 +8,37 @@ use crate::db::{
     open_or_create_text_table, 
     open_or_create_image_table,
     open_or_create_amharic_text_table, // Added for Amharic
-    upsert_document, 
-    upsert_amharic_document, // Added for Amharic
+    upsert_document_chunks,
+    upsert_amharic_document_chunks, // Added for Amharic
+    append_document_chunks,
+    append_amharic_document_chunks,
+    delete_document,
+    delete_documents_with_prefix,
     upsert_image
 };
-use crate::embedder::embed_text;
+use crate::chunker::{chunk_page, get_chunking_config, ChunkingConfig, TextChunk};
+use crate::embedder::{embed_chunks, embed_chunks_multilingual, embed_extraction, get_cross_lingual_config};
 use crate::image_embedder::embed_image;
 use crate::extractor::{
     extract_text, 
-    calculate_hash, 
-    process_image, 
+    process_image, read_image_metadata,
     calculate_file_hash, 
     get_content_type, 
+    detect_language_with_confidence,
+    assign_chunk_languages,
+    LanguageDetection,
+    is_paged_document,
+    PdfPageReceiver,
+    PAGED_EXTRACTION_TIMEOUT,
+    extract_archive,
+    extract_image_text,
+    ARCHIVE_MEMBER_SEPARATOR,
     ContentType,
-    DetectedLanguage
+    DetectedLanguage,
+    ExtractorError,
+    TextExtractionResult
 };
-use walkdir::WalkDir;
+use walkdir::{DirEntry, WalkDir};
 use std::time::Instant;
 use std::sync::{RwLock, Arc};
 use tokio::task;
@@ -52,6 +67,46 @@ pub const EXCLUDED_PATTERNS: &[&str] = &[
     ".plugin"
 ];
 
+/// Whether folder walks skip the entry at `path`: hidden files and folders, excluded
+/// folders and application bundles. Indexing, the watcher and the filename index all walk
+/// with this rule, so they agree on which files a folder holds.
+pub fn is_excluded_path(path: &Path, is_dir: bool) -> bool {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    if name.starts_with('.') {
+        return true;
+    }
+    is_dir
+        && (EXCLUDED_DIRS.contains(&name.as_ref()) || EXCLUDED_PATTERNS.iter().any(|pattern| name.ends_with(pattern)))
+}
+
+/// `WalkDir::filter_entry` predicate applying `is_excluded_path`; the walked folder itself
+/// is always entered
+pub fn is_walked_entry(entry: &DirEntry) -> bool {
+    entry.depth() == 0 || !is_excluded_path(entry.path(), entry.file_type().is_dir())
+}
+
+/// Number of chunks embedded and written together when streaming a paged document
+const PAGED_EMBEDDING_BATCH_SIZE: usize = 32;
+
+/// Characters of leading text used to detect the language of a paged document
+const PAGED_LANGUAGE_SAMPLE_CHARS: usize = 2000;
+
+/// Number of extracted archive members that may wait to be stored
+const ARCHIVE_MEMBER_CHANNEL_CAPACITY: usize = 4;
+
+/// A file that could not be indexed and why
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailedFile {
+    pub file_path: String,
+    pub reason: String,
+}
+
+impl FailedFile {
+    pub fn new(file_path: &Path, reason: impl Into<String>) -> Self {
+        FailedFile { file_path: file_path.to_string_lossy().to_string(), reason: reason.into() }
+    }
+}
+
 /// Indexing status information with separate counters for text and image files
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IndexingStats {
@@ -73,7 +128,7 @@ pub struct IndexingStats {
     pub image_files_failed: u32,
     
     pub indexed_files: Vec<String>,
-    pub failed_files: Vec<String>,
+    pub failed_files: Vec<FailedFile>,
 }
 
 // Static variable to store the last indexing statistics
@@ -89,17 +144,20 @@ fn set_last_indexing_stats(stats: IndexingStats) {
     *LAST_INDEXING_STATS.write().unwrap() = Some(stats.clone());
 }
 
-/// Index the macOS Downloads folder at application startup
-pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
-    let start_time = Instant::now();
-    
-    // Get the Downloads folder path for macOS
+/// The Downloads folder in the home directory
+pub fn downloads_folder() -> Result<PathBuf, String> {
     let home_dir = dirs::home_dir().ok_or_else(|| {
         error!("Could not find home directory");
         "Failed to find home directory".to_string()
     })?;
+    Ok(home_dir.join("Downloads"))
+}
+
+/// Index the macOS Downloads folder at application startup
+pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
+    let start_time = Instant::now();
     
-    let downloads_dir = home_dir.join("Downloads");
+    let downloads_dir = downloads_folder()?;
     println!("Starting Downloads folder indexing: {}", downloads_dir.display());
     
     info!("Starting Downloads folder indexing: {}", downloads_dir.display());
@@ -157,36 +215,7 @@ pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
     for entry in WalkDir::new(&downloads_dir)
         .follow_links(false)
         .into_iter()
-        .filter_entry(|e| {
-            // Skip hidden files and directories
-            if let Some(file_name) = e.file_name().to_str() {
-                if file_name.starts_with(".") {
-                    return false;
-                }
-            }
-            
-            // Skip directories in the excluded list
-            if e.file_type().is_dir() {
-                if let Some(dir_name) = e.file_name().to_str() {
-                    if EXCLUDED_DIRS.iter().any(|excluded| dir_name.contains(excluded)) {
-                        debug!("Skipping excluded directory: {}", e.path().display());
-                        return false;
-                    }
-                }
-            }
-            
-            // Skip macOS application bundles and system extensions
-            if e.path().is_dir() {
-                if let Some(path_str) = e.path().to_str() {
-                    if EXCLUDED_PATTERNS.iter().any(|pattern| path_str.contains(pattern)) {
-                        debug!("Skipping macOS bundle: {}", e.path().display());
-                        return false;
-                    }
-                }
-            }
-            
-            true
-        }) {
+        .filter_entry(is_walked_entry) {
         match entry {
             Ok(entry) => {
                 let path = entry.path();
@@ -201,7 +230,7 @@ pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
                 // Determine content type and process accordingly
                 let content_type = get_content_type(path);
                 match content_type {
-                    ContentType::Text => {
+                    ContentType::Text | ContentType::Code => {
                         text_files_processed += 1;
                         println!("text files {}", text_files_processed);
                         println!("path {:?}", path);
@@ -211,7 +240,7 @@ pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
                             error!("Error processing text file {}: {}", path.display(), e);
                             files_failed += 1;
                             text_files_failed += 1;
-                            failed_files.push(path.to_string_lossy().to_string());
+                            failed_files.push(FailedFile::new(path, e));
                         } else {
                             info!("Indexed text file: {}", path.display());
                             db_inserts += 1;
@@ -229,13 +258,35 @@ pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
                             error!("Error processing image file {}: {}", path.display(), e);
                             files_failed += 1;
                             image_files_failed += 1;
-                            failed_files.push(path.to_string_lossy().to_string());
+                            failed_files.push(FailedFile::new(path, e));
                         } else {
                             info!("Indexed image file: {}", path.display());
                             db_inserts += 1;
                             image_files_indexed += 1;
                             indexed_files.push(path.to_string_lossy().to_string());
                         }
+                        if let Err(e) = index_image_text(path, &text_table, &amharic_text_table).await {
+                            warn!("Error indexing text of image {}: {}", path.display(), e);
+                        }
+                    },
+                    ContentType::Archive => {
+                        match index_archive(path, &text_table, &amharic_text_table).await {
+                            Ok((indexed, failed)) => {
+                                text_files_processed += (indexed.len() + failed.len()) as u32;
+                                text_files_indexed += indexed.len() as u32;
+                                db_inserts += indexed.len() as u32;
+                                files_failed += failed.len() as u32;
+                                text_files_failed += failed.len() as u32;
+                                indexed_files.extend(indexed);
+                                failed_files.extend(failed);
+                            }
+                            Err(e) => {
+                                error!("Error processing archive {}: {}", path.display(), e);
+                                files_failed += 1;
+                                text_files_failed += 1;
+                                failed_files.push(FailedFile::new(path, e));
+                            }
+                        }
                     },
                     ContentType::Unsupported => {
                         debug!("Skipping unsupported file type: {}", path.display());
@@ -295,43 +346,440 @@ pub async fn index_downloads_folder() -> Result<IndexingStats, String> {
     Ok(stats)
 }
 
+/// Runs a blocking extractor on tokio's blocking thread pool, so waiting out the
+/// extractor's timeout never stalls the async executor
+async fn extract_blocking<T: Send + 'static>(
+    file_path: &Path,
+    extract: fn(&Path) -> Result<T, ExtractorError>,
+) -> Result<T, ExtractorError> {
+    let worker_path = file_path.to_path_buf();
+    tokio::task::spawn_blocking(move || extract(&worker_path))
+        .await
+        .map_err(|e| ExtractorError::BackendPanicked(file_path.display().to_string(), e.to_string()))?
+}
+
 /// Process a text file for indexing - used by the single-threaded version
 async fn process_text_file(file_path: &Path, text_table: &lancedb::Table, amharic_text_table: &lancedb::Table) -> Result<(), String> {
+    if is_paged_document(file_path) {
+        return index_paged_document(file_path, text_table, amharic_text_table).await.map(|_| ());
+    }
+
     // Extract text content from the file
-    let extraction_result = extract_text(file_path).map_err(|e| format!("Failed to extract text: {}", e))?;
-    
-    // Calculate content hash
-    let content_hash = calculate_hash(&extraction_result.text);
+    let extraction_result = extract_blocking(file_path, extract_text)
+        .await
+        .map_err(|e| format!("Failed to extract text: {}", e))?;
     
-    // Get embeddings for the content
-    let content_vec = vec![extraction_result.text.clone()];
-    let embeddings = embed_text(&content_vec, &extraction_result.language, false).map_err(|e| {
-        error!("Embedding error for {}: {}", file_path.display(), e);
+    let (content_hash, file_size) = file_version(file_path)?;
+    let file_path_str = file_path.to_string_lossy().to_string();
+    store_extraction(&file_path_str, &content_hash, file_size, &extraction_result, text_table, amharic_text_table).await
+}
+
+/// Index one file with the same pipeline `index_folder` uses for it: paged documents
+/// page by page, archives member by member, images as an image embedding plus the text
+/// recognized in them, other text and code files in one pass.
+/// Used to apply changes reported by the file watcher.
+pub async fn index_file(
+    file_path: &Path,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+    image_table: &lancedb::Table,
+) -> Result<(), String> {
+    match get_content_type(file_path) {
+        ContentType::Text | ContentType::Code => process_text_file(file_path, text_table, amharic_text_table).await,
+        ContentType::Image => {
+            process_image_file(file_path, image_table).await?;
+            match index_image_text(file_path, text_table, amharic_text_table).await {
+                Ok(true) => {}
+                // No text in this version of the image: drop what an earlier version had
+                Ok(false) => {
+                    let file_path_str = file_path.to_string_lossy();
+                    for table in [text_table, amharic_text_table] {
+                        if let Err(e) = delete_document(table, &file_path_str).await {
+                            warn!("Failed to remove previous text of image {}: {}", file_path.display(), e);
+                        }
+                    }
+                }
+                Err(e) => warn!("Error indexing text of image {}: {}", file_path.display(), e),
+            }
+            Ok(())
+        }
+        ContentType::Archive => {
+            let (_, failed) = index_archive(file_path, text_table, amharic_text_table).await?;
+            if !failed.is_empty() {
+                warn!("{} members of {} could not be indexed", failed.len(), file_path.display());
+            }
+            Ok(())
+        }
+        content_type => Err(format!("Unsupported content type {:?}: {}", content_type, file_path.display())),
+    }
+}
+
+/// Remove a file from every table, together with the members indexed from it if it
+/// was an archive
+pub async fn remove_file(
+    file_path: &Path,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+    image_table: &lancedb::Table,
+) -> Result<(), String> {
+    let file_path_str = file_path.to_string_lossy().to_string();
+    let member_prefix = format!("{}{}", file_path_str, ARCHIVE_MEMBER_SEPARATOR);
+    delete_document(image_table, &file_path_str)
+        .await
+        .map_err(|e| format!("Failed to remove {}: {}", file_path.display(), e))?;
+    for table in [text_table, amharic_text_table] {
+        delete_document(table, &file_path_str)
+            .await
+            .map_err(|e| format!("Failed to remove {}: {}", file_path.display(), e))?;
+        delete_documents_with_prefix(table, &member_prefix)
+            .await
+            .map_err(|e| format!("Failed to remove members of {}: {}", file_path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Remove everything indexed under a folder from every table, e.g. after the folder was
+/// deleted or moved out of a watched root
+pub async fn remove_folder(
+    folder_path: &Path,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+    image_table: &lancedb::Table,
+) -> Result<(), String> {
+    let prefix = format!("{}{}", folder_path.to_string_lossy(), std::path::MAIN_SEPARATOR);
+    for table in [text_table, amharic_text_table, image_table] {
+        delete_documents_with_prefix(table, &prefix)
+            .await
+            .map_err(|e| format!("Failed to remove files under {}: {}", folder_path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Hash and size of a file on disk. Every row indexed from the file stores both, so that
+/// reconciliation can tell whether the file changed without extracting it again.
+fn file_version(file_path: &Path) -> Result<(String, u64), String> {
+    let content_hash = calculate_file_hash(file_path).map_err(|e| format!("File hash calculation failed: {}", e))?;
+    let file_size = std::fs::metadata(file_path)
+        .map_err(|e| format!("Failed to read metadata of {}: {}", file_path.display(), e))?
+        .len();
+    Ok((content_hash, file_size))
+}
+
+/// Chunk, embed and store extracted text under the given (possibly virtual) path.
+/// `content_hash` and `file_size` are those of the file on disk the text came from.
+async fn store_extraction(
+    file_path: &str,
+    content_hash: &str,
+    file_size: u64,
+    extraction_result: &TextExtractionResult,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+) -> Result<(), String> {
+    // Chunk the content and get embeddings for every chunk
+    let extension = Path::new(file_path).extension().and_then(|ext| ext.to_str());
+    let (chunks, embeddings) = embed_extraction(extraction_result, extension).map_err(|e| {
+        error!("Embedding error for {}: {}", file_path, e);
         format!("Embedding generation failed: {}", e)
     })?;
     
     if embeddings.is_empty() {
-        return Err(format!("No embeddings generated for {}", file_path.display()));
+        return Err(format!("No embeddings generated for {}", file_path));
     }
     
-    // Store in the database - now passing all embeddings
+    store_chunks(file_path, content_hash, file_size, &chunks, &embeddings, text_table, amharic_text_table).await
+}
+
+/// Splits the chunks of a document by embedding model: English and undetected chunks go to
+/// the text table, chunks in any other language to the multilingual (Amharic) table
+fn partition_by_table(
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
+) -> ((Vec<TextChunk>, Vec<Vec<f32>>), (Vec<TextChunk>, Vec<Vec<f32>>)) {
+    let mut english = (Vec::new(), Vec::new());
+    let mut multilingual = (Vec::new(), Vec::new());
+    for (chunk, embedding) in chunks.iter().zip(embeddings) {
+        let target = match &chunk.language {
+            Some(language) if language.uses_multilingual_model() => &mut multilingual,
+            _ => &mut english,
+        };
+        target.0.push(chunk.clone());
+        target.1.push(embedding.clone());
+    }
+    (english, multilingual)
+}
+
+/// When cross-lingual indexing is enabled, English chunks are also embedded with the
+/// multilingual model and written to the multilingual table, which cross-lingual queries search
+fn add_cross_lingual_copies(
+    english: &(Vec<TextChunk>, Vec<Vec<f32>>),
+    multilingual: &mut (Vec<TextChunk>, Vec<Vec<f32>>),
+) -> Result<(), String> {
+    if english.0.is_empty() || !get_cross_lingual_config().index_english {
+        return Ok(());
+    }
+    let embeddings = embed_chunks_multilingual(&english.0).map_err(|e| format!("Embedding generation failed: {}", e))?;
+    multilingual.0.extend(english.0.iter().cloned());
+    multilingual.1.extend(embeddings);
+    Ok(())
+}
+
+/// Writes the chunks of a document to the tables of their languages, replacing the previous
+/// version of the document in both tables (its language mix may have changed)
+pub(crate) async fn store_chunks(
+    file_path: &str,
+    content_hash: &str,
+    file_size: u64,
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+) -> Result<(), String> {
+    let (english, mut multilingual) = partition_by_table(chunks, embeddings);
+    add_cross_lingual_copies(&english, &mut multilingual)?;
+    let (english_chunks, english_embeddings) = english;
+    let (multilingual_chunks, multilingual_embeddings) = multilingual;
+
+    if english_chunks.is_empty() {
+        let _ = delete_document(text_table, file_path).await;
+    } else {
+        upsert_document_chunks(text_table, file_path, content_hash, Some(file_size), &english_chunks, &english_embeddings).await.map_err(|e| {
+            error!("Database error (English) for {}: {}", file_path, e);
+            format!("Database upsert failed: {}", e)
+        })?;
+    }
+    if multilingual_chunks.is_empty() {
+        let _ = delete_document(amharic_text_table, file_path).await;
+    } else {
+        upsert_amharic_document_chunks(amharic_text_table, file_path, content_hash, Some(file_size), &multilingual_chunks, &multilingual_embeddings).await.map_err(|e| {
+            error!("Database error (multilingual) for {}: {}", file_path, e);
+            format!("Database upsert failed: {}", e)
+        })?;
+    }
+    debug!(
+        "Stored {} English and {} multilingual chunks of {}",
+        english_chunks.len(), multilingual_chunks.len(), file_path
+    );
+    Ok(())
+}
+
+/// Index the text recognized in an image (OCR) as text chunks of the image file.
+/// Does nothing when OCR is disabled; returns whether any text was indexed.
+pub async fn index_image_text(
+    file_path: &Path,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+) -> Result<bool, String> {
+    let Some(extraction_result) = extract_blocking(file_path, extract_image_text)
+        .await
+        .map_err(|e| format!("OCR failed: {}", e))?
+    else {
+        return Ok(false);
+    };
+    let (content_hash, file_size) = file_version(file_path)?;
+    let file_path_str = file_path.to_string_lossy().to_string();
+    store_extraction(&file_path_str, &content_hash, file_size, &extraction_result, text_table, amharic_text_table).await?;
+    info!("Indexed recognized text of image {}", file_path.display());
+    Ok(true)
+}
+
+/// Index the supported members of a ZIP or tar archive under virtual paths such as
+/// `bundle.zip!/docs/spec.pdf`. Members indexed from an earlier version of the archive
+/// are removed first. Members are extracted on the blocking thread pool and stored one
+/// at a time as they arrive, each with the hash and size of the archive itself.
+/// Returns the virtual paths indexed and the members that failed.
+pub async fn index_archive(
+    file_path: &Path,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+) -> Result<(Vec<String>, Vec<FailedFile>), String> {
+    let (content_hash, file_size) = file_version(file_path)?;
+    let member_prefix = format!("{}{}", file_path.to_string_lossy(), ARCHIVE_MEMBER_SEPARATOR);
+    for table in [text_table, amharic_text_table] {
+        if let Err(e) = delete_documents_with_prefix(table, &member_prefix).await {
+            warn!("Failed to remove previous members of {}: {}", file_path.display(), e);
+        }
+    }
+
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(ARCHIVE_MEMBER_CHANNEL_CAPACITY);
+    let archive_path = file_path.to_path_buf();
+    let reader = tokio::task::spawn_blocking(move || {
+        extract_archive(&archive_path, |member| {
+            // The receiver only goes away when indexing the archive was abandoned
+            let _ = sender.blocking_send(member);
+        })
+    });
+
+    let mut indexed = Vec::new();
+    let mut failed = Vec::new();
+    while let Some(member) = receiver.recv().await {
+        let result = match member.extraction {
+            Ok(extraction) => {
+                store_extraction(&member.virtual_path, &content_hash, file_size, &extraction, text_table, amharic_text_table).await
+            }
+            Err(e) => Err(format!("Failed to extract text: {}", e)),
+        };
+        match result {
+            Ok(()) => indexed.push(member.virtual_path),
+            Err(reason) => {
+                warn!("Failed to index archive member {}: {}", member.virtual_path, reason);
+                failed.push(FailedFile { file_path: member.virtual_path, reason });
+            }
+        }
+    }
+    reader
+        .await
+        .map_err(|e| format!("Failed to read archive: {}", e))?
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+    info!("Indexed {} members of archive {} ({} failed)", indexed.len(), file_path.display(), failed.len());
+    Ok((indexed, failed))
+}
+
+/// Index a paged document (PDF) page by page.
+///
+/// Pages are streamed from the extractor on a worker thread, chunked with their page
+/// number and embedded in batches of `PAGED_EMBEDDING_BATCH_SIZE`, so the whole text of
+/// a long document is indexed without ever holding it in memory. A page that takes
+/// longer than `PAGED_EXTRACTION_TIMEOUT` fails the document; the rows of a failed document
+/// are removed, so it is indexed again rather than left partly indexed.
+/// Returns the number of chunks written.
+pub async fn index_paged_document(
+    file_path: &Path,
+    text_table: &lancedb::Table,
+    amharic_text_table: &lancedb::Table,
+) -> Result<usize, String> {
+    // Hash the file bytes; the content hash is only known after the last page otherwise
+    let (content_hash, file_size) = file_version(file_path)?;
+    let (metadata, mut pages) = PdfPageReceiver::open(file_path, PAGED_EXTRACTION_TIMEOUT)
+        .await
+        .map_err(|e| format!("Failed to extract text: {}", e))?;
+
+    // Every page is indexed, so the per-document chunk cap does not apply
+    let config = ChunkingConfig { max_chunks: None, ..get_chunking_config() };
+    let extension = file_path.extension().and_then(|ext| ext.to_str());
     let file_path_str = file_path.to_string_lossy().to_string();
-    match extraction_result.language {
-        DetectedLanguage::English | DetectedLanguage::Other => {
-            upsert_document(text_table, &file_path_str, &content_hash, &embeddings).await.map_err(|e| {
-                error!("Database error (English/Other) for {}: {}", file_path.display(), e);
-                format!("Database upsert failed: {}", e)
-            })?;
+
+    // Replace the previous version of the document; batches are appended after this
+    for table in [text_table, amharic_text_table] {
+        delete_document(table, &file_path_str)
+            .await
+            .map_err(|e| format!("Failed to remove previous version of {}: {}", file_path.display(), e))?;
+    }
+
+    let mut writer = PagedDocumentWriter {
+        file_path: &file_path_str,
+        content_hash: &content_hash,
+        file_size,
+        text_table,
+        amharic_text_table,
+        document_language: None,
+        english_chunks_written: 0,
+        multilingual_chunks_written: 0,
+    };
+    let written: Result<(), String> = async {
+        let mut language_sample = String::new();
+        let mut pending: Vec<TextChunk> = Vec::new();
+
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| format!("Failed to extract text: {}", e))?;
+            // Token chunks are sized for the model that embeds the language of the page
+            let chunks = chunk_page(&page.text, page.page_number, extension, &config.for_text(&page.text))
+                .map_err(|e| format!("Chunking failed on page {}: {}", page.page_number, e))?;
+            pending.extend(chunks.into_iter().map(|chunk| TextChunk { metadata: metadata.clone(), ..chunk }));
+
+            // Chunks too short to detect take the document language, so hold them back until
+            // there is enough text to detect it
+            if writer.document_language.is_none() {
+                language_sample.push_str(&page.text);
+                if language_sample.chars().count() >= PAGED_LANGUAGE_SAMPLE_CHARS {
+                    writer.document_language = Some(detect_language_with_confidence(&language_sample));
+                    language_sample = String::new();
+                }
+            }
+
+            if writer.document_language.is_some() && pending.len() >= PAGED_EMBEDDING_BATCH_SIZE {
+                writer.write_batch(&mut pending).await?;
+                pending.clear();
+            }
+        }
+
+        if writer.document_language.is_none() {
+            writer.document_language = Some(detect_language_with_confidence(&language_sample));
         }
-        DetectedLanguage::Amharic => {
-            upsert_amharic_document(amharic_text_table, &file_path_str, &content_hash, &embeddings).await.map_err(|e| {
-                error!("Database error (Amharic) for {}: {}", file_path.display(), e);
-                format!("Database upsert failed: {}", e)
-            })?;
+        for batch in pending.chunks_mut(PAGED_EMBEDDING_BATCH_SIZE) {
+            writer.write_batch(batch).await?;
         }
+        Ok(())
+    }
+    .await;
+    if let Err(e) = written {
+        // The pages written so far carry the real hash and size of the file, so reconciliation
+        // would take them for the whole document: remove them
+        for table in [text_table, amharic_text_table] {
+            if let Err(delete_error) = delete_document(table, &file_path_str).await {
+                warn!("Failed to remove partial index of {}: {}", file_path.display(), delete_error);
+            }
+        }
+        return Err(e);
+    }
+
+    let chunks_written = writer.english_chunks_written + writer.multilingual_chunks_written;
+    if chunks_written == 0 {
+        return Err(format!("No text found in {}", file_path.display()));
+    }
+    info!(
+        "Indexed {} chunks ({} English, {} multilingual) from paged document {}",
+        chunks_written, writer.english_chunks_written, writer.multilingual_chunks_written, file_path.display()
+    );
+    Ok(chunks_written)
+}
+
+/// Writes the chunks of a paged document in batches, each chunk to the table of its language
+struct PagedDocumentWriter<'a> {
+    file_path: &'a str,
+    content_hash: &'a str,
+    file_size: u64,
+    text_table: &'a lancedb::Table,
+    amharic_text_table: &'a lancedb::Table,
+    document_language: Option<LanguageDetection>,
+    english_chunks_written: usize,
+    multilingual_chunks_written: usize,
+}
+
+impl PagedDocumentWriter<'_> {
+    async fn write_batch(&mut self, chunks: &mut [TextChunk]) -> Result<(), String> {
+        let document_language = self
+            .document_language
+            .clone()
+            .unwrap_or(LanguageDetection { language: DetectedLanguage::Other, confidence: 0.0 });
+        assign_chunk_languages(chunks, &document_language);
+        let embeddings = embed_chunks(chunks, &document_language.language).map_err(|e| {
+            error!("Embedding error for {}: {}", self.file_path, e);
+            format!("Embedding generation failed: {}", e)
+        })?;
+
+        let (english, mut multilingual) = partition_by_table(chunks, &embeddings);
+        add_cross_lingual_copies(&english, &mut multilingual)?;
+        let (english_chunks, english_embeddings) = english;
+        let (multilingual_chunks, multilingual_embeddings) = multilingual;
+        let result = async {
+            append_document_chunks(
+                self.text_table, self.file_path, self.content_hash, Some(self.file_size),
+                &english_chunks, &english_embeddings, self.english_chunks_written,
+            ).await?;
+            append_amharic_document_chunks(
+                self.amharic_text_table, self.file_path, self.content_hash, Some(self.file_size),
+                &multilingual_chunks, &multilingual_embeddings, self.multilingual_chunks_written,
+            ).await
+        }.await;
+        result.map_err(|e| {
+            error!("Database error for {}: {}", self.file_path, e);
+            format!("Database upsert failed: {}", e)
+        })?;
+
+        self.english_chunks_written += english_chunks.len();
+        self.multilingual_chunks_written += multilingual_chunks.len();
+        Ok(())
     }
-    
-    Ok(())
 }
 
 /// Process an image file for indexing - used by the single-threaded version
@@ -342,10 +790,10 @@ async fn process_image_file(file_path: &Path, table: &lancedb::Table) -> Result<
         format!("Image processing failed: {}", e)
     })?;
     
-    // Calculate file hash for the image
-    let file_hash = calculate_file_hash(file_path).map_err(|e| {
+    // Calculate file hash and size for the image
+    let (file_hash, file_size) = file_version(file_path).map_err(|e| {
         error!("Hashing error for {}: {}", file_path.display(), e);
-        format!("File hash calculation failed: {}", e)
+        e
     })?;
     
     // Generate embedding for the image
@@ -357,19 +805,17 @@ async fn process_image_file(file_path: &Path, table: &lancedb::Table) -> Result<
     // Store in the database
     let file_path_str = file_path.to_string_lossy().to_string();
     
-    // For now, we don't have image dimensions or thumbnails
-    // These could be added in a future enhancement
-    let width: Option<i32> = None;
-    let height: Option<i32> = None;
+    // Dimensions and EXIF data (capture date, camera, GPS); thumbnails are not generated yet
+    let metadata = read_image_metadata(file_path);
     let thumbnail_path: Option<&str> = None;
     
     upsert_image(
         table, 
         &file_path_str, 
         &file_hash, 
+        Some(file_size),
         &embedding, 
-        width, 
-        height, 
+        &metadata, 
         thumbnail_path
     ).await.map_err(|e| {
         error!("Database error for {}: {}", file_path.display(), e);
@@ -380,47 +826,32 @@ async fn process_image_file(file_path: &Path, table: &lancedb::Table) -> Result<
 }
 
 /// Handle text file indexing with a batch of files in a separate thread
-async fn handle_specific_language_text_indexing(
-    text_data_batch: Vec<(String, String, Vec<Vec<f32>>)>, // path_str, content_hash, embeddings
-    table: Arc<lancedb::Table>,
-    language_name_for_log: &str // e.g., "English/Other" or "Amharic"
+async fn handle_text_indexing(
+    text_data_batch: Vec<(String, String, u64, Vec<TextChunk>, Vec<Vec<f32>>)>, // path_str, content_hash, file_size, chunks, embeddings
+    text_table: Arc<lancedb::Table>,
+    amharic_text_table: Arc<lancedb::Table>,
 ) -> HashMap<String, Result<(), String>> {
     let mut results = HashMap::new();
 
-    // The input `text_data_batch` is Vec<(String, String, Vec<Vec<f32>>)>
-    // representing (path_str, content_hash, embeddings)
-
-    // Process files in batches (e.g., 10 at a time) to manage concurrency for DB operations
-    // Each item in text_data_batch is already processed for extraction and embedding.
+    // Process files in batches (e.g., 10 at a time) to manage concurrency for DB operations.
+    // Each chunk already carries its language, which decides the table it is written to.
     for batch_chunk in text_data_batch.chunks(10) {
-        let mut mut_futures = Vec::new(); // Renamed from futures to avoid conflict if std::future::futures is in scope
-        for (file_path_str, content_hash, embeddings) in batch_chunk {
-            // Clone Arcs and owned Strings for the async move block
-            let table_clone = Arc::clone(&table);
-            let path_str_clone = file_path_str.clone();
-            let hash_clone = content_hash.clone();
-            let embeddings_clone = embeddings.clone(); // Vec<Vec<f32>> can be cloned
-            let lang_log_clone = language_name_for_log.to_string(); // Clone for async move
-
+        let mut mut_futures = Vec::new();
+        for (file_path_str, content_hash, file_size, chunks, embeddings) in batch_chunk {
+            let text_table = Arc::clone(&text_table);
+            let amharic_text_table = Arc::clone(&amharic_text_table);
             mut_futures.push(async move {
-                let upsert_result = upsert_document(
-                    &table_clone,
-                    &path_str_clone,
-                    &hash_clone,
-                    &embeddings_clone,
+                let upsert_result = store_chunks(
+                    file_path_str,
+                    content_hash,
+                    *file_size,
+                    chunks,
+                    embeddings,
+                    &text_table,
+                    &amharic_text_table,
                 )
-                .await
-                .map_err(|e| {
-                    error!(
-                        "Database error for {} file {}: {}",
-                        lang_log_clone, path_str_clone, e
-                    );
-                    format!(
-                        "Database upsert failed for {} file {}: {}",
-                        lang_log_clone, path_str_clone, e
-                    )
-                });
-                (path_str_clone, upsert_result) // Return path and result for HashMap
+                .await;
+                (file_path_str.clone(), upsert_result) // Return path and result for HashMap
             });
         }
 
@@ -464,11 +895,13 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
     info!("Excluding system folders and application bundles from indexing");
     
     // Initialize file lists for parallel processing
-    let mut english_text_data_to_process: Vec<(String, String, Vec<Vec<f32>>)> = Vec::new(); // Path, Hash, Embeddings
-    let mut amharic_text_data_to_process: Vec<(String, String, Vec<Vec<f32>>)> = Vec::new(); // Path, Hash, Embeddings
+    let mut text_data_to_process: Vec<(String, String, u64, Vec<TextChunk>, Vec<Vec<f32>>)> = Vec::new(); // Path, Hash, Size, Chunks, Embeddings
     let mut image_files: Vec<String> = Vec::new(); // Paths for images
     let mut files_skipped = 0;
     let mut files_failed_preprocessing = 0; // Added for errors during initial scan/extraction/embedding
+    let mut files_indexed_during_scan = 0; // Paged documents and archive members are written straight into the tables during the scan
+    let mut indexed_files: Vec<String> = Vec::new();
+    let mut failed_files: Vec<FailedFile> = Vec::new();
     
     // Open connection to database
     let conn = connect_db().await.map_err(|e| {
@@ -502,36 +935,7 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
     for entry in WalkDir::new(path)
         .follow_links(false)
         .into_iter()
-        .filter_entry(|e| {
-            // Skip hidden files and directories
-            if let Some(file_name) = e.file_name().to_str() {
-                if file_name.starts_with(".") {
-                    return false;
-                }
-            }
-            
-            // Skip directories in the excluded list
-            if e.file_type().is_dir() {
-                if let Some(dir_name) = e.file_name().to_str() {
-                    if EXCLUDED_DIRS.iter().any(|excluded| dir_name.contains(excluded)) {
-                        debug!("Skipping excluded directory: {}", e.path().display());
-                        return false;
-                    }
-                }
-            }
-            
-            // Skip macOS application bundles and system extensions
-            if e.path().is_dir() {
-                if let Some(path_str) = e.path().to_str() {
-                    if EXCLUDED_PATTERNS.iter().any(|pattern| path_str.contains(pattern)) {
-                        debug!("Skipping macOS bundle: {}", e.path().display());
-                        return false;
-                    }
-                }
-            }
-            
-            true
-        }) {
+        .filter_entry(is_walked_entry) {
         match entry {
             Ok(entry) => {
                 let path = entry.path();
@@ -544,53 +948,79 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
                 // Determine content type and add to appropriate list
                 let content_type = get_content_type(path);
                 match content_type {
-                    ContentType::Text => {
+                    ContentType::Text if is_paged_document(path) => {
+                        match index_paged_document(path, &text_table_arc, &amharic_text_table_arc).await {
+                            Ok(_) => {
+                                files_indexed_during_scan += 1;
+                                indexed_files.push(path.to_string_lossy().to_string());
+                            }
+                            Err(e) => {
+                                error!("Failed to index paged document {}: {}", path.display(), e);
+                                files_failed_preprocessing += 1;
+                                failed_files.push(FailedFile::new(path, e));
+                            }
+                        }
+                    },
+                    ContentType::Text | ContentType::Code => {
                         let file_path_display = path.display().to_string(); // For logging
-                        match extract_text(path) {
+                        match extract_blocking(path, extract_text).await {
                             Ok(extraction_result) => {
-                                let content_hash = calculate_hash(&extraction_result.text);
-                                // embed_text expects Vec<String>, even if it's just one document
-                                let content_for_embedding = vec![extraction_result.text.clone()]; 
-                                match embed_text(&content_for_embedding, &extraction_result.language, false) {
-                                    Ok(embeddings) => {
-                                        // embed_text returns Vec<Vec<f32>>, one inner Vec per input string
+                                let (content_hash, file_size) = match file_version(path) {
+                                    Ok(version) => version,
+                                    Err(e) => {
+                                        error!("Failed to hash {}: {}", file_path_display, e);
+                                        files_failed_preprocessing += 1;
+                                        failed_files.push(FailedFile::new(path, e));
+                                        continue;
+                                    }
+                                };
+                                let extension = path.extension().and_then(|ext| ext.to_str());
+                                match embed_extraction(&extraction_result, extension) {
+                                    Ok((chunks, embeddings)) => {
+                                        // One embedding per chunk, each stored as its own row
                                         if embeddings.is_empty() || embeddings[0].is_empty() {
                                             error!("No embeddings generated for text file: {}", file_path_display);
                                             files_failed_preprocessing += 1;
+                                            failed_files.push(FailedFile::new(path, "No embeddings generated"));
                                         } else {
-                                            // We passed one string, so we expect one Vec<f32> in the outer Vec.
-                                            // The db upsert functions expect &[Vec<f32>], which is effectively Vec<Vec<f32>> for multiple chunks of ONE document.
-                                            // Here, embeddings IS Vec<Vec<f32>> where the outer Vec corresponds to input strings (1 here) 
-                                            // and inner Vec<f32> is the embedding for that string. 
-                                            // If chunking were implemented in embed_text, 'embeddings' would be Vec<Vec<f32>> where each inner Vec is an embedding for a chunk.
-                                            // For now, assume embed_text returns one embedding for the whole text if not chunked internally.
-                                            // The db functions (upsert_document, upsert_amharic_document) take &[Vec<f32>] where each Vec<f32> is an embedding for a chunk.
-                                            // So, 'embeddings' from embed_text (which is Vec<Vec<f32>>) fits this directly.
-                                            let data_tuple = (path.to_string_lossy().to_string(), content_hash, embeddings);
-                                            match extraction_result.language {
-                                                DetectedLanguage::English | DetectedLanguage::Other => {
-                                                    english_text_data_to_process.push(data_tuple);
-                                                }
-                                                DetectedLanguage::Amharic => {
-                                                    amharic_text_data_to_process.push(data_tuple);
-                                                }
-                                            }
+                                            let data_tuple = (path.to_string_lossy().to_string(), content_hash, file_size, chunks, embeddings);
+                                            text_data_to_process.push(data_tuple);
                                         }
                                     }
                                     Err(e) => {
                                         error!("Failed to embed text for {}: {}", file_path_display, e);
                                         files_failed_preprocessing += 1;
+                                        failed_files.push(FailedFile::new(path, format!("Embedding generation failed: {}", e)));
                                     }
                                 }
                             }
                             Err(e) => {
                                 error!("Failed to extract text from {}: {}", file_path_display, e);
                                 files_failed_preprocessing += 1;
+                                failed_files.push(FailedFile::new(path, format!("Failed to extract text: {}", e)));
                             }
                         }
                     },
                     ContentType::Image => {
                         image_files.push(path.to_string_lossy().to_string());
+                        if let Err(e) = index_image_text(path, &text_table_arc, &amharic_text_table_arc).await {
+                            warn!("Failed to index text of image {}: {}", path.display(), e);
+                        }
+                    },
+                    ContentType::Archive => {
+                        match index_archive(path, &text_table_arc, &amharic_text_table_arc).await {
+                            Ok((indexed, failed)) => {
+                                files_indexed_during_scan += indexed.len() as u32;
+                                files_failed_preprocessing += failed.len() as u32;
+                                indexed_files.extend(indexed);
+                                failed_files.extend(failed);
+                            }
+                            Err(e) => {
+                                error!("Failed to index archive {}: {}", path.display(), e);
+                                files_failed_preprocessing += 1;
+                                failed_files.push(FailedFile::new(path, e));
+                            }
+                        }
                     },
                     ContentType::Unsupported => {
                         debug!("Skipping unsupported file type: {}", path.display());
@@ -605,33 +1035,23 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
     }
     
     // Log collection summary
-    info!("Found {} English/Other text items, {} Amharic text items, and {} image files to process. {} files failed pre-processing.", 
-          english_text_data_to_process.len(), amharic_text_data_to_process.len(), image_files.len(), files_failed_preprocessing);
+    info!("Found {} text items and {} image files to process. {} files failed pre-processing.", 
+          text_data_to_process.len(), image_files.len(), files_failed_preprocessing);
     
     // Second pass: process files in parallel using separate threads
     info!("Starting parallel processing of files...");
 
     // Define futures for each type of processing.
-    let english_text_task_handle;
-    if !english_text_data_to_process.is_empty() {
-        let table_for_task = Arc::clone(&text_table_arc);
-        let data_for_task = english_text_data_to_process.clone(); // Clone data for the task
-        english_text_task_handle = task::spawn(async move {
-            handle_specific_language_text_indexing(data_for_task, table_for_task, "English/Other").await
-        });
-    } else {
-        english_text_task_handle = task::spawn(async move { HashMap::new() }); // Dummy task
-    }
-
-    let amharic_text_task_handle;
-    if !amharic_text_data_to_process.is_empty() {
-        let table_for_task = Arc::clone(&amharic_text_table_arc);
-        let data_for_task = amharic_text_data_to_process.clone(); // Clone data for the task
-        amharic_text_task_handle = task::spawn(async move {
-            handle_specific_language_text_indexing(data_for_task, table_for_task, "Amharic").await
+    let text_task_handle;
+    if !text_data_to_process.is_empty() {
+        let text_table_for_task = Arc::clone(&text_table_arc);
+        let amharic_text_table_for_task = Arc::clone(&amharic_text_table_arc);
+        let data_for_task = text_data_to_process.clone(); // Clone data for the task
+        text_task_handle = task::spawn(async move {
+            handle_text_indexing(data_for_task, text_table_for_task, amharic_text_table_for_task).await
         });
     } else {
-        amharic_text_task_handle = task::spawn(async move { HashMap::new() }); // Dummy task
+        text_task_handle = task::spawn(async move { HashMap::new() }); // Dummy task
     }
 
     let image_task_handle;
@@ -645,12 +1065,10 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
 
     // Wait for all tasks to complete
     let (
-        english_text_join_result,
-        amharic_text_join_result,
+        text_join_result,
         image_join_result
     ) = tokio::join!(
-        english_text_task_handle,
-        amharic_text_task_handle,
+        text_task_handle,
         image_task_handle
     );
 
@@ -659,56 +1077,56 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
     stats.files_skipped = files_skipped; // From the first pass (file categorization)
     // Add failures from the pre-processing (extraction/embedding) stage to text_files_failed
     stats.text_files_failed += files_failed_preprocessing; 
+    stats.text_files_processed += files_indexed_during_scan;
 
-    // Process English text results
-    match english_text_join_result {
+    // Process text results
+    match text_join_result {
         Ok(map) => {
-            for (_path, res) in map {
-                if res.is_ok() {
-                    stats.text_files_processed += 1;
-                } else {
-                    stats.text_files_failed += 1;
+            for (path, res) in map {
+                match res {
+                    Ok(()) => {
+                        stats.text_files_processed += 1;
+                        indexed_files.push(path);
+                    }
+                    Err(reason) => {
+                        stats.text_files_failed += 1;
+                        failed_files.push(FailedFile { file_path: path, reason });
+                    }
                 }
             }
         }
         Err(e) => {
-            error!("English text processing task failed to join: {}", e);
+            error!("Text processing task failed to join: {}", e);
             // If the task itself panicked or was cancelled, count all its intended files as failed.
-            stats.text_files_failed += english_text_data_to_process.len() as u32;
-        }
-    }
-
-    // Process Amharic text results
-    match amharic_text_join_result {
-        Ok(map) => {
-            for (_path, res) in map {
-                if res.is_ok() {
-                    stats.text_files_processed += 1; // Aggregating all text together for now
-                } else {
-                    stats.text_files_failed += 1;    // Aggregating all text together for now
-                }
+            stats.text_files_failed += text_data_to_process.len() as u32;
+            for (path, _, _, _, _) in &text_data_to_process {
+                failed_files.push(FailedFile { file_path: path.clone(), reason: format!("Indexing task failed: {}", e) });
             }
         }
-        Err(e) => {
-            error!("Amharic text processing task failed to join: {}", e);
-            stats.text_files_failed += amharic_text_data_to_process.len() as u32;
-        }
     }
 
     // Process Image results
     match image_join_result {
         Ok(map) => {
-            for (_path, res) in map {
-                if res.is_ok() {
-                    stats.image_files_processed += 1;
-                } else {
-                    stats.image_files_failed += 1;
+            for (path, res) in map {
+                match res {
+                    Ok(()) => {
+                        stats.image_files_processed += 1;
+                        indexed_files.push(path);
+                    }
+                    Err(reason) => {
+                        stats.image_files_failed += 1;
+                        failed_files.push(FailedFile { file_path: path, reason });
+                    }
                 }
             }
         }
         Err(e) => {
             error!("Image processing task failed to join: {}", e);
             stats.image_files_failed += image_files.len() as u32;
+            for path in &image_files {
+                failed_files.push(FailedFile { file_path: path.clone(), reason: format!("Indexing task failed: {}", e) });
+            }
         }
     }
 
@@ -729,8 +1147,8 @@ pub async fn index_folder(folder_path: &str) -> Result<IndexingStats, String> {
         image_files_indexed: stats.image_files_processed, // Assume processed means indexed for now
         image_files_failed: stats.image_files_failed,
         
-        indexed_files: Vec::new(), // Not populated in current parallel logic
-        failed_files: Vec::new(),  // Not populated in current parallel logic
+        indexed_files,
+        failed_files,
     };
 
     set_last_indexing_stats(final_stats.clone());
@@ -787,7 +1205,7 @@ mod tests {
         
         assert_eq!(get_content_type(&no_ext_path), ContentType::Unsupported, "No extension should be Unsupported");
         assert_eq!(get_content_type(&hidden_path), ContentType::Text, "'.hidden.pdf' should be Text as get_content_type checks the '.pdf' extension");
-        assert_eq!(get_content_type(&zip_path), ContentType::Unsupported, "ZIP should be Unsupported");
+        assert_eq!(get_content_type(&zip_path), ContentType::Archive, "ZIP should be Archive");
     }
     
     #[test]
@@ -803,10 +1221,23 @@ mod tests {
         assert_eq!(get_content_type(Path::new("animation.gif")), ContentType::Image);
         
         // Unsupported files
-        assert_eq!(get_content_type(Path::new("archive.zip")), ContentType::Unsupported);
+        assert_eq!(get_content_type(Path::new("archive.zip")), ContentType::Archive);
         assert_eq!(get_content_type(Path::new("unknown")), ContentType::Unsupported);
     }
-    
+
+    #[test]
+    fn test_excluded_paths() {
+        assert!(is_excluded_path(Path::new("/home/user/.config"), true));
+        assert!(is_excluded_path(Path::new("/home/user/notes/.draft.md"), false));
+        assert!(is_excluded_path(Path::new("/home/user/project/node_modules"), true));
+        assert!(is_excluded_path(Path::new("/Applications/Preview.app"), true));
+
+        // Folders are matched by their whole name, not by part of it or of their path
+        assert!(!is_excluded_path(Path::new("/home/user/MyLibrary"), true));
+        assert!(!is_excluded_path(Path::new("/home/user/my.apple/photos"), true));
+        assert!(!is_excluded_path(Path::new("/home/user/Library.txt"), false));
+    }
+
     #[test]
     fn test_create_mock_text_file() {
         let dir = tempdir().unwrap();
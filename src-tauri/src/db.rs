@@ -1,18 +1,21 @@
 // src-tauri/src/db.rs
 
-use arrow_array::{RecordBatch, RecordBatchIterator, StringArray, FixedSizeListArray, TimestampSecondArray, Int32Array};
+use arrow_array::{Array, RecordBatch, RecordBatchIterator, StringArray, FixedSizeListArray, TimestampSecondArray, Int32Array, Int64Array, Float32Array, Float64Array};
 use arrow_array::builder::Float32Builder;
 use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
-use lancedb::{connection::Connection, table::Table, Error as LanceError};
+use lancedb::{connection::Connection, table::{NewColumnTransform, Table}, Error as LanceError};
 use lancedb::query::{QueryBase, ExecutableQuery, Select};
 use futures::TryStreamExt; // For stream operations
-use std::{path::{Path, PathBuf}, sync::Arc};
+use std::{collections::HashMap, path::{Path, PathBuf}, sync::Arc};
 use std::fs;
 use tempfile::TempDir; // Add this line for temporary directory support
 use thiserror::Error;
 use chrono::Utc;
 use log::{info, warn, debug};
 
+use crate::chunker::TextChunk;
+use crate::extractor::ImageMetadata;
+
 use lance_arrow::FixedSizeListArrayExt;
 pub const TEXT_TABLE_NAME: &str = "documents";
 pub const IMAGE_TABLE_NAME: &str = "images";
@@ -68,7 +71,9 @@ pub fn get_db_path() -> Result<PathBuf, DbError> {
     Ok(db_dir)
 }
 
-fn create_amharic_schema() -> SchemaRef {
+/// Create the schema shared by the text chunk tables.
+/// New columns must be nullable and appended at the end so existing tables can be migrated.
+fn create_chunk_schema(embedding_dim: i32) -> SchemaRef {
     Arc::new(Schema::new(vec![
         Field::new("file_path", DataType::Utf8, false),
         Field::new("content_hash", DataType::Utf8, false),
@@ -77,29 +82,40 @@ fn create_amharic_schema() -> SchemaRef {
             "embedding",
             DataType::FixedSizeList(
                 Arc::new(Field::new("item", DataType::Float32, true)),
-                AMHARIC_EMBEDDING_DIM,
+                embedding_dim,
             ),
             true,
         ),
         Field::new("last_modified", DataType::Timestamp(TimeUnit::Second, None), false),
+        Field::new("heading_path", DataType::Utf8, true), // Markdown section breadcrumb
+        Field::new("page_number", DataType::Int32, true), // Page of paged documents (PDF)
+        Field::new("sheet_name", DataType::Utf8, true), // Worksheet of spreadsheet chunks
+        Field::new("row_start", DataType::Int32, true), // First spreadsheet row of the chunk
+        Field::new("row_end", DataType::Int32, true), // Last spreadsheet row of the chunk
+        Field::new("symbol_name", DataType::Utf8, true), // Top-level symbol of source code chunks
+        Field::new("line_start", DataType::Int32, true), // First source line of the chunk
+        Field::new("line_end", DataType::Int32, true), // Last source line of the chunk
+        Field::new("message_index", DataType::Int32, true), // Position of the email message in its archive
+        Field::new("message_subject", DataType::Utf8, true), // Email subject
+        Field::new("message_from", DataType::Utf8, true), // Email sender
+        Field::new("message_to", DataType::Utf8, true), // Email recipients
+        Field::new("message_date", DataType::Utf8, true), // Email date (RFC 3339 when parseable)
+        Field::new("title", DataType::Utf8, true), // Document title from its metadata
+        Field::new("author", DataType::Utf8, true), // Document author from its metadata
+        Field::new("created", DataType::Utf8, true), // Document creation date from its metadata
+        Field::new("tags", DataType::Utf8, true), // Document keywords, comma separated
+        Field::new("language", DataType::Utf8, true), // ISO 639-1 code of the chunk language
+        Field::new("language_confidence", DataType::Float32, true), // Confidence of the language detection
+        Field::new("file_size", DataType::Int64, true), // Size in bytes of the file the chunk was indexed from
     ]))
 }
 
+fn create_amharic_schema() -> SchemaRef {
+    create_chunk_schema(AMHARIC_EMBEDDING_DIM)
+}
+
 fn create_text_schema() -> SchemaRef {
-    Arc::new(Schema::new(vec![
-        Field::new("file_path", DataType::Utf8, false),
-        Field::new("content_hash", DataType::Utf8, false),
-        Field::new("chunk_id", DataType::Int32, false),
-        Field::new(
-            "embedding",
-            DataType::FixedSizeList(
-                Arc::new(Field::new("item", DataType::Float32, true)),
-                TEXT_EMBEDDING_DIM,
-            ),
-            true,
-        ),
-        Field::new("last_modified", DataType::Timestamp(TimeUnit::Second, None), false),
-    ]))
+    create_chunk_schema(TEXT_EMBEDDING_DIM)
 }
 
 /// Create the schema for image embeddings table
@@ -124,6 +140,12 @@ fn create_image_schema() -> SchemaRef {
         Field::new("width", DataType::Int32, true),      // Image width in pixels
         Field::new("height", DataType::Int32, true),     // Image height in pixels
         Field::new("thumbnail_path", DataType::Utf8, true),  // Path to thumbnail if generated
+        Field::new("taken_at", DataType::Utf8, true),    // EXIF capture date (YYYY-MM-DDTHH:MM:SS)
+        Field::new("camera_model", DataType::Utf8, true), // EXIF camera make and model
+        Field::new("orientation", DataType::Utf8, true), // landscape, portrait or square
+        Field::new("latitude", DataType::Float64, true), // EXIF GPS latitude in decimal degrees
+        Field::new("longitude", DataType::Float64, true), // EXIF GPS longitude in decimal degrees
+        Field::new("file_size", DataType::Int64, true), // Size of the image file in bytes
     ]))
 }
 
@@ -197,7 +219,12 @@ async fn open_or_create_table_with_schema(
             .await
             .map_err(|e| DbError::TableOpenError(table_name.to_string(), e))?;
 
-        let existing_schema = table.schema().await?;
+        let mut existing_schema = table.schema().await?;
+        if !schemas_compatible(&*existing_schema, &*expected_schema) {
+            if add_missing_columns(&table, &existing_schema, &expected_schema).await? {
+                existing_schema = table.schema().await?;
+            }
+        }
         if !schemas_compatible(&*existing_schema, &*expected_schema) {
              eprintln!(
                 "Schema mismatch for table '{}':\nExpected: {:?}\nFound: {:?}",
@@ -218,6 +245,44 @@ async fn open_or_create_table_with_schema(
     }
 }
 
+/// Adds nullable columns that exist in `expected` but not in `existing`, filled with NULL.
+/// Only applies when `existing` is a prefix of `expected`, i.e. the table predates new columns.
+/// Returns whether any column was added.
+async fn add_missing_columns(
+    table: &Table,
+    existing: &Schema,
+    expected: &Schema,
+) -> Result<bool, DbError> {
+    if existing.fields.len() >= expected.fields.len() {
+        return Ok(false);
+    }
+    let is_prefix = existing.fields.iter().zip(expected.fields.iter()).all(|(f1, f2)| {
+        f1.name() == f2.name() && f1.data_type() == f2.data_type()
+    });
+    if !is_prefix {
+        return Ok(false);
+    }
+
+    let mut transforms = Vec::new();
+    for field in expected.fields.iter().skip(existing.fields.len()) {
+        let sql_type = match (field.is_nullable(), field.data_type()) {
+            (true, DataType::Utf8) => "STRING",
+            (true, DataType::Int32) => "INT",
+            (true, DataType::Int64) => "BIGINT",
+            (true, DataType::Float32) => "FLOAT",
+            (true, DataType::Float64) => "DOUBLE",
+            _ => return Ok(false), // Non-nullable or unsupported columns need a repair instead
+        };
+        transforms.push((field.name().to_string(), format!("CAST(NULL AS {})", sql_type)));
+    }
+
+    info!("Migrating table '{}': adding columns {:?}", table.name(), transforms.iter().map(|(name, _)| name).collect::<Vec<_>>());
+    table
+        .add_columns(NewColumnTransform::SqlExpressions(transforms), None)
+        .await?;
+    Ok(true)
+}
+
 fn schemas_compatible(schema1: &Schema, schema2: &Schema) -> bool {
     if schema1.fields.len() != schema2.fields.len() {
         return false;
@@ -234,11 +299,111 @@ fn schemas_compatible(schema1: &Schema, schema2: &Schema) -> bool {
 pub async fn delete_document(table: &Table, file_path: &str) -> Result<(), DbError> {
     debug!("Deleting document: {}", file_path);
     // Use a SQL-like WHERE clause to specify the record to delete
-    let predicate = format!("file_path = '{}'", file_path);
+    let predicate = format!("file_path = '{}'", file_path.replace('\'', "''"));
     table.delete(&predicate).await?; // Map LanceError to DbError via From
     Ok(())
 }
 
+/// Escapes a value for use inside a single-quoted `LIKE ... ESCAPE '\'` pattern.
+/// Backslash is the escape character, so it and the wildcards are escaped along with quotes.
+pub fn escape_like_pattern(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+        .replace('\'', "''")
+}
+
+/// Deletes every document whose file path starts with the given prefix,
+/// e.g. all members of an archive (`bundle.zip!/`).
+pub async fn delete_documents_with_prefix(table: &Table, prefix: &str) -> Result<(), DbError> {
+    debug!("Deleting documents under: {}", prefix);
+    let predicate = format!("file_path LIKE '{}%' ESCAPE '\\'", escape_like_pattern(prefix));
+    table.delete(&predicate).await?;
+    Ok(())
+}
+
+/// What the index holds for one stored file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedFile {
+    pub content_hash: String,
+    /// Size of the file in bytes when it was indexed; unknown for rows written before sizes were stored
+    pub size: Option<u64>,
+    /// When the file was indexed, as a Unix timestamp (the oldest of its rows)
+    pub indexed_at: i64,
+}
+
+/// Lists the files stored under a path prefix with their hash, size and indexing time
+pub async fn list_indexed_files(table: &Table, prefix: &str) -> Result<HashMap<String, IndexedFile>, DbError> {
+    // Images keep their hash in `file_hash`, text chunks in `content_hash`
+    let schema = table.schema().await?;
+    let hash_column = if schema.column_with_name("file_hash").is_some() { "file_hash" } else { "content_hash" };
+
+    let batches = table
+        .query()
+        .only_if(format!("file_path LIKE '{}%' ESCAPE '\\'", escape_like_pattern(prefix)))
+        .select(Select::columns(&["file_path", hash_column, "last_modified", "file_size"]))
+        .execute()
+        .await?
+        .try_collect::<Vec<RecordBatch>>()
+        .await?;
+
+    let mut files: HashMap<String, IndexedFile> = HashMap::new();
+    for batch in &batches {
+        let paths = batch.column_by_name("file_path").and_then(|a| a.as_any().downcast_ref::<StringArray>());
+        let hashes = batch.column_by_name(hash_column).and_then(|a| a.as_any().downcast_ref::<StringArray>());
+        let times = batch.column_by_name("last_modified").and_then(|a| a.as_any().downcast_ref::<TimestampSecondArray>());
+        let sizes = batch.column_by_name("file_size").and_then(|a| a.as_any().downcast_ref::<Int64Array>());
+        let (Some(paths), Some(hashes), Some(times), Some(sizes)) = (paths, hashes, times, sizes) else {
+            return Err(DbError::Other(format!("Unexpected columns listing files under {}", prefix)));
+        };
+        for i in 0..batch.num_rows() {
+            let indexed_at = times.value(i);
+            let size = (!sizes.is_null(i)).then(|| sizes.value(i) as u64);
+            files
+                .entry(paths.value(i).to_string())
+                .and_modify(|file| file.indexed_at = file.indexed_at.min(indexed_at))
+                .or_insert_with(|| IndexedFile { content_hash: hashes.value(i).to_string(), size, indexed_at });
+        }
+    }
+    Ok(files)
+}
+
+/// Points the rows of a moved file or folder at its new path, keeping their embeddings.
+/// Besides the path itself this covers archive members (`old!/...`) and, for folders,
+/// every row under it (`old/...`). Rows already stored under the new path are replaced.
+pub async fn move_documents(table: &Table, old_path: &str, new_path: &str) -> Result<(), DbError> {
+    debug!("Moving documents from {} to {}", old_path, new_path);
+    let prefixes = |path: &str| [format!("{}!/", path), format!("{}{}", path, std::path::MAIN_SEPARATOR)];
+
+    // The destination may hold rows of a file that was overwritten by the move
+    delete_document(table, new_path).await?;
+    for prefix in prefixes(new_path) {
+        delete_documents_with_prefix(table, &prefix).await?;
+    }
+
+    table
+        .update()
+        .only_if(format!("file_path = '{}'", old_path.replace('\'', "''")))
+        .column("file_path", format!("'{}'", new_path.replace('\'', "''")))
+        .execute()
+        .await?;
+    for (old_prefix, new_prefix) in prefixes(old_path).into_iter().zip(prefixes(new_path)) {
+        // substr is 1-based and counts characters
+        let rest_start = old_prefix.chars().count() + 1;
+        table
+            .update()
+            .only_if(format!("file_path LIKE '{}%' ESCAPE '\\'", escape_like_pattern(&old_prefix)))
+            .column(
+                "file_path",
+                format!("concat('{}', substr(file_path, {}))", new_prefix.replace('\'', "''"), rest_start),
+            )
+            .execute()
+            .await?;
+    }
+    Ok(())
+}
+
 /// Adds or updates a document record in the LanceDB table.
 /// This performs a delete followed by an add, as LanceDB lacks native upsert.
 ///
@@ -250,97 +415,170 @@ pub async fn upsert_document(
     file_path: &str,
     content_hash: &str,
     embeddings: &[Vec<f32>],
+) -> Result<(), DbError> {
+    let chunks = vec![TextChunk::default(); embeddings.len()];
+    upsert_document_chunks(table, file_path, content_hash, None, &chunks, embeddings).await
+}
+
+pub async fn upsert_amharic_document(
+    table: &Table,
+    file_path: &str,
+    content_hash: &str,
+    embeddings: &[Vec<f32>],
+) -> Result<(), DbError> {
+    let chunks = vec![TextChunk::default(); embeddings.len()];
+    upsert_amharic_document_chunks(table, file_path, content_hash, None, &chunks, embeddings).await
+}
+
+/// Adds or updates a document in the English/Other text table, storing the
+/// per-chunk metadata (such as the heading breadcrumb) next to each embedding.
+/// `chunks` and `embeddings` must be the same length and in the same order.
+/// `content_hash` and `file_size` describe the file on disk the document was read from.
+pub async fn upsert_document_chunks(
+    table: &Table,
+    file_path: &str,
+    content_hash: &str,
+    file_size: Option<u64>,
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
+) -> Result<(), DbError> {
+    upsert_chunks_with_schema(table, create_text_schema(), TEXT_EMBEDDING_DIM, file_path, content_hash, file_size, chunks, embeddings).await
+}
+
+/// Adds or updates a document in the Amharic text table, see `upsert_document_chunks`.
+pub async fn upsert_amharic_document_chunks(
+    table: &Table,
+    file_path: &str,
+    content_hash: &str,
+    file_size: Option<u64>,
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
+) -> Result<(), DbError> {
+    upsert_chunks_with_schema(table, create_amharic_schema(), AMHARIC_EMBEDDING_DIM, file_path, content_hash, file_size, chunks, embeddings).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn upsert_chunks_with_schema(
+    table: &Table,
+    schema: SchemaRef,
+    embedding_dim: i32,
+    file_path: &str,
+    content_hash: &str,
+    file_size: Option<u64>,
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
 ) -> Result<(), DbError> {
     if embeddings.is_empty() {
         warn!("No embeddings provided for {}, skipping upsert", file_path);
         return Ok(());
     }
+    if chunks.len() != embeddings.len() {
+        return Err(DbError::Other(format!(
+            "Chunk count ({}) does not match embedding count ({}) for {}",
+            chunks.len(), embeddings.len(), file_path
+        )));
+    }
+
+    debug!("Upserting document into '{}': {} with {} chunks", table.name(), file_path, embeddings.len());
 
-    debug!("Upserting document: {} with {} chunks", file_path, embeddings.len());
-    
     // 1. Delete existing entries for this file path (ignore error if not found)
     let _ = delete_document(table, file_path).await; // Allow delete to fail if not present
 
-    // 2. Prepare the new record batches
-    let schema = create_text_schema(); // Get the schema
-    let now_ts = Utc::now().timestamp();
-
-    // Create batches for all embeddings/chunks
-    let mut batches = Vec::with_capacity(embeddings.len());
-    
-    for (i, embedding) in embeddings.iter().enumerate() {
-        // Create Arrow arrays for each record
-        let file_path_array = StringArray::from(vec![file_path]);
-        let content_hash_array = StringArray::from(vec![content_hash]);
-        let chunk_id_array = Int32Array::from(vec![i as i32]);
-        let last_modified_array = TimestampSecondArray::from(vec![now_ts]);
-
-        // Create the FixedSizeList array for the embedding
-        let mut embedding_builder = Float32Builder::new();
-        embedding_builder.append_slice(embedding);
-        let values_array = Arc::new(embedding_builder.finish()) as Arc<dyn arrow_array::Array>;
-        let embedding_array = FixedSizeListArray::try_new_from_values(values_array, TEXT_EMBEDDING_DIM)
-            .expect("Failed to create FixedSizeListArray");
-
-        // Create the RecordBatch
-        let batch = RecordBatch::try_new(
-            schema.clone(),
-            vec![
-                Arc::new(file_path_array),
-                Arc::new(content_hash_array),
-                Arc::new(chunk_id_array),
-                Arc::new(embedding_array),
-                Arc::new(last_modified_array),
-            ],
-        ).map_err(|e| DbError::SchemaError(e))?; // Convert ArrowError to DbError
-        
-        batches.push(Ok(batch));
-    }
-
-    // 3. Add all record batches to the table
-    let reader = RecordBatchIterator::new(batches, schema);
-    table.add(Box::new(reader)).execute().await?; // Map LanceError via From
+    // 2. Add the new chunks
+    append_chunks_with_schema(table, schema, embedding_dim, file_path, content_hash, file_size, chunks, embeddings, 0).await?;
 
     debug!("Successfully upserted document: {} with {} chunks", file_path, embeddings.len());
     Ok(())
 }
 
-pub async fn upsert_amharic_document(
+/// Appends chunks of a document to the English/Other text table without deleting
+/// existing rows. Used to write long documents in batches; chunk ids start at `first_chunk_id`.
+pub async fn append_document_chunks(
     table: &Table,
     file_path: &str,
     content_hash: &str,
+    file_size: Option<u64>,
+    chunks: &[TextChunk],
     embeddings: &[Vec<f32>],
+    first_chunk_id: usize,
+) -> Result<(), DbError> {
+    append_chunks_with_schema(table, create_text_schema(), TEXT_EMBEDDING_DIM, file_path, content_hash, file_size, chunks, embeddings, first_chunk_id).await
+}
+
+/// Appends chunks of a document to the Amharic text table, see `append_document_chunks`.
+pub async fn append_amharic_document_chunks(
+    table: &Table,
+    file_path: &str,
+    content_hash: &str,
+    file_size: Option<u64>,
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
+    first_chunk_id: usize,
+) -> Result<(), DbError> {
+    append_chunks_with_schema(table, create_amharic_schema(), AMHARIC_EMBEDDING_DIM, file_path, content_hash, file_size, chunks, embeddings, first_chunk_id).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn append_chunks_with_schema(
+    table: &Table,
+    schema: SchemaRef,
+    embedding_dim: i32,
+    file_path: &str,
+    content_hash: &str,
+    file_size: Option<u64>,
+    chunks: &[TextChunk],
+    embeddings: &[Vec<f32>],
+    first_chunk_id: usize,
 ) -> Result<(), DbError> {
     if embeddings.is_empty() {
-        warn!("No embeddings provided for {}, skipping upsert", file_path);
         return Ok(());
     }
+    if chunks.len() != embeddings.len() {
+        return Err(DbError::Other(format!(
+            "Chunk count ({}) does not match embedding count ({}) for {}",
+            chunks.len(), embeddings.len(), file_path
+        )));
+    }
 
-    debug!("Upserting Amharic document: {} with {} chunks", file_path, embeddings.len());
-    
-    // 1. Delete existing entries for this file path (ignore error if not found)
-    let _ = delete_document(table, file_path).await; // Allow delete to fail if not present
-
-    // 2. Prepare the new record batches
-    let schema = create_amharic_schema(); // Get the schema
+    // Prepare the new record batches
     let now_ts = Utc::now().timestamp();
 
     // Create batches for all embeddings/chunks
     let mut batches = Vec::with_capacity(embeddings.len());
-    
-    for (i, embedding) in embeddings.iter().enumerate() {
+
+    for (i, (chunk, embedding)) in chunks.iter().zip(embeddings.iter()).enumerate() {
         // Create Arrow arrays for each record
         let file_path_array = StringArray::from(vec![file_path]);
         let content_hash_array = StringArray::from(vec![content_hash]);
-        let chunk_id_array = Int32Array::from(vec![i as i32]);
+        let chunk_id_array = Int32Array::from(vec![(first_chunk_id + i) as i32]);
         let last_modified_array = TimestampSecondArray::from(vec![now_ts]);
+        let heading_path_array = StringArray::from(vec![chunk.heading_path.as_deref()]);
+        let page_number_array = Int32Array::from(vec![chunk.page_number.map(|page| page as i32)]);
+        let sheet_name_array = StringArray::from(vec![chunk.sheet_name.as_deref()]);
+        let row_start_array = Int32Array::from(vec![chunk.row_start.map(|row| row as i32)]);
+        let row_end_array = Int32Array::from(vec![chunk.row_end.map(|row| row as i32)]);
+        let symbol_name_array = StringArray::from(vec![chunk.symbol_name.as_deref()]);
+        let line_start_array = Int32Array::from(vec![chunk.line_start.map(|line| line as i32)]);
+        let line_end_array = Int32Array::from(vec![chunk.line_end.map(|line| line as i32)]);
+        let message_index_array = Int32Array::from(vec![chunk.message_index.map(|index| index as i32)]);
+        let message_subject_array = StringArray::from(vec![chunk.message_subject.as_deref()]);
+        let message_from_array = StringArray::from(vec![chunk.message_from.as_deref()]);
+        let message_to_array = StringArray::from(vec![chunk.message_to.as_deref()]);
+        let message_date_array = StringArray::from(vec![chunk.message_date.as_deref()]);
+        let title_array = StringArray::from(vec![chunk.metadata.title.as_deref()]);
+        let author_array = StringArray::from(vec![chunk.metadata.author.as_deref()]);
+        let created_array = StringArray::from(vec![chunk.metadata.created.as_deref()]);
+        let tags_array = StringArray::from(vec![chunk.metadata.tags_column()]);
+        let language_array = StringArray::from(vec![chunk.language.as_ref().map(|language| language.code())]);
+        let language_confidence_array = Float32Array::from(vec![chunk.language_confidence]);
+        let file_size_array = Int64Array::from(vec![file_size.map(|size| size as i64)]);
 
         // Create the FixedSizeList array for the embedding
         let mut embedding_builder = Float32Builder::new();
         embedding_builder.append_slice(embedding);
         let values_array = Arc::new(embedding_builder.finish()) as Arc<dyn arrow_array::Array>;
-        let embedding_array = FixedSizeListArray::try_new_from_values(values_array, AMHARIC_EMBEDDING_DIM)
-            .expect("Failed to create FixedSizeListArray");
+        let embedding_array = FixedSizeListArray::try_new_from_values(values_array, embedding_dim)
+            .map_err(DbError::SchemaError)?;
 
         // Create the RecordBatch
         let batch = RecordBatch::try_new(
@@ -351,17 +589,35 @@ pub async fn upsert_amharic_document(
                 Arc::new(chunk_id_array),
                 Arc::new(embedding_array),
                 Arc::new(last_modified_array),
+                Arc::new(heading_path_array),
+                Arc::new(page_number_array),
+                Arc::new(sheet_name_array),
+                Arc::new(row_start_array),
+                Arc::new(row_end_array),
+                Arc::new(symbol_name_array),
+                Arc::new(line_start_array),
+                Arc::new(line_end_array),
+                Arc::new(message_index_array),
+                Arc::new(message_subject_array),
+                Arc::new(message_from_array),
+                Arc::new(message_to_array),
+                Arc::new(message_date_array),
+                Arc::new(title_array),
+                Arc::new(author_array),
+                Arc::new(created_array),
+                Arc::new(tags_array),
+                Arc::new(language_array),
+                Arc::new(language_confidence_array),
+                Arc::new(file_size_array),
             ],
         ).map_err(|e| DbError::SchemaError(e))?; // Convert ArrowError to DbError
-        
+
         batches.push(Ok(batch));
     }
 
-    // 3. Add all record batches to the table
+    // Add all record batches to the table
     let reader = RecordBatchIterator::new(batches, schema);
     table.add(Box::new(reader)).execute().await?; // Map LanceError via From
-
-    debug!("Successfully upserted Amharic document: {} with {} chunks", file_path, embeddings.len());
     Ok(())
 }
 
@@ -370,9 +626,9 @@ pub async fn upsert_image(
     table: &Table,
     file_path: &str,
     file_hash: &str,
+    file_size: Option<u64>,
     embedding: &[f32],
-    width: Option<i32>,
-    height: Option<i32>,
+    metadata: &ImageMetadata,
     thumbnail_path: Option<&str>,
 ) -> Result<(), DbError> {
     debug!("Upserting image: {}", file_path);
@@ -388,9 +644,15 @@ pub async fn upsert_image(
     let file_path_array = StringArray::from(vec![file_path]);
     let file_hash_array = StringArray::from(vec![file_hash]);
     let last_modified_array = TimestampSecondArray::from(vec![now_ts]);
-    let width_array = Int32Array::from(vec![width]);
-    let height_array = Int32Array::from(vec![height]);
+    let width_array = Int32Array::from(vec![metadata.width]);
+    let height_array = Int32Array::from(vec![metadata.height]);
     let thumbnail_path_array = StringArray::from(vec![thumbnail_path]);
+    let taken_at_array = StringArray::from(vec![metadata.taken_at.as_deref()]);
+    let camera_model_array = StringArray::from(vec![metadata.camera_model.as_deref()]);
+    let orientation_array = StringArray::from(vec![metadata.orientation()]);
+    let latitude_array = Float64Array::from(vec![metadata.latitude]);
+    let longitude_array = Float64Array::from(vec![metadata.longitude]);
+    let file_size_array = Int64Array::from(vec![file_size.map(|size| size as i64)]);
 
     // Create the FixedSizeList array for the embedding
     let mut embedding_builder = Float32Builder::new();
@@ -410,6 +672,12 @@ pub async fn upsert_image(
             Arc::new(width_array),
             Arc::new(height_array),
             Arc::new(thumbnail_path_array),
+            Arc::new(taken_at_array),
+            Arc::new(camera_model_array),
+            Arc::new(orientation_array),
+            Arc::new(latitude_array),
+            Arc::new(longitude_array),
+            Arc::new(file_size_array),
         ],
     ).map_err(|e| DbError::SchemaError(e))?;
 
@@ -566,6 +834,180 @@ mod tests {
         let delete_result_nonexistent = delete_document(&table, "/path/does/not/exist.txt").await;
         assert!(delete_result_nonexistent.is_ok(), "Delete non-existent failed: {:?}", delete_result_nonexistent.err());
     }
+
+    #[tokio::test]
+    async fn test_upsert_document_chunks_stores_heading_path() {
+        let (_test_db, _conn, table) = setup_test_table().await;
+
+        let chunks = vec![
+            TextChunk { text: "Run the installer.".to_string(), heading_path: Some("Guide > Install > Linux".to_string()), ..TextChunk::default() },
+            TextChunk::new("No heading.".to_string()),
+        ];
+        let embeddings = vec![generate_dummy_embedding(1.0), generate_dummy_embedding(2.0)];
+        upsert_document_chunks(&table, "/docs/guide.md", "hash", None, &chunks, &embeddings)
+            .await
+            .expect("Upsert failed");
+
+        let batches = table
+            .query()
+            .only_if("heading_path IS NOT NULL")
+            .execute()
+            .await
+            .expect("Query failed")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Collect failed");
+        let heading_paths: Vec<String> = batches
+            .iter()
+            .flat_map(|batch| {
+                let column = batch
+                    .column_by_name("heading_path")
+                    .and_then(|array| array.as_any().downcast_ref::<StringArray>())
+                    .expect("Missing heading_path column");
+                (0..column.len()).map(|i| column.value(i).to_string()).collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(heading_paths, vec!["Guide > Install > Linux".to_string()]);
+
+        let mismatched = upsert_document_chunks(&table, "/docs/guide.md", "hash", None, &chunks[..1], &embeddings).await;
+        assert!(matches!(mismatched, Err(DbError::Other(_))));
+    }
+
+    #[tokio::test]
+    async fn test_move_documents_keeps_rows_and_rewrites_paths() {
+        let (_test_db, _conn, table) = setup_test_table().await;
+        let sep = std::path::MAIN_SEPARATOR;
+        let in_folder = format!("/docs/old{}notes.txt", sep);
+        let nested = format!("/docs/old{}sub{}plan.md", sep, sep);
+        let sibling = "/docs/old_drafts.txt";
+        for (i, path) in [in_folder.as_str(), nested.as_str(), sibling, "/docs/report.txt", "/docs/bundle.zip!/a.txt"]
+            .iter()
+            .enumerate()
+        {
+            upsert_document(&table, path, "hash", &[generate_dummy_embedding(i as f32)]).await.expect("Upsert failed");
+        }
+        // Overwritten by the renamed file
+        upsert_document(&table, "/docs/final's.txt", "stale", &[generate_dummy_embedding(9.0)]).await.expect("Upsert failed");
+
+        move_documents(&table, "/docs/old", "/docs/new").await.expect("Folder move failed");
+        move_documents(&table, "/docs/report.txt", "/docs/final's.txt").await.expect("File rename failed");
+        move_documents(&table, "/docs/bundle.zip", "/archive/bundle.zip").await.expect("Archive move failed");
+
+        let batches = table
+            .query()
+            .execute()
+            .await
+            .expect("Query failed")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Collect failed");
+        let mut rows: Vec<(String, String)> = batches
+            .iter()
+            .flat_map(|batch| {
+                let paths = batch.column_by_name("file_path").and_then(|a| a.as_any().downcast_ref::<StringArray>()).unwrap();
+                let hashes = batch.column_by_name("content_hash").and_then(|a| a.as_any().downcast_ref::<StringArray>()).unwrap();
+                (0..batch.num_rows()).map(|i| (paths.value(i).to_string(), hashes.value(i).to_string())).collect::<Vec<_>>()
+            })
+            .collect();
+        rows.sort();
+        let paths: Vec<&str> = rows.iter().map(|(path, _)| path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/archive/bundle.zip!/a.txt",
+                "/docs/final's.txt",
+                format!("/docs/new{}notes.txt", sep).as_str(),
+                format!("/docs/new{}sub{}plan.md", sep, sep).as_str(),
+                sibling,
+            ]
+        );
+        // The stale destination rows were replaced by the moved ones
+        assert!(rows.iter().all(|(_, hash)| hash == "hash"));
+    }
+
+    #[tokio::test]
+    async fn test_list_indexed_files_reports_file_size() {
+        let (_test_db, _conn, table) = setup_test_table().await;
+        let chunks = vec![TextChunk::new("Sized.".to_string())];
+        upsert_document_chunks(&table, "/docs/sized.txt", "hash", Some(42), &chunks, &[generate_dummy_embedding(1.0)])
+            .await
+            .expect("Upsert failed");
+        upsert_document(&table, "/docs/legacy.txt", "hash", &[generate_dummy_embedding(2.0)]).await.expect("Upsert failed");
+
+        let files = list_indexed_files(&table, "/docs/").await.expect("Listing failed");
+        assert_eq!(files["/docs/sized.txt"].size, Some(42));
+        assert_eq!(files["/docs/legacy.txt"].size, None);
+    }
+
+    #[tokio::test]
+    async fn test_append_document_chunks_continues_chunk_ids() {
+        let (_test_db, _conn, table) = setup_test_table().await;
+
+        let first = vec![TextChunk { text: "Page one.".to_string(), page_number: Some(1), ..TextChunk::default() }];
+        let second = vec![TextChunk { text: "Page two.".to_string(), page_number: Some(2), ..TextChunk::default() }];
+        append_document_chunks(&table, "/docs/long.pdf", "hash", None, &first, &[generate_dummy_embedding(1.0)], 0)
+            .await
+            .expect("First append failed");
+        append_document_chunks(&table, "/docs/long.pdf", "hash", None, &second, &[generate_dummy_embedding(2.0)], 1)
+            .await
+            .expect("Second append failed");
+
+        let batches = table
+            .query()
+            .only_if("chunk_id = 1")
+            .execute()
+            .await
+            .expect("Query failed")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Collect failed");
+        let pages: Vec<i32> = batches
+            .iter()
+            .flat_map(|batch| {
+                let column = batch
+                    .column_by_name("page_number")
+                    .and_then(|array| array.as_any().downcast_ref::<Int32Array>())
+                    .expect("Missing page_number column");
+                (0..column.len()).map(|i| column.value(i)).collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(pages, vec![2]);
+        assert_eq!(table.count_rows(None).await.expect("Count failed"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_open_table_adds_missing_nullable_columns() {
+        let test_db = TestDb::new();
+        let conn = connect_db_with_path(&test_db.path).await.expect("DB connection failed");
+
+        // Create the table with the schema that predates the per-chunk metadata columns
+        let expected_schema = create_text_schema();
+        let legacy_fields: Vec<Field> = expected_schema
+            .fields()
+            .iter()
+            .filter(|field| {
+                !matches!(
+                    field.name().as_str(),
+                    "heading_path" | "page_number" | "sheet_name" | "row_start" | "row_end" | "symbol_name"
+                        | "line_start" | "line_end" | "message_index" | "message_subject" | "message_from"
+                        | "message_to" | "message_date" | "title" | "author" | "created" | "tags"
+                        | "language" | "language_confidence" | "file_size"
+                )
+            })
+            .map(|field| field.as_ref().clone())
+            .collect();
+        let legacy_schema = Arc::new(Schema::new(legacy_fields));
+        let batch = RecordBatch::new_empty(legacy_schema.clone());
+        let reader = RecordBatchIterator::new(vec![Ok(batch)], legacy_schema);
+        conn.create_table(TEXT_TABLE_NAME, Box::new(reader))
+            .execute()
+            .await
+            .expect("Manual create failed");
+
+        let table = open_or_create_text_table(&conn).await.expect("Migration failed");
+        let actual_schema = table.schema().await.expect("Get schema failed");
+        assert!(schemas_compatible(&*actual_schema, &*expected_schema), "Schema was not migrated");
+    }
 }
 
 /// Force drops a table by removing it directly from the database
@@ -4,9 +4,11 @@ use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
 use thiserror::Error;
 use log::{error, info, debug};
 use std::path::{PathBuf, Prefix};
+use std::sync::RwLock;
 use once_cell::sync::Lazy;
-use crate::chunker::{chunk_text, ChunkerError};
-use crate::extractor::DetectedLanguage;
+use serde::{Deserialize, Serialize};
+use crate::chunker::{chunk_sections, chunk_structured, chunk_text, get_chunking_config, ChunkerError, TextChunk};
+use crate::extractor::{assign_chunk_languages, DetectedLanguage, LanguageDetection, TextExtractionResult};
 use log::warn;
 
 const DEFAULT_MODEL_NAME: EmbeddingModel = EmbeddingModel::BGESmallENV15;
@@ -47,6 +49,53 @@ static AMHARIC_MODEL: Lazy<Result<TextEmbedding, EmbeddingError>> = Lazy::new(||
     })
 });
 
+/// Settings of cross-lingual search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossLingualConfig {
+    /// Also embed English chunks with the multilingual model, so that queries in other
+    /// languages can find English documents. Doubles the embedding work for English text;
+    /// turning it off leaves "any language" searches matching English documents only with
+    /// English queries.
+    pub index_english: bool,
+}
+
+impl Default for CrossLingualConfig {
+    fn default() -> Self {
+        CrossLingualConfig { index_english: true }
+    }
+}
+
+// Active cross-lingual configuration used by the indexer
+static CROSS_LINGUAL_CONFIG: Lazy<RwLock<CrossLingualConfig>> =
+    Lazy::new(|| RwLock::new(CrossLingualConfig::default()));
+
+/// Get the active cross-lingual configuration
+pub fn get_cross_lingual_config() -> CrossLingualConfig {
+    CROSS_LINGUAL_CONFIG.read().unwrap().clone()
+}
+
+/// Replace the active cross-lingual configuration
+pub fn set_cross_lingual_config(config: CrossLingualConfig) {
+    info!("Updating cross-lingual configuration: {:?}", config);
+    *CROSS_LINGUAL_CONFIG.write().unwrap() = config;
+}
+
+/// Locates the `tokenizer.json` of the default or (with `multilingual`) the multilingual
+/// embedding model in the fastembed cache. Returns `None` until the model has been downloaded.
+pub fn model_tokenizer_path(multilingual: bool) -> Option<PathBuf> {
+    let model_dir_hint = if multilingual { "multilingual-e5-large" } else { "bge-small-en-v1.5" };
+    let cache_dir = PathBuf::from(CACHE_DIR_NAME);
+    walkdir::WalkDir::new(&cache_dir)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .find(|entry| {
+            entry.file_name() == "tokenizer.json"
+                && entry.path().to_string_lossy().contains(model_dir_hint)
+        })
+        .map(|entry| entry.into_path())
+}
+
 fn embed_with_model(
     model_instance: &Result<TextEmbedding, EmbeddingError>,
     content: &[String],
@@ -76,10 +125,11 @@ fn embed_with_model(
             if text_content.trim().is_empty() {
                 continue; 
             }
-            let prefix = if lang_prefix.is_some() { lang_prefix.unwrap() } else { "passage" };
-            let chunks = chunk_text(text_content, "passage: ")?;
-            println!("chunks: {}", chunks.len());
-            println!("chunks: {:?}", chunks);
+            // Only models that expect an instruction prefix (E5) get one
+            let chunk_prefix = lang_prefix.map(|prefix| format!("{}: ", prefix)).unwrap_or_default();
+            // Bare passages have no source file, so there is no extension to pick a grammar from
+            let chunks = chunk_text(text_content, None, &chunk_prefix)?;
+            debug!("Split passage into {} chunks", chunks.len());
             final_chunks_to_embed.extend(chunks);
         }
         if final_chunks_to_embed.is_empty() { return Ok(Vec::new()); }
@@ -91,10 +141,17 @@ fn embed_with_model(
         }
     }
     
-    debug!("Embedding {} final chunks.", final_chunks_to_embed.len());
+    run_model(model_instance, final_chunks_to_embed)
+}
+
+fn run_model(
+    model_instance: &Result<TextEmbedding, EmbeddingError>,
+    inputs: Vec<String>,
+) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+    debug!("Embedding {} final chunks.", inputs.len());
 
     match model_instance {
-        Ok(model) => model.embed(final_chunks_to_embed, None).map_err(|e| {
+        Ok(model) => model.embed(inputs, None).map_err(|e| {
             error!("Embedding generation failed: {}", e);
             EmbeddingError::GenerationError(format!("Embedding generation failed: {}", e))
         }),
@@ -105,23 +162,95 @@ fn embed_with_model(
     }
 }
 
+/// Embeds texts with the model for their language: English and undetected text with the
+/// default model, every other language with the multilingual E5 model
 pub fn embed_text(content: &[String], language: &DetectedLanguage, query: bool) -> Result<Vec<Vec<f32>>, EmbeddingError> {
-    debug!("Embedding Amharic text with Amharic model.");
-    let prefix = if query { "query" } else { "passage" }; // MultilingualE5Large uses these
-    embed_with_model(&AMHARIC_MODEL, content, query, Some(prefix))
-    // match language {
-    //     DetectedLanguage::English => {
-    //         debug!("Embedding English text with default model.");
-    //         embed_with_model(&DEFAULT_MODEL, content, query, None)
-    //     }
-    //     DetectedLanguage::Amharic => {
-            
-    //     }
-    //     DetectedLanguage::Other => {
-    //         warn!("Unsupported language {:?} detected for embedding, defaulting to English model.", language);
-    //         embed_with_model(&DEFAULT_MODEL, content, query, None)
-    //     }
-    // }
+    if language.uses_multilingual_model() {
+        debug!("Embedding {:?} text with the multilingual model.", language);
+        let prefix = if query { "query" } else { "passage" }; // MultilingualE5Large uses these
+        embed_with_model(&AMHARIC_MODEL, content, query, Some(prefix))
+    } else {
+        debug!("Embedding {:?} text with default model.", language);
+        embed_with_model(&DEFAULT_MODEL, content, query, None)
+    }
+}
+
+/// Embeds chunks that were already produced by the chunker.
+/// Returns one embedding per chunk, in the same order. Every chunk is embedded with the
+/// model for its own language (`language` for chunks without one), so the embeddings of
+/// a bilingual document can have different dimensions.
+pub fn embed_chunks(chunks: &[TextChunk], language: &DetectedLanguage) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+    let (multilingual, english): (Vec<usize>, Vec<usize>) = (0..chunks.len())
+        .partition(|&index| chunks[index].language.as_ref().unwrap_or(language).uses_multilingual_model());
+
+    let mut embeddings = vec![Vec::new(); chunks.len()];
+    if !english.is_empty() {
+        let inputs = english.iter().map(|&index| chunks[index].embedding_text()).collect();
+        for (index, embedding) in english.iter().zip(run_model(&DEFAULT_MODEL, inputs)?) {
+            embeddings[*index] = embedding;
+        }
+    }
+    if !multilingual.is_empty() {
+        let inputs = multilingual
+            .iter()
+            .map(|&index| format!("passage: {}", chunks[index].embedding_text()))
+            .collect();
+        for (index, embedding) in multilingual.iter().zip(run_model(&AMHARIC_MODEL, inputs)?) {
+            embeddings[*index] = embedding;
+        }
+    }
+    Ok(embeddings)
+}
+
+/// Embeds chunks with the multilingual model whatever their language, for the
+/// cross-lingual copies of English chunks
+pub fn embed_chunks_multilingual(chunks: &[TextChunk]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+    if chunks.is_empty() {
+        return Ok(Vec::new());
+    }
+    let inputs = chunks.iter().map(|chunk| format!("passage: {}", chunk.embedding_text())).collect();
+    run_model(&AMHARIC_MODEL, inputs)
+}
+
+/// Chunks a document with the active chunking configuration and embeds every chunk.
+///
+/// # Arguments
+/// * `text` - The extracted document text
+/// * `extension` - The source file extension, used for format-aware chunking
+/// * `language` - The language detected for the whole document, used for chunks too short to detect
+pub fn embed_document(
+    text: &str,
+    extension: Option<&str>,
+    language: &LanguageDetection,
+) -> Result<(Vec<TextChunk>, Vec<Vec<f32>>), EmbeddingError> {
+    let mut chunks = chunk_sections(text, extension, &get_chunking_config().for_language(&language.language))?;
+    assign_chunk_languages(&mut chunks, language);
+    let embeddings = embed_chunks(&chunks, &language.language)?;
+    Ok((chunks, embeddings))
+}
+
+/// Chunks and embeds the result of `extract_text`.
+/// Structured extractions (e.g. spreadsheets) are chunked section by section so
+/// every chunk keeps its location; other documents go through `embed_document`.
+/// Every chunk carries the document's metadata and its own detected language.
+pub fn embed_extraction(
+    extraction: &TextExtractionResult,
+    extension: Option<&str>,
+) -> Result<(Vec<TextChunk>, Vec<Vec<f32>>), EmbeddingError> {
+    let document_language = extraction.language_detection();
+    let (mut chunks, embeddings) = if extraction.sections.is_empty() {
+        embed_document(&extraction.text, extension, &document_language)?
+    } else {
+        let config = get_chunking_config().for_language(&extraction.language);
+        let mut chunks = chunk_structured(&extraction.sections, extension, &config)?;
+        assign_chunk_languages(&mut chunks, &document_language);
+        let embeddings = embed_chunks(&chunks, &extraction.language)?;
+        (chunks, embeddings)
+    };
+    for chunk in &mut chunks {
+        chunk.metadata = extraction.metadata.clone();
+    }
+    Ok((chunks, embeddings))
 }
 
 pub fn embed_amharic_text(content: &[String], query: bool) -> Result<Vec<Vec<f32>>, EmbeddingError> {
@@ -0,0 +1,176 @@
+// src-tauri/src/extractor/amharic.rs
+
+//! Normalization of Amharic text. Ge'ez script has several homophone consonant
+//! series (ሀ/ሐ/ኀ, ሰ/ሠ, አ/ዐ, ጸ/ፀ) that are used interchangeably in modern
+//! Amharic, so the same word is often spelled in different ways. Documents and
+//! queries are normalized the same way so these spellings match.
+//!
+//! Only characters of the Ethiopic block are touched; text in other scripts is
+//! returned unchanged.
+
+use std::borrow::Cow;
+
+/// First code points of the homophone series folded into another series, with the
+/// first code point of the series they are folded into. The seven vowel orders of
+/// a series are consecutive; labialized forms are left as they are.
+const FOLDED_SERIES: &[(u32, u32)] = &[
+    (0x1210, 0x1200), // ሐ -> ሀ
+    (0x1280, 0x1200), // ኀ -> ሀ
+    (0x1220, 0x1230), // ሠ -> ሰ
+    (0x12D0, 0x12A0), // ዐ -> አ
+    (0x1340, 0x1338), // ፀ -> ጸ
+];
+
+/// Ethiopic word space (፡), which stands in for an ordinary space in older texts
+const WORD_SPACE: char = '\u{1361}';
+
+/// Returns true for characters of the Ethiopic Unicode block
+pub fn is_ethiopic(c: char) -> bool {
+    ('\u{1200}'..='\u{137F}').contains(&c)
+}
+
+/// Normalizes Amharic text for indexing and search:
+///
+/// * homophone series are folded into one series, and the fourth order of ሀ and አ
+///   (ሃ, ኣ), which is pronounced like the first, into the first order
+/// * Ethiopic punctuation becomes its ASCII equivalent (። to `.`, ፣ to `,`, ...)
+/// * Ethiopic numerals become Arabic digits (፲፱፻፹፭ to `1985`)
+pub fn normalize_amharic(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_ethiopic) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut normalized = String::with_capacity(text.len());
+    let mut numeral = NumeralParser::default();
+    for c in text.chars() {
+        if numeral.push(c) {
+            continue;
+        }
+        numeral.flush(&mut normalized);
+        match c {
+            WORD_SPACE => normalized.push(' '),
+            '\u{1362}' => normalized.push('.'), // ።
+            '\u{1363}' => normalized.push(','), // ፣
+            '\u{1364}' => normalized.push(';'), // ፤
+            '\u{1365}' | '\u{1366}' => normalized.push(':'), // ፥ ፦
+            '\u{1367}' => normalized.push('?'), // ፧
+            '\u{1368}' => normalized.push('\n'), // ፨ paragraph separator
+            c => normalized.push(fold_homophone(c)),
+        }
+    }
+    numeral.flush(&mut normalized);
+    Cow::Owned(normalized)
+}
+
+/// Maps a character of a homophone series to the matching order of the canonical series
+fn fold_homophone(c: char) -> char {
+    let code = c as u32;
+    let folded = FOLDED_SERIES
+        .iter()
+        .find(|(start, _)| (*start..*start + 7).contains(&code))
+        .map_or(code, |(start, target)| target + (code - start));
+    let folded = match folded {
+        0x1203 => 0x1200, // ሃ -> ሀ
+        0x12A3 => 0x12A0, // ኣ -> አ
+        other => other,
+    };
+    char::from_u32(folded).unwrap_or(c)
+}
+
+/// Accumulates a run of Ethiopic numerals. The system is additive within groups of
+/// hundreds: ፻ multiplies what precedes it by 100 and ፼ by 10,000.
+#[derive(Default)]
+struct NumeralParser {
+    /// Value of the completed ten-thousands, including the last ፼
+    total: u64,
+    /// Hundreds of the current ten-thousand
+    hundreds: u64,
+    /// Units and tens written since the last ፻ or ፼
+    current: u64,
+    active: bool,
+}
+
+impl NumeralParser {
+    /// Consumes `c` if it is an Ethiopic numeral and returns whether it did
+    fn push(&mut self, c: char) -> bool {
+        match c {
+            '\u{1369}'..='\u{1371}' => self.current += c as u64 - 0x1368, // ፩-፱
+            '\u{1372}'..='\u{137A}' => self.current += (c as u64 - 0x1371) * 10, // ፲-፺
+            '\u{137B}' => {
+                // ፻
+                self.hundreds += self.current.max(1) * 100;
+                self.current = 0;
+            }
+            '\u{137C}' => {
+                // ፼
+                self.total = self.value().max(1).saturating_mul(10_000);
+                self.hundreds = 0;
+                self.current = 0;
+            }
+            _ => return false,
+        }
+        self.active = true;
+        true
+    }
+
+    /// Value of the number parsed so far; long runs of ፼ saturate instead of overflowing
+    fn value(&self) -> u64 {
+        self.total.saturating_add(self.hundreds).saturating_add(self.current)
+    }
+
+    /// Writes the number parsed so far, if any, and starts over
+    fn flush(&mut self, output: &mut String) {
+        if self.active {
+            output.push_str(&self.value().to_string());
+            *self = NumeralParser::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_homophone_spellings_fold_together() {
+        // "power" is written ኃይል, ሀይል, ሃይል or ሐይል
+        assert_eq!(normalize_amharic("ኃይል"), normalize_amharic("ሀይል"));
+        assert_eq!(normalize_amharic("ሃይል"), normalize_amharic("ሐይል"));
+        // "sun" ፀሐይ / ጸሀይ, "work" ሥራ / ስራ, "year" ዓመት / አመት
+        assert_eq!(normalize_amharic("ፀሐይ"), "ጸሀይ");
+        assert_eq!(normalize_amharic("ሥራ"), "ስራ");
+        assert_eq!(normalize_amharic("ዓመት"), normalize_amharic("አመት"));
+        // "hour" ሰዓት / ሰአት
+        assert_eq!(normalize_amharic("ሰዓት"), "ሰአት");
+        // Distinct consonants are left alone
+        assert_eq!(normalize_amharic("ቤት ትምህርት"), "ቤት ትምህርት");
+    }
+
+    #[test]
+    fn test_punctuation_and_word_space() {
+        assert_eq!(
+            normalize_amharic("ኢትዮጵያ፣ ኬንያ እና ሱዳን ጎረቤቶች ናቸው። ለምን፧"),
+            "ኢትዮጵያ, ኬንያ እና ሱዳን ጎረቤቶች ናቸው. ለምን?"
+        );
+        assert_eq!(normalize_amharic("ሰላም፡ለዓለም"), "ሰላም ለአለም");
+    }
+
+    #[test]
+    fn test_ethiopic_numerals_become_digits() {
+        assert_eq!(normalize_amharic("በ፲፱፻፹፭ ዓ.ም."), "በ1985 አ.ም.");
+        assert_eq!(normalize_amharic("፳፻፲፭"), "2015");
+        assert_eq!(normalize_amharic("ምዕራፍ ፫"), "ምእራፍ 3");
+        assert_eq!(normalize_amharic("፻"), "100");
+        assert_eq!(normalize_amharic("፲፪፻፴፬፼፶፮፻፸፰"), "12345678");
+        assert_eq!(normalize_amharic("፻፼"), "1000000");
+        // Numbers past u64 saturate rather than overflow
+        assert_eq!(normalize_amharic(&format!("{}፺፱", "፼".repeat(6))), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn test_text_without_ethiopic_is_unchanged() {
+        let text = "Quarterly report: 2024. Revenue grew, costs fell.";
+        assert!(matches!(normalize_amharic(text), Cow::Borrowed(_)));
+        assert_eq!(normalize_amharic("Budget ሪፖርት፣ 2024"), "Budget ሪፖርት, 2024");
+    }
+}
@@ -0,0 +1,479 @@
+// src-tauri/src/extractor/archive.rs
+
+//! ZIP and tar archives. Supported members are extracted through `extract_text` and
+//! indexed under virtual paths such as `bundle.zip!/docs/spec.pdf`; nested archives are
+//! opened up to a configurable depth. Members can be materialized to a temporary file
+//! so they can be opened with the default application.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use flate2::read::GzDecoder;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
+use zip::ZipArchive;
+
+use super::registry::find_extractor;
+use super::{calculate_hash, extract_text, ExtractorError, TextExtractionResult};
+
+/// Separates the archive path from the member path in a virtual path
+pub const ARCHIVE_MEMBER_SEPARATOR: &str = "!/";
+
+/// Limits applied when descending into archives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveConfig {
+    /// Whether archive members are indexed at all
+    pub enabled: bool,
+    /// Archives larger than this many bytes (on disk) are skipped
+    pub max_archive_size: u64,
+    /// Members larger than this many bytes (uncompressed) are skipped
+    pub max_member_size: u64,
+    /// Reading stops after this many files, counted across nested archives
+    pub max_members: usize,
+    /// Reading stops once this many bytes (uncompressed) have been copied out of the archive
+    pub max_total_size: u64,
+    /// How many levels of archives are opened; 1 only reads the archive itself
+    pub max_depth: u32,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        ArchiveConfig {
+            enabled: true,
+            max_archive_size: 512 * 1024 * 1024,
+            max_member_size: 64 * 1024 * 1024,
+            max_members: 10_000,
+            max_total_size: 2 * 1024 * 1024 * 1024,
+            max_depth: 2,
+        }
+    }
+}
+
+impl ArchiveConfig {
+    /// Checks the configuration for values that would make every archive unreadable
+    pub fn validate(&self) -> Result<(), ExtractorError> {
+        if self.max_depth == 0 {
+            return Err(ExtractorError::InvalidArchiveConfig("max_depth must be at least 1".to_string()));
+        }
+        if self.max_archive_size == 0 || self.max_member_size == 0 || self.max_total_size == 0 {
+            return Err(ExtractorError::InvalidArchiveConfig("size limits must be greater than 0".to_string()));
+        }
+        if self.max_members == 0 {
+            return Err(ExtractorError::InvalidArchiveConfig("max_members must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+}
+
+// Active archive configuration used by the indexing pipeline
+static ARCHIVE_CONFIG: Lazy<RwLock<ArchiveConfig>> = Lazy::new(|| RwLock::new(ArchiveConfig::default()));
+
+/// Get the active archive configuration
+pub fn get_archive_config() -> ArchiveConfig {
+    ARCHIVE_CONFIG.read().unwrap().clone()
+}
+
+/// Replace the active archive configuration
+pub fn set_archive_config(config: ArchiveConfig) -> Result<(), ExtractorError> {
+    config.validate()?;
+    info!("Updating archive configuration: {:?}", config);
+    *ARCHIVE_CONFIG.write().unwrap() = config;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+fn archive_kind(name: &str) -> Option<ArchiveKind> {
+    let name = name.to_lowercase();
+    if name.ends_with(".zip") {
+        Some(ArchiveKind::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(ArchiveKind::TarGz)
+    } else if name.ends_with(".tar") {
+        Some(ArchiveKind::Tar)
+    } else {
+        None
+    }
+}
+
+/// Returns true for zip, tar and tar.gz archives
+pub fn is_archive(file_path: &Path) -> bool {
+    file_path.file_name().and_then(|name| name.to_str()).and_then(archive_kind).is_some()
+}
+
+/// Builds the virtual path of an archive member, e.g. `bundle.zip!/docs/spec.pdf`
+pub fn archive_member_path(archive_path: &str, member_name: &str) -> String {
+    format!("{}{}{}", archive_path, ARCHIVE_MEMBER_SEPARATOR, member_name.trim_start_matches('/'))
+}
+
+/// Returns true if the path points inside an archive
+pub fn is_archive_member_path(path: &str) -> bool {
+    path.contains(ARCHIVE_MEMBER_SEPARATOR)
+}
+
+/// A supported archive member and the result of extracting its text
+#[derive(Debug)]
+pub struct ArchiveMember {
+    pub virtual_path: String,
+    pub extraction: Result<TextExtractionResult, ExtractorError>,
+}
+
+/// Extracts the text of every supported member of an archive, descending into nested
+/// archives up to `ArchiveConfig::max_depth`.
+///
+/// Members are handed to `on_member` one at a time as they are extracted, so only one
+/// member is held in memory. Reading stops early, keeping what was already handed over,
+/// once `ArchiveConfig::max_members` files or `ArchiveConfig::max_total_size` bytes are read.
+pub fn extract_archive(file_path: &Path, mut on_member: impl FnMut(ArchiveMember)) -> Result<(), ExtractorError> {
+    let config = get_archive_config();
+    let display = file_path.display().to_string();
+    let kind = file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(archive_kind)
+        .ok_or_else(|| ExtractorError::UnsupportedFileType(display.clone()))?;
+
+    let size = fs::metadata(file_path).map_err(|e| ExtractorError::IoError(display.clone(), e))?.len();
+    if size > config.max_archive_size {
+        return Err(ExtractorError::ExtractionFailed(
+            display,
+            format!("archive is {} bytes, above the {} byte limit", size, config.max_archive_size),
+        ));
+    }
+
+    let mut budget = ArchiveBudget { members: 0, total_size: 0, exhausted: false };
+    collect_members(file_path, kind, &display, 1, &config, &mut budget, &mut on_member)?;
+    if budget.exhausted {
+        warn!(
+            "Stopped reading archive {} after {} files and {} bytes",
+            file_path.display(),
+            budget.members,
+            budget.total_size
+        );
+    }
+    info!("Read {} files from archive {}", budget.members, file_path.display());
+    Ok(())
+}
+
+/// Files and bytes read so far from an archive and its nested archives
+struct ArchiveBudget {
+    members: usize,
+    total_size: u64,
+    exhausted: bool,
+}
+
+impl ArchiveBudget {
+    /// Counts a file, returning false once the file limit is reached
+    fn take_member(&mut self, config: &ArchiveConfig) -> bool {
+        if self.members >= config.max_members {
+            self.exhausted = true;
+            return false;
+        }
+        self.members += 1;
+        true
+    }
+
+    /// Bytes that may still be copied out of the archive, capped at the member size limit
+    fn copy_limit(&self, config: &ArchiveConfig) -> u64 {
+        config.max_member_size.min(config.max_total_size.saturating_sub(self.total_size))
+    }
+
+    /// Counts `size` copied bytes, returning false once the byte limit is reached
+    fn take_size(&mut self, size: u64, config: &ArchiveConfig) -> bool {
+        if self.total_size + size > config.max_total_size {
+            self.exhausted = true;
+            return false;
+        }
+        self.total_size += size;
+        true
+    }
+}
+
+fn collect_members(
+    file_path: &Path,
+    kind: ArchiveKind,
+    virtual_prefix: &str,
+    depth: u32,
+    config: &ArchiveConfig,
+    budget: &mut ArchiveBudget,
+    on_member: &mut dyn FnMut(ArchiveMember),
+) -> Result<(), ExtractorError> {
+    for_each_member(file_path, kind, |name, size, reader| {
+        if budget.exhausted || !budget.take_member(config) {
+            return false;
+        }
+        let virtual_path = archive_member_path(virtual_prefix, name);
+        if size > config.max_member_size {
+            debug!("Skipping archive member {} ({} bytes)", virtual_path, size);
+            return true;
+        }
+
+        let nested_kind = archive_kind(name);
+        if nested_kind.is_some() && depth >= config.max_depth {
+            debug!("Not descending into nested archive {}", virtual_path);
+            return true;
+        }
+        if nested_kind.is_none() && find_extractor(Path::new(name)).is_none() {
+            return true;
+        }
+        // A member whose size is misreported is cut off at what is left of the byte budget
+        let copy_limit = budget.copy_limit(config);
+        if !budget.take_size(size, config) {
+            return false;
+        }
+        // Extractors work on files, so the member is copied out under its own extension
+        let temp = copy_to_temp(reader, name, copy_limit)
+            .map_err(|e| ExtractorError::IoError(virtual_path.clone(), e));
+
+        match nested_kind {
+            Some(inner_kind) => {
+                let nested = temp.and_then(|temp| {
+                    collect_members(temp.path(), inner_kind, &virtual_path, depth + 1, config, budget, on_member)
+                });
+                if let Err(e) = nested {
+                    warn!("Failed to read nested archive {}: {}", virtual_path, e);
+                    on_member(ArchiveMember { virtual_path, extraction: Err(e) });
+                }
+            }
+            None => {
+                let extraction = temp.and_then(|temp| extract_text(temp.path()));
+                on_member(ArchiveMember { virtual_path, extraction });
+            }
+        }
+        !budget.exhausted
+    })
+}
+
+/// Calls `visit` with the name, uncompressed size and content of every file in the archive
+/// until it returns false
+fn for_each_member(
+    file_path: &Path,
+    kind: ArchiveKind,
+    mut visit: impl FnMut(&str, u64, &mut dyn Read) -> bool,
+) -> Result<(), ExtractorError> {
+    let display = file_path.display().to_string();
+    let io_error = |e: io::Error| ExtractorError::IoError(display.clone(), e);
+    let file = File::open(file_path).map_err(io_error)?;
+
+    match kind {
+        ArchiveKind::Zip => {
+            let failed = |e: zip::result::ZipError| ExtractorError::ExtractionFailed(display.clone(), e.to_string());
+            let mut archive = ZipArchive::new(file).map_err(failed)?;
+            for index in 0..archive.len() {
+                let mut member = archive.by_index(index).map_err(failed)?;
+                if member.is_dir() {
+                    continue;
+                }
+                // Names escaping the archive root (`../`) are ignored
+                let Some(name) = member.enclosed_name().map(|name| name.to_string_lossy().replace('\\', "/")) else {
+                    continue;
+                };
+                let size = member.size();
+                if !visit(&name, size, &mut member) {
+                    break;
+                }
+            }
+        }
+        ArchiveKind::Tar => visit_tar(tar::Archive::new(file), &mut visit).map_err(io_error)?,
+        ArchiveKind::TarGz => visit_tar(tar::Archive::new(GzDecoder::new(file)), &mut visit).map_err(io_error)?,
+    }
+    Ok(())
+}
+
+fn visit_tar<R: Read>(mut archive: tar::Archive<R>, visit: &mut impl FnMut(&str, u64, &mut dyn Read) -> bool) -> io::Result<()> {
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().trim_start_matches("./").to_string();
+        let size = entry.size();
+        if !visit(&name, size, &mut entry) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Copies a member into a temporary file with the member's extension. Fails if the member
+/// turns out to be larger than `max_size`, which protects against misreported sizes.
+fn copy_to_temp(reader: &mut dyn Read, name: &str, max_size: u64) -> io::Result<NamedTempFile> {
+    let suffix = match archive_kind(name) {
+        Some(ArchiveKind::TarGz) => ".tar.gz".to_string(),
+        _ => Path::new(name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| format!(".{}", ext))
+            .unwrap_or_default(),
+    };
+    let mut temp = tempfile::Builder::new().suffix(&suffix).tempfile()?;
+    let copied = io::copy(&mut reader.take(max_size + 1), &mut temp)?;
+    if copied > max_size {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("{} is larger than {} bytes", name, max_size)));
+    }
+    temp.flush()?;
+    Ok(temp)
+}
+
+/// Extracts the member a virtual path points at (through any nested archives) to a
+/// temporary file named like the member and returns its path
+pub fn extract_archive_member(virtual_path: &str) -> Result<PathBuf, ExtractorError> {
+    let (archive_path, member_path) = virtual_path
+        .split_once(ARCHIVE_MEMBER_SEPARATOR)
+        .ok_or_else(|| ExtractorError::InvalidPath(virtual_path.to_string()))?;
+    let mut kind = archive_kind(archive_path).ok_or_else(|| ExtractorError::UnsupportedFileType(archive_path.to_string()))?;
+    let max_size = get_archive_config().max_member_size;
+
+    // Nested archives are copied out one level at a time
+    let mut current: Option<NamedTempFile> = None;
+    let mut segments = member_path.split(ARCHIVE_MEMBER_SEPARATOR).peekable();
+    while let Some(segment) = segments.next() {
+        let archive_file = current.as_ref().map_or_else(|| PathBuf::from(archive_path), |temp| temp.path().to_path_buf());
+        let mut found = None;
+        for_each_member(&archive_file, kind, |name, _size, reader| {
+            if name == segment {
+                found = Some(copy_to_temp(reader, name, max_size));
+            }
+            found.is_none()
+        })?;
+        let temp = found
+            .ok_or_else(|| ExtractorError::ExtractionFailed(virtual_path.to_string(), format!("{} not found in archive", segment)))?
+            .map_err(|e| ExtractorError::IoError(virtual_path.to_string(), e))?;
+
+        if segments.peek().is_some() {
+            kind = archive_kind(segment).ok_or_else(|| ExtractorError::UnsupportedFileType(segment.to_string()))?;
+            current = Some(temp);
+            continue;
+        }
+
+        // Keep the member's own file name so the opening application shows it
+        let file_name = Path::new(segment).file_name().map(|name| name.to_os_string()).unwrap_or_else(|| segment.into());
+        let target_dir = std::env::temp_dir()
+            .join("semantic_file_explorer")
+            .join(&calculate_hash(virtual_path)[..16]);
+        fs::create_dir_all(&target_dir).map_err(|e| ExtractorError::IoError(target_dir.display().to_string(), e))?;
+        let target = target_dir.join(file_name);
+        fs::copy(temp.path(), &target).map_err(|e| ExtractorError::IoError(target.display().to_string(), e))?;
+        debug!("Extracted archive member {} to {}", virtual_path, target.display());
+        return Ok(target);
+    }
+    Err(ExtractorError::InvalidPath(virtual_path.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use zip::write::SimpleFileOptions;
+
+    fn write_zip(path: &Path, members: &[(&str, &[u8])]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in members {
+            writer.start_file(*name, SimpleFileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    fn write_tar_gz(path: &Path, members: &[(&str, &[u8])]) {
+        let encoder = flate2::write::GzEncoder::new(File::create(path).unwrap(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, content) in members {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_archive_kind() {
+        assert!(is_archive(Path::new("/data/bundle.zip")));
+        assert!(is_archive(Path::new("backup.TAR.GZ")));
+        assert!(is_archive(Path::new("backup.tgz")));
+        assert!(is_archive(Path::new("backup.tar")));
+        assert!(!is_archive(Path::new("notes.gz")));
+        assert_eq!(archive_member_path("/data/bundle.zip", "/docs/spec.md"), "/data/bundle.zip!/docs/spec.md");
+    }
+
+    #[test]
+    fn test_extract_zip_members_with_nested_archive() {
+        let dir = tempdir().unwrap();
+        let inner = dir.path().join("inner.tar.gz");
+        write_tar_gz(&inner, &[("./guide.md", b"# Guide\n\nInstall the tool.")]);
+        let archive = dir.path().join("bundle.zip");
+        write_zip(&archive, &[
+            ("docs/spec.txt", b"The spec covers retries."),
+            ("image.bin", b"\x00\x01"),
+            ("inner.tar.gz", &fs::read(&inner).unwrap()),
+        ]);
+
+        let mut members = Vec::new();
+        extract_archive(&archive, |member| members.push(member)).unwrap();
+        let archive_str = archive.display().to_string();
+        let paths: Vec<&str> = members.iter().map(|m| m.virtual_path.as_str()).collect();
+        assert_eq!(paths, vec![
+            format!("{}!/docs/spec.txt", archive_str),
+            format!("{}!/inner.tar.gz!/guide.md", archive_str),
+        ]);
+        assert_eq!(members[0].extraction.as_ref().unwrap().text, "The spec covers retries.");
+
+        let opened = extract_archive_member(paths[1]).unwrap();
+        assert_eq!(opened.file_name().unwrap(), "guide.md");
+        assert_eq!(fs::read_to_string(opened).unwrap(), "# Guide\n\nInstall the tool.");
+    }
+
+    #[test]
+    fn test_oversized_members_are_skipped() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("bundle.tar.gz");
+        write_tar_gz(&archive, &[("big.txt", "x".repeat(2048).as_bytes()), ("small.txt", b"ok")]);
+
+        let config = ArchiveConfig { max_member_size: 1024, ..ArchiveConfig::default() };
+        let members = collect_with(&archive, ArchiveKind::TarGz, &config);
+        assert_eq!(members, vec!["bundle.tar.gz!/small.txt".to_string()]);
+    }
+
+    #[test]
+    fn test_member_and_size_limits_stop_reading() {
+        let dir = tempdir().unwrap();
+        let archive = dir.path().join("bundle.tar.gz");
+        write_tar_gz(&archive, &[("a.txt", b"first"), ("skip.bin", b"\x00"), ("b.txt", b"second"), ("c.txt", b"third")]);
+
+        // Unsupported files count towards the file limit too
+        let config = ArchiveConfig { max_members: 3, ..ArchiveConfig::default() };
+        let members = collect_with(&archive, ArchiveKind::TarGz, &config);
+        assert_eq!(members, vec!["bundle.tar.gz!/a.txt".to_string(), "bundle.tar.gz!/b.txt".to_string()]);
+
+        let config = ArchiveConfig { max_total_size: 12, ..ArchiveConfig::default() };
+        let members = collect_with(&archive, ArchiveKind::TarGz, &config);
+        assert_eq!(members, vec!["bundle.tar.gz!/a.txt".to_string(), "bundle.tar.gz!/b.txt".to_string()]);
+    }
+
+    /// Virtual paths of the members `collect_members` hands over under `config`
+    fn collect_with(archive: &Path, kind: ArchiveKind, config: &ArchiveConfig) -> Vec<String> {
+        let mut budget = ArchiveBudget { members: 0, total_size: 0, exhausted: false };
+        let mut members = Vec::new();
+        let prefix = archive.file_name().unwrap().to_str().unwrap();
+        collect_members(archive, kind, prefix, 1, config, &mut budget, &mut |member| members.push(member.virtual_path))
+            .unwrap();
+        members
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        assert!(ArchiveConfig { max_depth: 0, ..ArchiveConfig::default() }.validate().is_err());
+        assert!(ArchiveConfig { max_members: 0, ..ArchiveConfig::default() }.validate().is_err());
+        assert!(ArchiveConfig::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,213 @@
+// src-tauri/src/extractor/code.rs
+
+//! Source code extraction. Files in a language with a tree-sitter grammar are split
+//! into one section per top-level symbol (function, struct, class, ...) carrying the
+//! symbol name and line range; other source files are indexed as plain text.
+
+use std::path::Path;
+use log::{debug, info, warn};
+use tree_sitter::{Node, Parser};
+
+use crate::chunker::{code_language_for_extension, TextChunk};
+use super::plain_text::read_text_lossy;
+use super::registry::TextExtractor;
+use super::{ContentType, ExtractedText, ExtractorError};
+
+/// Top-level node kinds treated as symbols, across the supported grammars
+const SYMBOL_KINDS: &[&str] = &[
+    // Rust
+    "function_item", "struct_item", "enum_item", "union_item", "trait_item", "impl_item", "mod_item",
+    "macro_definition", "const_item", "static_item", "type_item",
+    // Python
+    "function_definition", "class_definition", "decorated_definition",
+    // JavaScript / TypeScript
+    "function_declaration", "generator_function_declaration", "class_declaration", "abstract_class_declaration",
+    "interface_declaration", "type_alias_declaration", "enum_declaration", "lexical_declaration", "export_statement",
+    // Go
+    "method_declaration", "type_declaration",
+];
+
+/// Comments and attributes, attached to the symbol that directly follows them
+const LEADING_KINDS: &[&str] = &["comment", "line_comment", "block_comment", "attribute_item"];
+
+/// Source code files, split by top-level symbol where a grammar is available
+pub struct SourceCodeExtractor;
+
+impl TextExtractor for SourceCodeExtractor {
+    fn name(&self) -> &'static str {
+        "code"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &[
+            "rs", "py", "js", "jsx", "ts", "tsx", "go", "java", "kt", "c", "h", "cpp", "cc", "hpp", "cs", "rb", "php",
+            "swift", "scala", "sh", "sql", "lua",
+        ]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &[
+            "text/rust", "text/x-rust", "text/x-python", "text/javascript", "application/javascript",
+            "application/typescript", "text/x-go", "text/x-java", "text/x-kotlin", "text/x-c", "text/x-c++",
+            "text/x-csharp", "text/x-ruby", "application/x-httpd-php", "text/x-swift", "text/x-scala",
+            "application/x-sh", "application/sql", "text/x-lua",
+        ]
+    }
+
+    fn content_type(&self) -> ContentType {
+        ContentType::Code
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting source code: {}", file_path.display());
+        let source = read_text_lossy(file_path)?;
+        let extension = file_path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+
+        match symbol_sections(&source, extension) {
+            Some(sections) if !sections.is_empty() => Ok(ExtractedText { sections, ..ExtractedText::from(source) }),
+            _ => Ok(source.into()),
+        }
+    }
+}
+
+/// Splits source code into top-level symbol sections. Returns `None` when the language
+/// has no grammar or the file cannot be parsed.
+fn symbol_sections(source: &str, extension: &str) -> Option<Vec<TextChunk>> {
+    let language = code_language_for_extension(extension)?;
+    let mut parser = Parser::new();
+    if let Err(e) = parser.set_language(&language) {
+        warn!("Failed to load grammar for .{} files: {}", extension, e);
+        return None;
+    }
+    let tree = parser.parse(source, None)?;
+    let root = tree.root_node();
+
+    let mut sections = Vec::new();
+    let mut loose: Vec<Node> = Vec::new();
+    let mut cursor = root.walk();
+    for node in root.named_children(&mut cursor) {
+        if !SYMBOL_KINDS.contains(&node.kind()) {
+            loose.push(node);
+            continue;
+        }
+
+        // Comments and attributes directly above the symbol (no blank line in between) belong to it
+        let mut first_line = node.start_position().row;
+        let mut attached = loose.len();
+        while attached > 0 {
+            let candidate = loose[attached - 1];
+            if !LEADING_KINDS.contains(&candidate.kind()) || candidate.end_position().row + 1 < first_line {
+                break;
+            }
+            first_line = candidate.start_position().row;
+            attached -= 1;
+        }
+        let leading = loose.split_off(attached);
+        push_loose_section(&mut sections, &loose, source);
+        loose.clear();
+
+        let start = leading.first().unwrap_or(&node);
+        sections.push(TextChunk {
+            text: source[start.start_byte()..node.end_byte()].to_string(),
+            symbol_name: symbol_name(node, source),
+            line_start: Some(start.start_position().row as u32 + 1),
+            line_end: Some(node.end_position().row as u32 + 1),
+            ..TextChunk::default()
+        });
+    }
+    push_loose_section(&mut sections, &loose, source);
+
+    debug!("Split .{} source into {} symbol sections", extension, sections.len());
+    Some(sections)
+}
+
+/// Adds a run of non-symbol top-level code (imports, statements) as an unnamed section
+fn push_loose_section(sections: &mut Vec<TextChunk>, loose: &[Node], source: &str) {
+    let (Some(first), Some(last)) = (loose.first(), loose.last()) else {
+        return;
+    };
+    let text = &source[first.start_byte()..last.end_byte()];
+    if text.trim().is_empty() {
+        return;
+    }
+    sections.push(TextChunk {
+        text: text.to_string(),
+        line_start: Some(first.start_position().row as u32 + 1),
+        line_end: Some(last.end_position().row as u32 + 1),
+        ..TextChunk::default()
+    });
+}
+
+/// Name of a top-level symbol, e.g. `retry_request` or `impl Display for Error`
+fn symbol_name(node: Node, source: &str) -> Option<String> {
+    let text = |node: Node| node.utf8_text(source.as_bytes()).ok().map(str::to_string);
+    match node.kind() {
+        "impl_item" => {
+            let type_name = node.child_by_field_name("type").and_then(text)?;
+            Some(match node.child_by_field_name("trait").and_then(text) {
+                Some(trait_name) => format!("impl {} for {}", trait_name, type_name),
+                None => format!("impl {}", type_name),
+            })
+        }
+        "decorated_definition" => node.child_by_field_name("definition").and_then(|d| symbol_name(d, source)),
+        "export_statement" => node.child_by_field_name("declaration").and_then(|d| symbol_name(d, source)),
+        "lexical_declaration" | "type_declaration" => {
+            // `const a = ...` / `type A struct {...}`: the name lives on the first declarator or spec
+            let mut cursor = node.walk();
+            let declarator = node.named_children(&mut cursor).find_map(|child| child.child_by_field_name("name"));
+            declarator.and_then(text)
+        }
+        _ => node.child_by_field_name("name").and_then(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST_SOURCE: &str = r#"use std::time::Duration;
+
+/// Retries an HTTP request with backoff
+fn retry_request(attempts: u32) -> bool {
+    attempts > 0
+}
+
+#[derive(Debug)]
+struct Client {
+    timeout: Duration,
+}
+
+impl Client {
+    fn new() -> Self {
+        Client { timeout: Duration::from_secs(1) }
+    }
+}
+"#;
+
+    #[test]
+    fn test_rust_symbols_with_line_ranges() {
+        let sections = symbol_sections(RUST_SOURCE, "rs").unwrap();
+        let names: Vec<Option<&str>> = sections.iter().map(|s| s.symbol_name.as_deref()).collect();
+        assert_eq!(names, vec![None, Some("retry_request"), Some("Client"), Some("impl Client")]);
+
+        // The doc comment belongs to the function
+        assert!(sections[1].text.starts_with("/// Retries an HTTP request"));
+        assert_eq!((sections[1].line_start, sections[1].line_end), (Some(3), Some(6)));
+        // Attributes stay with the item they annotate
+        assert!(sections[2].text.starts_with("#[derive(Debug)]"));
+        assert_eq!((sections[3].line_start, sections[3].line_end), (Some(13), Some(17)));
+    }
+
+    #[test]
+    fn test_python_decorated_and_class_symbols() {
+        let source = "import os\n\n@cache\ndef load(path):\n    return path\n\nclass Store:\n    pass\n";
+        let sections = symbol_sections(source, "py").unwrap();
+        let names: Vec<Option<&str>> = sections.iter().map(|s| s.symbol_name.as_deref()).collect();
+        assert_eq!(names, vec![None, Some("load"), Some("Store")]);
+    }
+
+    #[test]
+    fn test_language_without_grammar_falls_back_to_text() {
+        assert!(symbol_sections("int main() { return 0; }", "c").is_none());
+    }
+}
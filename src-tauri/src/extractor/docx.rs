@@ -0,0 +1,103 @@
+// src-tauri/src/extractor/docx.rs
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use dotext::{Docx, MsDoc};
+use log::{debug, error, info};
+use zip::ZipArchive;
+
+use super::markup::element_text;
+use super::metadata::{dedup_tags, split_tags};
+use super::registry::TextExtractor;
+use super::{DocumentMetadata, ExtractedText, ExtractorError};
+
+/// Package part holding the document's core properties (title, creator, ...)
+const CORE_PROPERTIES_PART: &str = "docProps/core.xml";
+
+/// Extracts DOCX documents with `dotext`
+pub struct DocxExtractor;
+
+impl TextExtractor for DocxExtractor {
+    fn name(&self) -> &'static str {
+        "docx"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["docx"]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &["application/vnd.openxmlformats-officedocument.wordprocessingml.document"]
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting text from DOCX using dotext: {}", file_path.display());
+        let mut docx_reader = Docx::open(file_path).map_err(|e| {
+            error!("Failed to open DOCX file with dotext {}: {}", file_path.display(), e);
+            ExtractorError::DocxExtractionFailed(file_path.display().to_string(), e.to_string())
+        })?;
+
+        let mut text_content = String::new();
+        docx_reader.read_to_string(&mut text_content).map_err(|e| {
+            error!("Failed to extract text from DOCX (dotext) {}: {}", file_path.display(), e);
+            ExtractorError::DocxExtractionFailed(file_path.display().to_string(), e.to_string())
+        })?;
+        Ok(ExtractedText::from(text_content).with_metadata(read_core_properties(file_path)))
+    }
+}
+
+/// Reads title, creator, creation date and keywords from the package's core properties.
+/// Missing or unreadable properties only mean the document has no metadata.
+fn read_core_properties(file_path: &Path) -> DocumentMetadata {
+    let read = || -> Result<String, Box<dyn std::error::Error>> {
+        let mut archive = ZipArchive::new(File::open(file_path)?)?;
+        let mut xml = String::new();
+        archive.by_name(CORE_PROPERTIES_PART)?.read_to_string(&mut xml)?;
+        Ok(xml)
+    };
+    match read() {
+        Ok(xml) => parse_core_properties(&xml),
+        Err(e) => {
+            debug!("No core properties in {}: {}", file_path.display(), e);
+            DocumentMetadata::default()
+        }
+    }
+}
+
+fn parse_core_properties(xml: &str) -> DocumentMetadata {
+    let tags = ["cp:keywords", "dc:subject", "cp:category"]
+        .iter()
+        .filter_map(|name| element_text(xml, name))
+        .flat_map(|value| split_tags(&value))
+        .collect();
+    DocumentMetadata {
+        title: element_text(xml, "dc:title"),
+        author: element_text(xml, "dc:creator"),
+        created: element_text(xml, "dcterms:created"),
+        tags: dedup_tags(tags),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_core_properties() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+    xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+  <dc:title>Field report &amp; notes</dc:title>
+  <dc:subject></dc:subject>
+  <dc:creator>Abebe Kebede</dc:creator>
+  <cp:keywords>survey; Addis Ababa, survey</cp:keywords>
+  <dcterms:created xsi:type="dcterms:W3CDTF">2024-05-01T08:30:00Z</dcterms:created>
+</cp:coreProperties>"#;
+        let metadata = parse_core_properties(xml);
+        assert_eq!(metadata.title.as_deref(), Some("Field report & notes"));
+        assert_eq!(metadata.author.as_deref(), Some("Abebe Kebede"));
+        assert_eq!(metadata.created.as_deref(), Some("2024-05-01T08:30:00Z"));
+        assert_eq!(metadata.tags, vec!["survey", "Addis Ababa"]);
+    }
+}
@@ -0,0 +1,403 @@
+// src-tauri/src/extractor/email.rs
+
+//! Email extraction for single `.eml` messages and `.mbox` archives. Every message
+//! becomes its own section carrying subject, sender, recipients, date and (for
+//! archives) its position, so search results can point at the message inside the
+//! archive. Attachments are run through the registered text extractors.
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, warn};
+use mailparse::{DispositionType, MailHeaderMap, ParsedMail};
+
+use crate::chunker::TextChunk;
+use super::markup::markup_to_text;
+use super::registry::{find_extractor, TextExtractor};
+use super::{DocumentMetadata, ExtractedText, ExtractorError};
+
+/// Attachments larger than this are not extracted
+const MAX_ATTACHMENT_BYTES: usize = 32 * 1024 * 1024;
+
+/// Extracts `.eml` messages and `.mbox` archives, one section per message
+pub struct EmailExtractor;
+
+impl TextExtractor for EmailExtractor {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["eml", "mbox"]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &["message/rfc822", "application/mbox"]
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting email: {}", file_path.display());
+        let io_error = |e: std::io::Error| {
+            error!("Failed to read email file {}: {}", file_path.display(), e);
+            ExtractorError::IoError(file_path.display().to_string(), e)
+        };
+        let is_mbox = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("mbox"));
+
+        let mut sections = Vec::new();
+        let mut metadata = DocumentMetadata::default();
+        if is_mbox {
+            let reader = BufReader::new(File::open(file_path).map_err(io_error)?);
+            let mut index = 0;
+            for_each_mbox_message(reader, |raw| {
+                index += 1;
+                match message_sections(&raw, Some(index)) {
+                    Ok(message) => sections.extend(message),
+                    Err(e) => warn!("Skipping message {} of {}: {}", index, file_path.display(), e),
+                }
+            })
+            .map_err(io_error)?;
+            debug!("Read {} messages from {}", index, file_path.display());
+        } else {
+            let raw = fs::read(file_path).map_err(io_error)?;
+            sections = message_sections(&raw, None).map_err(|e| {
+                ExtractorError::ExtractionFailed(file_path.display().to_string(), e.to_string())
+            })?;
+            // A single message is the document: its subject, sender and date describe it.
+            // Messages in an mbox keep these per chunk only.
+            if let Some(message) = sections.first() {
+                metadata = DocumentMetadata {
+                    title: message.message_subject.clone(),
+                    author: message.message_from.clone(),
+                    created: message.message_date.clone(),
+                    tags: Vec::new(),
+                };
+            }
+        }
+
+        Ok(ExtractedText::from_sections(sections).with_metadata(metadata))
+    }
+}
+
+/// Splits an mbox archive into raw messages, calling `on_message` for each one.
+///
+/// Messages start at a `From ` line at the beginning of the file or after a blank line;
+/// `>From ` lines escaped by the writer are unescaped again.
+fn for_each_mbox_message<R: BufRead>(mut reader: R, mut on_message: impl FnMut(Vec<u8>)) -> std::io::Result<()> {
+    let mut message: Vec<u8> = Vec::new();
+    let mut line = Vec::new();
+    let mut previous_blank = true;
+    let mut in_message = false;
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        if previous_blank && line.starts_with(b"From ") {
+            if in_message {
+                on_message(std::mem::take(&mut message));
+            }
+            in_message = true;
+            previous_blank = false;
+            continue;
+        }
+        previous_blank = line.iter().all(|b| b.is_ascii_whitespace());
+
+        let unescaped = match line.iter().position(|&b| b != b'>') {
+            Some(quotes) if quotes > 0 && line[quotes..].starts_with(b"From ") => &line[1..],
+            _ => &line[..],
+        };
+        message.extend_from_slice(unescaped);
+    }
+    if in_message || !message.is_empty() {
+        on_message(message);
+    }
+    Ok(())
+}
+
+/// Headers identifying a message, attached to every section taken from it
+#[derive(Debug, Clone, Default)]
+struct MessageHeaders {
+    index: Option<u32>,
+    subject: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    date: Option<String>,
+}
+
+impl MessageHeaders {
+    fn section(&self, text: String, heading_path: Option<String>) -> TextChunk {
+        TextChunk {
+            text,
+            heading_path,
+            message_index: self.index,
+            message_subject: self.subject.clone(),
+            message_from: self.from.clone(),
+            message_to: self.to.clone(),
+            message_date: self.date.clone(),
+            ..TextChunk::default()
+        }
+    }
+}
+
+/// Parses one raw message into its body section followed by the sections of its attachments
+fn message_sections(raw: &[u8], index: Option<u32>) -> Result<Vec<TextChunk>, mailparse::MailParseError> {
+    let mail = mailparse::parse_mail(raw)?;
+    let header = |name: &str| {
+        mail.headers
+            .get_first_value(name)
+            .map(|value| value.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|value| !value.is_empty())
+    };
+    let headers = MessageHeaders {
+        index,
+        subject: header("Subject"),
+        from: header("From"),
+        to: header("To"),
+        date: header("Date").map(|date| normalize_date(&date)),
+    };
+
+    let mut body = Vec::new();
+    let mut attachments = Vec::new();
+    collect_parts(&mail, &mut body, &mut attachments);
+
+    let mut body = body.join("\n\n").trim().to_string();
+    if body.is_empty() {
+        // Keep body-less messages findable by their subject
+        body = headers.subject.clone().unwrap_or_default();
+    }
+
+    let mut sections = vec![headers.section(body, None)];
+    for attachment in attachments {
+        sections.extend(attachment_sections(&attachment, &headers));
+    }
+    Ok(sections)
+}
+
+/// Converts an RFC 2822 date to RFC 3339 (UTC), keeping the raw header if it cannot be parsed
+fn normalize_date(date: &str) -> String {
+    mailparse::dateparse(date)
+        .ok()
+        .and_then(|timestamp| DateTime::<Utc>::from_timestamp(timestamp, 0))
+        .map(|date| date.to_rfc3339())
+        .unwrap_or_else(|| date.to_string())
+}
+
+/// A file attached to a message
+struct Attachment {
+    name: String,
+    data: Vec<u8>,
+}
+
+/// Walks the MIME tree, collecting readable body text and attachments.
+/// For `multipart/alternative` only the plain text version (or else the HTML one) is read.
+fn collect_parts(part: &ParsedMail, body: &mut Vec<String>, attachments: &mut Vec<Attachment>) {
+    let mimetype = part.ctype.mimetype.to_lowercase();
+
+    if !part.subparts.is_empty() {
+        if mimetype == "multipart/alternative" {
+            let preferred = part
+                .subparts
+                .iter()
+                .find(|sub| sub.ctype.mimetype.eq_ignore_ascii_case("text/plain"))
+                .or_else(|| part.subparts.iter().find(|sub| sub.ctype.mimetype.eq_ignore_ascii_case("text/html")))
+                .or_else(|| part.subparts.first());
+            if let Some(preferred) = preferred {
+                collect_parts(preferred, body, attachments);
+            }
+        } else {
+            for sub in &part.subparts {
+                collect_parts(sub, body, attachments);
+            }
+        }
+        return;
+    }
+
+    let disposition = part.get_content_disposition();
+    let file_name = disposition
+        .params
+        .get("filename")
+        .or_else(|| part.ctype.params.get("name"))
+        .cloned();
+    let is_attachment = disposition.disposition == DispositionType::Attachment
+        || mimetype == "message/rfc822"
+        || (file_name.is_some() && !mimetype.starts_with("text/"));
+
+    if is_attachment {
+        let name = file_name.unwrap_or_else(|| match mimetype.as_str() {
+            "message/rfc822" => "attached-message.eml".to_string(),
+            _ => "attachment".to_string(),
+        });
+        match part.get_body_raw() {
+            Ok(data) => attachments.push(Attachment { name, data }),
+            Err(e) => warn!("Failed to decode attachment {}: {}", name, e),
+        }
+        return;
+    }
+
+    let text = match part.get_body() {
+        Ok(text) => text,
+        Err(e) => {
+            warn!("Failed to decode {} body part: {}", mimetype, e);
+            return;
+        }
+    };
+    match mimetype.as_str() {
+        "text/html" => body.push(markup_to_text(&text)),
+        _ if mimetype.starts_with("text/") => body.push(text),
+        _ => {}
+    }
+}
+
+/// Extracts an attachment with the extractor registered for its file name. The sections are
+/// tagged with the message headers and prefixed with the attachment name in their heading path.
+fn attachment_sections(attachment: &Attachment, headers: &MessageHeaders) -> Vec<TextChunk> {
+    if attachment.data.len() > MAX_ATTACHMENT_BYTES {
+        debug!("Skipping attachment {} ({} bytes)", attachment.name, attachment.data.len());
+        return Vec::new();
+    }
+    let file_name = Path::new(&attachment.name);
+    let Some(extractor) = find_extractor(file_name) else {
+        debug!("No extractor for attachment {}", attachment.name);
+        return Vec::new();
+    };
+    let extension = file_name.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+
+    // Extractors work on files, so the attachment is written to a temporary one
+    let extracted = tempfile::Builder::new()
+        .suffix(&format!(".{}", extension))
+        .tempfile()
+        .and_then(|mut file| file.write_all(&attachment.data).map(|_| file))
+        .map_err(|e| ExtractorError::IoError(attachment.name.clone(), e))
+        .and_then(|file| extractor.extract(file.path()));
+    let extracted = match extracted {
+        Ok(extracted) => extracted,
+        Err(e) => {
+            warn!("Failed to extract attachment {}: {}", attachment.name, e);
+            return Vec::new();
+        }
+    };
+
+    let breadcrumb = |inner: Option<&String>| match inner {
+        Some(inner) => format!("{} > {}", attachment.name, inner),
+        None => attachment.name.clone(),
+    };
+    if extracted.sections.is_empty() {
+        if extracted.text.trim().is_empty() {
+            return Vec::new();
+        }
+        return vec![headers.section(extracted.text, Some(breadcrumb(None)))];
+    }
+    extracted
+        .sections
+        .into_iter()
+        .map(|section| {
+            let heading_path = Some(breadcrumb(section.heading_path.as_ref()));
+            // Keep the attachment's own location (page, sheet, rows) but report it under this message
+            TextChunk {
+                heading_path,
+                message_index: headers.index,
+                message_subject: headers.subject.clone(),
+                message_from: headers.from.clone(),
+                message_to: headers.to.clone(),
+                message_date: headers.date.clone(),
+                ..section
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const MBOX: &str = "From alice@example.com Mon Mar  4 10:00:00 2024\n\
+Subject: Retry policy\n\
+From: Alice <alice@example.com>\n\
+To: Bob <bob@example.com>\n\
+Date: Mon, 4 Mar 2024 10:00:00 +0000\n\
+\n\
+We retry failed uploads three times.\n\
+>From the logs it looks fine.\n\
+\n\
+From bob@example.com Tue Mar  5 09:30:00 2024\n\
+Subject: =?UTF-8?B?4Yiw4YiL4Yid?=\n\
+From: bob@example.com\n\
+To: alice@example.com\n\
+Date: Tue, 5 Mar 2024 09:30:00 +0000\n\
+MIME-Version: 1.0\n\
+Content-Type: multipart/mixed; boundary=\"XYZ\"\n\
+\n\
+--XYZ\n\
+Content-Type: text/plain; charset=utf-8\n\
+Content-Transfer-Encoding: quoted-printable\n\
+\n\
+See the attached notes=2E\n\
+--XYZ\n\
+Content-Type: text/plain; name=\"notes.txt\"\n\
+Content-Disposition: attachment; filename=\"notes.txt\"\n\
+\n\
+Budget review moved to Friday.\n\
+--XYZ--\n";
+
+    #[test]
+    fn test_mbox_splits_messages_and_unescapes_from_lines() {
+        let mut messages = Vec::new();
+        for_each_mbox_message(MBOX.as_bytes(), |raw| messages.push(String::from_utf8(raw).unwrap())).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].contains("\nFrom the logs it looks fine."));
+        assert!(messages[1].starts_with("Subject: =?UTF-8?B?"));
+    }
+
+    #[test]
+    fn test_extract_mbox_messages_with_attachments() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("archive.mbox");
+        fs::write(&file_path, MBOX).unwrap();
+
+        let extracted = EmailExtractor.extract(&file_path).unwrap();
+        assert_eq!(extracted.sections.len(), 3);
+
+        let first = &extracted.sections[0];
+        assert_eq!(first.message_index, Some(1));
+        assert_eq!(first.message_subject.as_deref(), Some("Retry policy"));
+        assert_eq!(first.message_from.as_deref(), Some("Alice <alice@example.com>"));
+        assert_eq!(first.message_date.as_deref(), Some("2024-03-04T10:00:00+00:00"));
+        assert!(first.text.starts_with("We retry failed uploads"));
+
+        let second = &extracted.sections[1];
+        assert_eq!(second.message_index, Some(2));
+        assert_eq!(second.message_subject.as_deref(), Some("ሰላም"));
+        assert_eq!(second.text, "See the attached notes.");
+
+        let attachment = &extracted.sections[2];
+        assert_eq!(attachment.message_index, Some(2));
+        assert_eq!(attachment.heading_path.as_deref(), Some("notes.txt"));
+        assert_eq!(attachment.text.trim(), "Budget review moved to Friday.");
+    }
+
+    #[test]
+    fn test_extract_eml_prefers_plain_text_alternative() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("message.eml");
+        fs::write(
+            &file_path,
+            "Subject: Launch\r\nFrom: carol@example.com\r\nContent-Type: multipart/alternative; boundary=b\r\n\r\n\
+             --b\r\nContent-Type: text/html\r\n\r\n<p>HTML version</p>\r\n\
+             --b\r\nContent-Type: text/plain\r\n\r\nPlain version\r\n--b--\r\n",
+        )
+        .unwrap();
+
+        let extracted = EmailExtractor.extract(&file_path).unwrap();
+        assert_eq!(extracted.sections.len(), 1);
+        assert_eq!(extracted.sections[0].message_index, None);
+        assert_eq!(extracted.sections[0].message_subject.as_deref(), Some("Launch"));
+        assert_eq!(extracted.sections[0].text, "Plain version");
+        assert_eq!(extracted.metadata.title.as_deref(), Some("Launch"));
+    }
+}
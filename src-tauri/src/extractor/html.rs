@@ -0,0 +1,60 @@
+// src-tauri/src/extractor/html.rs
+
+use std::path::Path;
+use log::info;
+
+use super::markup::{element_text, markup_to_text, meta_content};
+use super::metadata::split_tags;
+use super::plain_text::read_text_lossy;
+use super::registry::TextExtractor;
+use super::{DocumentMetadata, ExtractedText, ExtractorError};
+
+/// Extracts the visible text of HTML pages
+pub struct HtmlExtractor;
+
+impl TextExtractor for HtmlExtractor {
+    fn name(&self) -> &'static str {
+        "html"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["html", "htm", "xhtml"]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &["text/html", "application/xhtml+xml"]
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting text from HTML: {}", file_path.display());
+        let page = read_text_lossy(file_path)?;
+        Ok(ExtractedText::from(markup_to_text(&page)).with_metadata(page_metadata(&page)))
+    }
+}
+
+/// Title and the author/keywords/date `<meta>` tags of a page
+fn page_metadata(page: &str) -> DocumentMetadata {
+    DocumentMetadata {
+        title: element_text(page, "title"),
+        author: meta_content(page, "author"),
+        created: meta_content(page, "date").or_else(|| meta_content(page, "dcterms.created")),
+        tags: meta_content(page, "keywords").map(|keywords| split_tags(&keywords)).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_extract_html() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("page.html");
+        std::fs::write(&file_path, "<html><head><title>T</title></head><body><p>Hello ሰላም</p></body></html>").unwrap();
+        let extracted = HtmlExtractor.extract(&file_path).unwrap();
+        assert_eq!(extracted.text, "Hello ሰላም");
+        assert_eq!(extracted.metadata.title.as_deref(), Some("T"));
+        assert_eq!(extracted.metadata.author, None);
+    }
+}
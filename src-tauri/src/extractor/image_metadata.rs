@@ -0,0 +1,170 @@
+// src-tauri/src/extractor/image_metadata.rs
+
+//! Image properties stored with every indexed image: pixel dimensions and, when the
+//! file carries EXIF data, capture date, camera model, orientation and GPS position.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use exif::{DateTime, In, Reader, Tag, Value};
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+/// Metadata of an image file; every field is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    /// Width and height in pixels as displayed, i.e. after applying the EXIF rotation
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// Capture date (`DateTimeOriginal`), formatted as `YYYY-MM-DDTHH:MM:SS`
+    pub taken_at: Option<String>,
+    /// Camera make and model, e.g. `Canon EOS R6`
+    pub camera_model: Option<String>,
+    /// EXIF orientation tag (1-8); 5-8 mean the image is stored rotated by 90 degrees
+    pub exif_orientation: Option<u32>,
+    /// GPS position in decimal degrees, negative for south / west
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl ImageMetadata {
+    /// `landscape`, `portrait` or `square`, based on the displayed dimensions
+    pub fn orientation(&self) -> Option<&'static str> {
+        let (width, height) = (self.width?, self.height?);
+        Some(match width.cmp(&height) {
+            std::cmp::Ordering::Greater => "landscape",
+            std::cmp::Ordering::Less => "portrait",
+            std::cmp::Ordering::Equal => "square",
+        })
+    }
+}
+
+/// Reads the dimensions and EXIF data of an image. Formats without EXIF (PNG, GIF, ...)
+/// only get their dimensions; unreadable files give empty metadata.
+pub fn read_image_metadata(file_path: &Path) -> ImageMetadata {
+    let mut metadata = ImageMetadata::default();
+    match image::image_dimensions(file_path) {
+        Ok((width, height)) => {
+            metadata.width = Some(width as i32);
+            metadata.height = Some(height as i32);
+        }
+        Err(e) => debug!("Could not read dimensions of {}: {}", file_path.display(), e),
+    }
+
+    let exif = match File::open(file_path)
+        .map_err(exif::Error::Io)
+        .and_then(|file| Reader::new().read_from_container(&mut BufReader::new(file)))
+    {
+        Ok(exif) => exif,
+        Err(e) => {
+            debug!("No EXIF data in {}: {}", file_path.display(), e);
+            return metadata;
+        }
+    };
+    let field = |tag: Tag| exif.get_field(tag, In::PRIMARY).map(|field| &field.value);
+    let ascii = |tag: Tag| field(tag).and_then(ascii_value);
+
+    metadata.taken_at = field(Tag::DateTimeOriginal)
+        .or_else(|| field(Tag::DateTime))
+        .and_then(|value| match value {
+            Value::Ascii(parts) => parts.first().and_then(|bytes| DateTime::from_ascii(bytes).ok()),
+            _ => None,
+        })
+        .map(|date| format_exif_date(&date));
+    metadata.camera_model = camera_model(ascii(Tag::Make), ascii(Tag::Model));
+    metadata.exif_orientation = field(Tag::Orientation).and_then(|value| value.get_uint(0));
+    if matches!(metadata.exif_orientation, Some(5..=8)) {
+        std::mem::swap(&mut metadata.width, &mut metadata.height);
+    }
+    metadata.latitude = gps_coordinate(field(Tag::GPSLatitude), ascii(Tag::GPSLatitudeRef).as_deref(), "S");
+    metadata.longitude = gps_coordinate(field(Tag::GPSLongitude), ascii(Tag::GPSLongitudeRef).as_deref(), "W");
+    metadata
+}
+
+fn ascii_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Ascii(parts) => parts
+            .first()
+            .map(|bytes| String::from_utf8_lossy(bytes).trim_matches(char::from(0)).trim().to_string())
+            .filter(|text| !text.is_empty()),
+        _ => None,
+    }
+}
+
+fn format_exif_date(date: &DateTime) -> String {
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        date.year, date.month, date.day, date.hour, date.minute, date.second
+    )
+}
+
+/// Joins make and model, without repeating the make when the model already starts with it
+fn camera_model(make: Option<String>, model: Option<String>) -> Option<String> {
+    match (make, model) {
+        (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => Some(model),
+        (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+        (make, model) => model.or(make),
+    }
+}
+
+/// Converts degrees/minutes/seconds rationals to decimal degrees
+fn gps_coordinate(value: Option<&Value>, reference: Option<&str>, negative_reference: &str) -> Option<f64> {
+    let Some(Value::Rational(parts)) = value else {
+        return None;
+    };
+    let degrees = parts.first()?.to_f64();
+    let minutes = parts.get(1).map_or(0.0, |minutes| minutes.to_f64());
+    let seconds = parts.get(2).map_or(0.0, |seconds| seconds.to_f64());
+    let decimal = degrees + minutes / 60.0 + seconds / 3600.0;
+    if !decimal.is_finite() {
+        return None;
+    }
+    Some(if reference.is_some_and(|reference| reference.eq_ignore_ascii_case(negative_reference)) {
+        -decimal
+    } else {
+        decimal
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use exif::Rational;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_png_dimensions_and_orientation() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("wide.png");
+        image::RgbImage::new(40, 20).save(&file_path).unwrap();
+
+        let metadata = read_image_metadata(&file_path);
+        assert_eq!((metadata.width, metadata.height), (Some(40), Some(20)));
+        assert_eq!(metadata.orientation(), Some("landscape"));
+        assert_eq!(metadata.taken_at, None);
+        assert_eq!(metadata.latitude, None);
+    }
+
+    #[test]
+    fn test_gps_coordinate_conversion() {
+        let rational = |num, denom| Rational { num, denom };
+        // 9° 1' 48" N, 38° 44' 24" E (Addis Ababa)
+        let latitude = Value::Rational(vec![rational(9, 1), rational(1, 1), rational(48, 1)]);
+        let longitude = Value::Rational(vec![rational(38, 1), rational(44, 1), rational(2400, 100)]);
+        let lat = gps_coordinate(Some(&latitude), Some("N"), "S").unwrap();
+        let lon = gps_coordinate(Some(&longitude), Some("W"), "W").unwrap();
+        assert!((lat - 9.03).abs() < 1e-9);
+        assert!((lon + 38.74).abs() < 1e-9);
+        assert_eq!(gps_coordinate(None, Some("N"), "S"), None);
+    }
+
+    #[test]
+    fn test_camera_model_and_date_formatting() {
+        assert_eq!(camera_model(Some("Canon".into()), Some("Canon EOS R6".into())).as_deref(), Some("Canon EOS R6"));
+        assert_eq!(camera_model(Some("Apple".into()), Some("iPhone 15".into())).as_deref(), Some("Apple iPhone 15"));
+        assert_eq!(camera_model(None, None), None);
+
+        let date = DateTime::from_ascii(b"2024:05:01 08:30:09").unwrap();
+        assert_eq!(format_exif_date(&date), "2024-05-01T08:30:09");
+    }
+}
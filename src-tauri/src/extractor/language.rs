@@ -0,0 +1,177 @@
+// src-tauri/src/extractor/language.rs
+
+//! Language detection for whole documents and for individual chunks. Bilingual
+//! documents are detected chunk by chunk so every chunk is embedded with the model
+//! (and stored in the table) that suits its own language.
+
+use serde::{Deserialize, Serialize};
+use whatlang::{detect, Lang};
+
+use crate::chunker::TextChunk;
+
+/// Below this confidence a chunk inherits the language detected for its document
+pub const MIN_CHUNK_CONFIDENCE: f32 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DetectedLanguage {
+    English,
+    Amharic,
+    Arabic,
+    Chinese,
+    French,
+    German,
+    Hindi,
+    Italian,
+    Portuguese,
+    Russian,
+    Spanish,
+    Other,
+}
+
+impl DetectedLanguage {
+    fn from_whatlang(lang: Lang) -> Self {
+        match lang {
+            Lang::Eng => DetectedLanguage::English,
+            Lang::Amh => DetectedLanguage::Amharic,
+            Lang::Ara => DetectedLanguage::Arabic,
+            Lang::Cmn => DetectedLanguage::Chinese,
+            Lang::Fra => DetectedLanguage::French,
+            Lang::Deu => DetectedLanguage::German,
+            Lang::Hin => DetectedLanguage::Hindi,
+            Lang::Ita => DetectedLanguage::Italian,
+            Lang::Por => DetectedLanguage::Portuguese,
+            Lang::Rus => DetectedLanguage::Russian,
+            Lang::Spa => DetectedLanguage::Spanish,
+            _ => DetectedLanguage::Other,
+        }
+    }
+
+    /// ISO 639-1 code stored with every chunk; `und` (undetermined) for other languages
+    pub fn code(&self) -> &'static str {
+        match self {
+            DetectedLanguage::English => "en",
+            DetectedLanguage::Amharic => "am",
+            DetectedLanguage::Arabic => "ar",
+            DetectedLanguage::Chinese => "zh",
+            DetectedLanguage::French => "fr",
+            DetectedLanguage::German => "de",
+            DetectedLanguage::Hindi => "hi",
+            DetectedLanguage::Italian => "it",
+            DetectedLanguage::Portuguese => "pt",
+            DetectedLanguage::Russian => "ru",
+            DetectedLanguage::Spanish => "es",
+            DetectedLanguage::Other => "und",
+        }
+    }
+
+    /// Parses a code written by `code`; unknown codes map to `Other`
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "en" => DetectedLanguage::English,
+            "am" => DetectedLanguage::Amharic,
+            "ar" => DetectedLanguage::Arabic,
+            "zh" => DetectedLanguage::Chinese,
+            "fr" => DetectedLanguage::French,
+            "de" => DetectedLanguage::German,
+            "hi" => DetectedLanguage::Hindi,
+            "it" => DetectedLanguage::Italian,
+            "pt" => DetectedLanguage::Portuguese,
+            "ru" => DetectedLanguage::Russian,
+            "es" => DetectedLanguage::Spanish,
+            _ => DetectedLanguage::Other,
+        }
+    }
+
+    /// English, and text in no language the detector recognizes (numbers, code, tables), is
+    /// embedded with the English model into the `documents` table; every other language goes
+    /// through the multilingual E5 model into the `amharic_documents` table
+    pub fn uses_multilingual_model(&self) -> bool {
+        !matches!(self, DetectedLanguage::English | DetectedLanguage::Other)
+    }
+}
+
+/// A detected language together with the detector's confidence (0.0 to 1.0)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageDetection {
+    pub language: DetectedLanguage,
+    pub confidence: f32,
+}
+
+/// Detects the language of a text
+pub fn detect_language(text: &str) -> DetectedLanguage {
+    detect_language_with_confidence(text).language
+}
+
+/// Detects the language of a text along with the detector's confidence
+pub fn detect_language_with_confidence(text: &str) -> LanguageDetection {
+    match detect(text) {
+        Some(info) => LanguageDetection {
+            language: DetectedLanguage::from_whatlang(info.lang()),
+            confidence: info.confidence() as f32,
+        },
+        None => LanguageDetection { language: DetectedLanguage::Other, confidence: 0.0 },
+    }
+}
+
+/// Detects the language of a chunk. Short or ambiguous chunks, for which the detector is
+/// not confident, take the language of their document instead.
+pub fn detect_chunk_language(text: &str, document: &LanguageDetection) -> LanguageDetection {
+    let detection = detect_language_with_confidence(text);
+    if detection.confidence >= MIN_CHUNK_CONFIDENCE {
+        detection
+    } else {
+        document.clone()
+    }
+}
+
+/// Records the detected language and confidence on every chunk
+pub fn assign_chunk_languages(chunks: &mut [TextChunk], document: &LanguageDetection) {
+    for chunk in chunks {
+        let detection = detect_chunk_language(&chunk.text, document);
+        chunk.language = Some(detection.language);
+        chunk.language_confidence = Some(detection.confidence);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_languages_with_confidence() {
+        let amharic = detect_language_with_confidence("ኢትዮጵያ በምስራቅ አፍሪካ የምትገኝ አገር ናት። ዋና ከተማዋ አዲስ አበባ ናት።");
+        assert_eq!(amharic.language, DetectedLanguage::Amharic);
+        assert!(amharic.confidence >= MIN_CHUNK_CONFIDENCE);
+
+        let french = detect_language("Le rapport annuel présente les résultats financiers de la société.");
+        assert_eq!(french, DetectedLanguage::French);
+        assert_eq!(detect_language(""), DetectedLanguage::Other);
+    }
+
+    #[test]
+    fn test_bilingual_document_chunks_get_their_own_language() {
+        let document = LanguageDetection { language: DetectedLanguage::English, confidence: 0.9 };
+        let mut chunks = vec![
+            TextChunk::new("The quarterly budget review covers spending across all regional offices.".to_string()),
+            TextChunk::new("የሩብ ዓመቱ የበጀት ግምገማ በሁሉም የክልል ቢሮዎች ያለውን ወጪ ይሸፍናል።".to_string()),
+            TextChunk::new("Q3".to_string()),
+        ];
+        assign_chunk_languages(&mut chunks, &document);
+
+        assert_eq!(chunks[0].language, Some(DetectedLanguage::English));
+        assert_eq!(chunks[1].language, Some(DetectedLanguage::Amharic));
+        // Too short to detect: inherits the document language
+        assert_eq!(chunks[2].language, Some(DetectedLanguage::English));
+        assert_eq!(chunks[2].language_confidence, Some(0.9));
+        assert!(chunks[1].language.as_ref().unwrap().uses_multilingual_model());
+        assert!(!chunks[0].language.as_ref().unwrap().uses_multilingual_model());
+        assert!(!DetectedLanguage::Other.uses_multilingual_model());
+    }
+
+    #[test]
+    fn test_language_codes_round_trip() {
+        for language in [DetectedLanguage::English, DetectedLanguage::Amharic, DetectedLanguage::Spanish, DetectedLanguage::Other] {
+            assert_eq!(DetectedLanguage::from_code(language.code()), language);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+// src-tauri/src/extractor/markup.rs
+
+//! Conversion of (X)HTML markup to plain text, shared by the PDF stream and the HTML extractor.
+
+/// Elements whose end (or `<br>`) starts a new line in the extracted text
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p", "br", "div", "li", "tr", "h1", "h2", "h3", "h4", "h5", "h6", "section", "article", "blockquote", "pre",
+    "table", "ul", "ol", "title",
+];
+
+/// Elements whose content is never document text
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style", "head", "noscript", "template", "svg"];
+
+/// Converts an (X)HTML fragment to plain text, turning block-level tags into line breaks.
+/// Comments and the content of scripts, styles and the document head are dropped.
+pub fn markup_to_text(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut rest = markup;
+
+    while let Some(tag_start) = rest.find('<') {
+        text.push_str(&decode_entities(&rest[..tag_start]));
+        let after_open = &rest[tag_start..];
+
+        if after_open.starts_with("<!--") {
+            rest = after_open.find("-->").map_or("", |end| &after_open[end + 3..]);
+            continue;
+        }
+
+        let Some(tag_len) = after_open.find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &after_open[1..tag_len];
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        rest = &after_open[tag_len + 1..];
+
+        if !is_closing && !tag.ends_with('/') && SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            rest = skip_element(rest, &name);
+            continue;
+        }
+        if BLOCK_ELEMENTS.contains(&name.as_str()) && (is_closing || name == "br") {
+            text.push('\n');
+        }
+    }
+    text.push_str(&decode_entities(rest));
+
+    // Collapse runs of blank lines into a single paragraph break
+    let mut cleaned = String::with_capacity(text.len());
+    let mut pending_break = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            pending_break = true;
+            continue;
+        }
+        if !cleaned.is_empty() {
+            cleaned.push_str(if pending_break { "\n\n" } else { "\n" });
+        }
+        pending_break = false;
+        cleaned.push_str(line);
+    }
+    cleaned
+}
+
+/// Returns the markup following the closing tag of `name`, or nothing if it is never closed
+fn skip_element<'a>(markup: &'a str, name: &str) -> &'a str {
+    let closing = format!("</{}", name);
+    // ASCII lowercasing keeps byte offsets identical to the original
+    let lowered = markup.to_ascii_lowercase();
+    lowered
+        .find(&closing)
+        .and_then(|start| markup[start..].find('>').map(|end| &markup[start + end + 1..]))
+        .unwrap_or("")
+}
+
+/// Decoded, trimmed text of the first `name` element (e.g. `dc:title`), if present and non-empty
+pub fn element_text(markup: &str, name: &str) -> Option<String> {
+    let lowered = markup.to_ascii_lowercase();
+    let opening = format!("<{}", name.to_ascii_lowercase());
+    let mut search_from = 0;
+    while let Some(found) = lowered[search_from..].find(&opening) {
+        let start = search_from + found;
+        let after_name = start + opening.len();
+        search_from = after_name;
+        // `<title>` must not match `<titlebar>`
+        if !lowered[after_name..].starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            continue;
+        }
+        let content_start = after_name + lowered[after_name..].find('>')? + 1;
+        if lowered[..content_start].ends_with("/>") {
+            return None;
+        }
+        let closing = format!("</{}", name.to_ascii_lowercase());
+        let content_end = content_start + lowered[content_start..].find(&closing)?;
+        let text = decode_entities(markup[content_start..content_end].trim());
+        return if text.is_empty() { None } else { Some(text) };
+    }
+    None
+}
+
+/// Value of the `content` attribute of `<meta name="...">` in an HTML page
+pub fn meta_content(markup: &str, name: &str) -> Option<String> {
+    let lowered = markup.to_ascii_lowercase();
+    let mut rest_start = 0;
+    while let Some(found) = lowered[rest_start..].find("<meta") {
+        let start = rest_start + found;
+        let end = start + lowered[start..].find('>')?;
+        rest_start = end;
+        let tag = &markup[start..end];
+        if attribute(tag, "name").is_some_and(|value| value.eq_ignore_ascii_case(name)) {
+            return attribute(tag, "content")
+                .map(|value| decode_entities(value.trim()))
+                .filter(|value| !value.is_empty());
+        }
+    }
+    None
+}
+
+/// Value of a quoted attribute within a single tag
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let lowered = tag.to_ascii_lowercase();
+    let pattern = format!("{}=", name);
+    let mut search_from = 0;
+    while let Some(found) = lowered[search_from..].find(&pattern) {
+        let start = search_from + found;
+        search_from = start + pattern.len();
+        // `name=` must not match inside `data-name=`
+        if !lowered[..start].ends_with(|c: char| c.is_whitespace()) {
+            continue;
+        }
+        let value = &tag[search_from..];
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &value[1..];
+        return value.find(quote).map(|end| &value[..end]);
+    }
+    None
+}
+
+/// Decodes the XML/HTML entities Tika and web pages commonly emit
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let entity_end = after.find(';').filter(|end| *end <= 10);
+        let replacement = entity_end.and_then(|end| {
+            let entity = &after[..end];
+            match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32)
+                }
+                _ if entity.starts_with('#') => entity[1..].parse::<u32>().ok().and_then(char::from_u32),
+                _ => None,
+            }
+        });
+        match (replacement, entity_end) {
+            (Some(c), Some(end)) => {
+                decoded.push(c);
+                rest = &after[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = after;
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_text_and_meta_content() {
+        let page = r#"<html><head><title>Budget &amp; plan</title><titlebar>x</titlebar>
+            <meta charset="utf-8"><meta name="Author" content="Abebe Kebede">
+            <meta data-name="keywords" content="wrong"><meta name='keywords' content='finance, 2024'></head></html>"#;
+        assert_eq!(element_text(page, "title").as_deref(), Some("Budget & plan"));
+        assert_eq!(element_text(page, "h1"), None);
+        assert_eq!(meta_content(page, "author").as_deref(), Some("Abebe Kebede"));
+        assert_eq!(meta_content(page, "keywords").as_deref(), Some("finance, 2024"));
+        assert_eq!(element_text("<cp:coreProperties><dc:creator>Jane</dc:creator>", "dc:creator").as_deref(), Some("Jane"));
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &#65;&#x42; &unknown; &"), "a <b> AB &unknown; &");
+    }
+
+    #[test]
+    fn test_markup_to_text_drops_scripts_and_comments() {
+        let html = "<html><head><title>Ignored</title><style>p { color: red }</style></head>\
+                    <body><!-- note > here --><h1>Title</h1><p>First &amp; second</p>\
+                    <SCRIPT>var x = '<p>';</SCRIPT><p>Last<br/>line</p></body></html>";
+        assert_eq!(markup_to_text(html), "Title\nFirst & second\nLast\nline");
+    }
+}
@@ -0,0 +1,119 @@
+// src-tauri/src/extractor/metadata.rs
+
+//! Document-level metadata (title, author, creation date, tags) collected by the
+//! extractors and stored alongside every chunk of the document.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Descriptive metadata of a document; every field is optional
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// Creation date as reported by the document, ISO 8601 / RFC 3339 when available
+    pub created: Option<String>,
+    /// Keywords, subjects or categories
+    pub tags: Vec<String>,
+}
+
+/// Tika metadata keys, most specific first
+const TITLE_KEYS: &[&str] = &["dc:title", "title", "pdf:docinfo:title"];
+const AUTHOR_KEYS: &[&str] = &["dc:creator", "meta:author", "Author", "pdf:docinfo:creator"];
+const CREATED_KEYS: &[&str] = &["dcterms:created", "meta:creation-date", "Creation-Date", "pdf:docinfo:created"];
+const TAG_KEYS: &[&str] = &["meta:keyword", "pdf:docinfo:keywords", "Keywords", "cp:keywords", "dc:subject"];
+
+impl DocumentMetadata {
+    /// Reads the metadata map returned by Tika (`extractous`)
+    pub fn from_tika(metadata: &HashMap<String, Vec<String>>) -> Self {
+        let first = |keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                metadata
+                    .get(*key)?
+                    .iter()
+                    .map(|value| value.trim())
+                    .find(|value| !value.is_empty())
+                    .map(str::to_string)
+            })
+        };
+        let tags = TAG_KEYS
+            .iter()
+            .filter_map(|key| metadata.get(*key))
+            .flatten()
+            .flat_map(|value| split_tags(value))
+            .collect();
+
+        DocumentMetadata {
+            title: first(TITLE_KEYS),
+            author: first(AUTHOR_KEYS),
+            created: first(CREATED_KEYS),
+            tags: dedup_tags(tags),
+        }
+    }
+
+    /// Returns true if no field is set
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.author.is_none() && self.created.is_none() && self.tags.is_empty()
+    }
+
+    /// Tags in the form stored in the database: comma separated, `None` when there are none
+    pub fn tags_column(&self) -> Option<String> {
+        if self.tags.is_empty() {
+            None
+        } else {
+            Some(self.tags.join(", "))
+        }
+    }
+}
+
+/// Splits a keyword field on commas and semicolons
+pub fn split_tags(value: &str) -> Vec<String> {
+    value
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Removes repeated tags (case-insensitive), keeping the first spelling
+pub fn dedup_tags(tags: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        if !unique.iter().any(|existing| existing.eq_ignore_ascii_case(&tag)) {
+            unique.push(tag);
+        }
+    }
+    unique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tika_prefers_dublin_core_keys() {
+        let mut metadata = HashMap::new();
+        metadata.insert("dc:title".to_string(), vec!["Quarterly report".to_string()]);
+        metadata.insert("title".to_string(), vec!["ignored".to_string()]);
+        metadata.insert("meta:author".to_string(), vec!["  ".to_string()]);
+        metadata.insert("pdf:docinfo:creator".to_string(), vec!["Jane Doe".to_string()]);
+        metadata.insert("dcterms:created".to_string(), vec!["2024-03-04T10:00:00Z".to_string()]);
+        metadata.insert("meta:keyword".to_string(), vec!["finance; budget".to_string()]);
+        metadata.insert("dc:subject".to_string(), vec!["Budget, Q3".to_string()]);
+
+        let parsed = DocumentMetadata::from_tika(&metadata);
+        assert_eq!(parsed.title.as_deref(), Some("Quarterly report"));
+        assert_eq!(parsed.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(parsed.created.as_deref(), Some("2024-03-04T10:00:00Z"));
+        assert_eq!(parsed.tags, vec!["finance", "budget", "Q3"]);
+        assert_eq!(parsed.tags_column().as_deref(), Some("finance, budget, Q3"));
+    }
+
+    #[test]
+    fn test_empty_metadata() {
+        let parsed = DocumentMetadata::from_tika(&HashMap::new());
+        assert!(parsed.is_empty());
+        assert_eq!(parsed.tags_column(), None);
+    }
+}
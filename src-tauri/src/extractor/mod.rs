@@ -0,0 +1,657 @@
+// src-tauri/src/extractor/mod.rs
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{mpsc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use log::{debug, error};
+use once_cell::sync::Lazy;
+use thiserror::Error;
+use sha2::{Sha256, Digest};
+use serde::{Serialize, Deserialize};
+
+use crate::chunker::TextChunk;
+
+mod amharic;
+mod archive;
+mod code;
+mod docx;
+mod email;
+mod html;
+mod image_metadata;
+mod language;
+mod markup;
+mod metadata;
+mod ocr;
+mod office;
+mod pdf;
+mod plain_text;
+mod registry;
+mod spreadsheet;
+
+pub use amharic::{is_ethiopic, normalize_amharic};
+pub use archive::{
+    archive_member_path, extract_archive, extract_archive_member, get_archive_config, is_archive,
+    is_archive_member_path, set_archive_config, ArchiveConfig, ArchiveMember, ARCHIVE_MEMBER_SEPARATOR,
+};
+pub use code::SourceCodeExtractor;
+pub use docx::DocxExtractor;
+pub use email::EmailExtractor;
+pub use html::HtmlExtractor;
+pub use image_metadata::{read_image_metadata, ImageMetadata};
+pub use language::{
+    assign_chunk_languages, detect_chunk_language, detect_language, detect_language_with_confidence,
+    DetectedLanguage, LanguageDetection, MIN_CHUNK_CONFIDENCE,
+};
+pub use metadata::{split_tags, DocumentMetadata};
+pub use ocr::{extract_image_text, get_ocr_config, is_ocr_active, is_ocr_available, set_ocr_config, OcrConfig};
+pub use office::TikaExtractor;
+pub use pdf::{is_paged_document, stream_pdf_pages, PageText, PdfExtractor, PdfPageReceiver, PdfPageStream, PAGE_BREAK};
+pub use plain_text::PlainTextExtractor;
+pub use spreadsheet::SpreadsheetExtractor;
+pub use registry::{
+    find_extractor, find_extractor_for_mime_type, is_supported_text_file, register_extractor,
+    supported_text_extensions, ExtractorRegistry, TextExtractor,
+};
+
+#[derive(Error, Debug)]
+pub enum ExtractorError {
+    #[error("IO Error reading file {0}: {1}")]
+    IoError(String, #[source] std::io::Error),
+    #[error("Unsupported file type: {0}")]
+    UnsupportedFileType(String),
+    #[error("PDF extraction failed for {0}: {1}")]
+    PdfExtractionFailed(String, String),
+    #[error("Image file handling: {0}")]
+    ImageHandling(String),
+    #[error("DOCX extraction failed for {0}: {1}")]
+    DocxExtractionFailed(String, String),
+    #[error("Text extraction failed for {0}: {1}")]
+    ExtractionFailed(String, String),
+    #[error("Path is not valid UTF-8: {0}")]
+    InvalidPath(String),
+    #[error("Extraction timed out for {0} after {1} seconds")]
+    Timeout(String, u64),
+    #[error("Extractor crashed on {0}: {1}")]
+    BackendPanicked(String, String),
+    #[error("Invalid archive configuration: {0}")]
+    InvalidArchiveConfig(String),
+    #[error("Invalid OCR configuration: {0}")]
+    InvalidOcrConfig(String),
+    #[error("OCR failed for {0}: {1}")]
+    OcrFailed(String, String),
+}
+
+#[derive(Debug, Clone)]
+pub struct TextExtractionResult {
+    pub text: String,
+    pub language: DetectedLanguage,
+    /// Confidence of the language detection, 0.0 to 1.0
+    pub language_confidence: f32,
+    /// Sections with their location (sheet, rows, ...) for structured formats; empty otherwise
+    pub sections: Vec<TextChunk>,
+    /// Title, author, creation date and tags reported by the document
+    pub metadata: DocumentMetadata,
+}
+
+impl TextExtractionResult {
+    /// Language of the whole document, the fallback for chunks too short to detect
+    pub fn language_detection(&self) -> LanguageDetection {
+        LanguageDetection { language: self.language.clone(), confidence: self.language_confidence }
+    }
+}
+
+/// Output of a `TextExtractor`
+#[derive(Debug, Clone, Default)]
+pub struct ExtractedText {
+    /// Full text of the document
+    pub text: String,
+    /// Sections carrying their own location metadata; chunked individually when present
+    pub sections: Vec<TextChunk>,
+    /// Document-level metadata, empty when the format has none
+    pub metadata: DocumentMetadata,
+}
+
+impl ExtractedText {
+    /// Builds the result of a structure-aware extractor; the full text is the sections joined
+    pub fn from_sections(sections: Vec<TextChunk>) -> Self {
+        let text = sections.iter().map(|section| section.text.as_str()).collect::<Vec<_>>().join("\n\n");
+        ExtractedText { text, sections, metadata: DocumentMetadata::default() }
+    }
+
+    /// Attaches document-level metadata
+    pub fn with_metadata(mut self, metadata: DocumentMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+impl From<String> for ExtractedText {
+    fn from(text: String) -> Self {
+        ExtractedText { text, sections: Vec::new(), metadata: DocumentMetadata::default() }
+    }
+}
+
+/// Content type enum to distinguish between different file types
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContentType {
+    Text,
+    /// Source code, split by top-level symbol
+    Code,
+    Image,
+    /// ZIP or tar archive whose members are indexed individually
+    Archive,
+    Unsupported,
+}
+
+/// Supported image extensions; text formats are declared by the extractor registry
+pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "svg"];
+
+/// Determines the content type of a file based on its extension and the extractor registry
+pub fn get_content_type(file_path: &Path) -> ContentType {
+    if let Some(extractor) = find_extractor(file_path) {
+        return extractor.content_type();
+    }
+    if is_archive(file_path) {
+        return if get_archive_config().enabled { ContentType::Archive } else { ContentType::Unsupported };
+    }
+    match file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|s| s.to_lowercase())
+    {
+        Some(ext) if SUPPORTED_IMAGE_EXTENSIONS.contains(&ext.as_str()) => ContentType::Image,
+        _ => ContentType::Unsupported,
+    }
+}
+
+/// Longest time a single-shot extraction may take before the file is given up on
+pub const EXTRACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Longest time a paged document may take to stream, measured between pages
+pub const PAGED_EXTRACTION_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Most extraction worker threads alive at once
+pub const MAX_EXTRACTION_WORKERS: usize = 8;
+
+// Slots for extraction worker threads, shared by every `run_with_timeout` call
+static EXTRACTION_WORKERS: Lazy<WorkerLimit> = Lazy::new(|| WorkerLimit::new(MAX_EXTRACTION_WORKERS));
+
+/// Counting limit on live worker threads
+struct WorkerLimit {
+    live: Mutex<usize>,
+    released: Condvar,
+    capacity: usize,
+}
+
+/// A claimed worker slot, given back when dropped
+struct WorkerSlot<'a> {
+    limit: &'a WorkerLimit,
+}
+
+impl WorkerLimit {
+    fn new(capacity: usize) -> Self {
+        WorkerLimit { live: Mutex::new(0), released: Condvar::new(), capacity }
+    }
+
+    /// Claims a slot, waiting up to `timeout` for one to be given back
+    fn acquire(&self, timeout: Duration) -> Option<WorkerSlot<'_>> {
+        let live = self.live.lock().unwrap();
+        let (mut live, _) = self
+            .released
+            .wait_timeout_while(live, timeout, |live| *live >= self.capacity)
+            .unwrap();
+        if *live >= self.capacity {
+            return None;
+        }
+        *live += 1;
+        Some(WorkerSlot { limit: self })
+    }
+}
+
+impl Drop for WorkerSlot<'_> {
+    fn drop(&mut self) {
+        *self.limit.live.lock().unwrap() -= 1;
+        self.limit.released.notify_one();
+    }
+}
+
+/// Runs an extractor backend call, turning a panic inside it into `ExtractorError::BackendPanicked`
+fn catch_backend_panic<T>(file_path: &Path, call: impl FnOnce() -> T) -> Result<T, ExtractorError> {
+    panic::catch_unwind(AssertUnwindSafe(call)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        error!("Extractor panicked on {}: {}", file_path.display(), message);
+        ExtractorError::BackendPanicked(file_path.display().to_string(), message)
+    })
+}
+
+/// Extracts text content from a supported file.
+///
+/// The extractor is chosen from the registry by file extension.
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the file.
+///
+/// # Returns
+///
+/// * `Ok(String)` containing the extracted text content.
+/// * `Err(ExtractorError)` if the file is unsupported or cannot be read.
+pub fn extract_text(file_path: &Path) -> Result<TextExtractionResult, ExtractorError> {
+    extract_text_with_timeout(file_path, EXTRACTION_TIMEOUT)
+}
+
+/// Extracts text like `extract_text`, giving up with `ExtractorError::Timeout` after `timeout`.
+///
+/// The extractor backend runs on a worker thread so a pathological file cannot block
+/// the caller; a panic in the backend is reported as `ExtractorError::BackendPanicked`.
+pub fn extract_text_with_timeout(file_path: &Path, timeout: Duration) -> Result<TextExtractionResult, ExtractorError> {
+    run_with_timeout(file_path, timeout, extract_text_blocking)
+}
+
+/// Runs a blocking extraction step on a worker thread, giving up after `timeout`.
+///
+/// The caller's thread blocks while it waits, so async code must call this through
+/// `tokio::task::spawn_blocking`. A backend call cannot be cancelled: a worker that timed
+/// out keeps running, and keeps its slot, until the call returns. At most
+/// `MAX_EXTRACTION_WORKERS` workers are alive at once; when every slot is held by a stuck
+/// worker, further extractions time out waiting for a slot instead of spawning threads.
+fn run_with_timeout<T: Send + 'static>(
+    file_path: &Path,
+    timeout: Duration,
+    work: fn(&Path) -> Result<T, ExtractorError>,
+) -> Result<T, ExtractorError> {
+    let started = Instant::now();
+    let Some(slot) = EXTRACTION_WORKERS.acquire(timeout) else {
+        error!("No extraction worker free after {:?}: {}", timeout, file_path.display());
+        return Err(ExtractorError::Timeout(file_path.display().to_string(), timeout.as_secs()));
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let worker_path = file_path.to_path_buf();
+    let spawn_result = thread::Builder::new()
+        .name("text-extractor".to_string())
+        .spawn(move || {
+            let _slot = slot;
+            let result = catch_backend_panic(&worker_path, || work(&worker_path)).and_then(|r| r);
+            // The receiver is gone if the caller already timed out
+            let _ = sender.send(result);
+        });
+    if let Err(e) = spawn_result {
+        return Err(ExtractorError::IoError(file_path.display().to_string(), e));
+    }
+
+    match receiver.recv_timeout(timeout.saturating_sub(started.elapsed())) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            error!("Text extraction timed out after {:?}: {}", timeout, file_path.display());
+            Err(ExtractorError::Timeout(file_path.display().to_string(), timeout.as_secs()))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(ExtractorError::BackendPanicked(
+            file_path.display().to_string(),
+            "extraction worker exited without a result".to_string(),
+        )),
+    }
+}
+
+fn extract_text_blocking(file_path: &Path) -> Result<TextExtractionResult, ExtractorError> {
+    debug!("Attempting to extract text from: {}", file_path.display());
+
+    let extension = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|s| s.to_lowercase())
+        .ok_or_else(|| {
+            error!("File has no extension: {}", file_path.display());
+            ExtractorError::UnsupportedFileType("No extension".to_string())
+        })?;
+
+    let extractor = find_extractor(file_path).ok_or_else(|| {
+        error!("Unsupported file type attempted: {}", extension);
+        ExtractorError::UnsupportedFileType(extension.clone())
+    })?;
+
+    debug!("Using '{}' extractor for {}", extractor.name(), file_path.display());
+    let extracted = extractor.extract(file_path)?;
+    let detection = detect_language_with_confidence(&extracted.text);
+    // Amharic spelling variants are folded before chunking so they embed alike
+    let sections = extracted
+        .sections
+        .into_iter()
+        .map(|section| TextChunk { text: normalize_amharic(&section.text).into_owned(), ..section })
+        .collect();
+    Ok(TextExtractionResult {
+        text: normalize_amharic(&extracted.text).into_owned(),
+        language: detection.language,
+        language_confidence: detection.confidence,
+        sections,
+        metadata: extracted.metadata,
+    })
+}
+
+/// Handles an image file by validating it exists and returning its path as a string
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the image file
+///
+/// # Returns
+///
+/// * `Ok(String)` - The validated path to the image file
+/// * `Err(ExtractorError)` - If the file is not a valid image or doesn't exist
+pub fn process_image(file_path: &Path) -> Result<String, ExtractorError> {
+    debug!("Processing image file: {}", file_path.display());
+    
+    // Check if file exists
+    if !file_path.exists() {
+        error!("Image file does not exist: {}", file_path.display());
+        return Err(ExtractorError::IoError(
+            file_path.display().to_string(),
+            std::io::Error::new(std::io::ErrorKind::NotFound, "File not found"),
+        ));
+    }
+    
+    // Validate the extension
+    let extension = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|s| s.to_lowercase());
+        
+    match extension.as_deref() {
+        Some(ext) if SUPPORTED_IMAGE_EXTENSIONS.contains(&ext) => {
+            // File exists and has valid extension - return absolute path
+            match file_path.canonicalize() {
+                Ok(abs_path) => Ok(abs_path.to_string_lossy().to_string()),
+                Err(e) => {
+                    error!("Failed to get absolute path for {}: {}", file_path.display(), e);
+                    Err(ExtractorError::IoError(file_path.display().to_string(), e))
+                }
+            }
+        },
+        Some(ext) => {
+            error!("Not a supported image type: {}", ext);
+            Err(ExtractorError::UnsupportedFileType(ext.to_string()))
+        },
+        None => {
+            error!("Image file has no extension: {}", file_path.display());
+            Err(ExtractorError::UnsupportedFileType("No extension".to_string()))
+        }
+    }
+}
+
+/// Calculates the SHA256 hash of the given content.
+///
+/// # Arguments
+///
+/// * `content` - The string content to hash.
+///
+/// # Returns
+///
+/// * A hex-encoded string representing the SHA256 hash.
+pub fn calculate_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    let result = hasher.finalize();
+    // Format the hash bytes as a hex string
+    format!("{:x}", result)
+}
+
+/// Calculates the SHA256 hash of a file
+///
+/// # Arguments
+///
+/// * `file_path` - The path to the file
+///
+/// # Returns
+///
+/// * `Ok(String)` - A hex-encoded string representing the SHA256 hash
+/// * `Err(ExtractorError)` - If the file cannot be read
+pub fn calculate_file_hash(file_path: &Path) -> Result<String, ExtractorError> {
+    // Stream the file so large documents are not loaded into memory at once
+    let mut file = fs::File::open(file_path).map_err(|e| {
+        error!("Failed to open file for hashing {}: {}", file_path.display(), e);
+        ExtractorError::IoError(file_path.display().to_string(), e)
+    })?;
+
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).map_err(|e| {
+        error!("Failed to read file for hashing {}: {}", file_path.display(), e);
+        ExtractorError::IoError(file_path.display().to_string(), e)
+    })?;
+    let result = hasher.finalize();
+
+    // Format as hex string
+    Ok(format!("{:x}", result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*; // Import items from parent module
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_extract_txt_success() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.txt");
+        let mut file = fs::File::create(&file_path).unwrap();
+        let content = "This is a test text file.";
+        writeln!(file, "{}", content).unwrap();
+
+        let extracted_text = extract_text(&file_path).unwrap();
+        assert_eq!(extracted_text.text.trim(), content);
+    }
+
+     #[test]
+    fn test_extract_md_success() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.md");
+        let mut file = fs::File::create(&file_path).unwrap();
+        let content = "# Markdown Header\n\nThis is markdown content.";
+        writeln!(file, "{}", content).unwrap();
+
+        let extracted_text = extract_text(&file_path).unwrap();
+        assert_eq!(extracted_text.text.trim(), content);
+    }
+
+    #[test]
+    fn test_extract_unsupported_type() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.jpg");
+        let _ = fs::File::create(&file_path).unwrap(); // Create dummy file
+
+        let result = extract_text(&file_path);
+        assert!(matches!(result, Err(ExtractorError::UnsupportedFileType(ext)) if ext == "jpg"));
+    }
+
+    #[test]
+    fn test_extract_no_extension() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test");
+         let _ = fs::File::create(&file_path).unwrap(); // Create dummy file
+
+        let result = extract_text(&file_path);
+        assert!(matches!(result, Err(ExtractorError::UnsupportedFileType(ext)) if ext == "No extension"));
+    }
+
+    #[test]
+    fn test_extract_non_existent_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("non_existent.txt");
+
+        let result = extract_text(&file_path);
+        assert!(matches!(result, Err(ExtractorError::IoError(_, _))));
+    }
+
+    #[test]
+    fn test_calculate_hash() {
+        let content1 = "Hello, world!";
+        let content2 = "Hello, world!";
+        let content3 = "Hello, Rust!";
+
+        let hash1 = calculate_hash(content1);
+        let hash2 = calculate_hash(content2);
+        let hash3 = calculate_hash(content3);
+
+        // Check that identical content produces the same hash
+        assert_eq!(hash1, hash2);
+        // Check that different content produces different hashes
+        assert_ne!(hash1, hash3);
+
+        // Check against a known SHA256 hash value for "Hello, world!"
+        // You can verify this using online tools or `shasum -a 256`
+        let expected_hash = "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3";
+        assert_eq!(hash1, expected_hash);
+    }
+    
+    #[test]
+    fn test_process_image_valid_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.jpg");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "Mock image data").unwrap();
+        
+        let result = process_image(&file_path);
+        assert!(result.is_ok());
+    }
+    
+    #[test]
+    fn test_process_image_nonexistent_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("nonexistent.jpg");
+        
+        let result = process_image(&file_path);
+        assert!(matches!(result, Err(ExtractorError::IoError(_, _))));
+    }
+    
+    #[test]
+    fn test_process_image_unsupported_type() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.xyz");
+        let mut file = fs::File::create(&file_path).unwrap();
+        writeln!(file, "Mock file data").unwrap();
+        
+        let result = process_image(&file_path);
+        assert!(matches!(result, Err(ExtractorError::UnsupportedFileType(_))));
+    }
+    
+    #[test]
+    fn test_get_content_type() {
+        // Text files
+        assert_eq!(get_content_type(Path::new("document.pdf")), ContentType::Text);
+        assert_eq!(get_content_type(Path::new("notes.txt")), ContentType::Text);
+        assert_eq!(get_content_type(Path::new("readme.md")), ContentType::Text);
+        
+        // Image files
+        assert_eq!(get_content_type(Path::new("photo.jpg")), ContentType::Image);
+        assert_eq!(get_content_type(Path::new("image.jpeg")), ContentType::Image);
+        assert_eq!(get_content_type(Path::new("icon.png")), ContentType::Image);
+        assert_eq!(get_content_type(Path::new("animation.gif")), ContentType::Image);
+        assert_eq!(get_content_type(Path::new("photo.webp")), ContentType::Image);
+        assert_eq!(get_content_type(Path::new("screenshot.bmp")), ContentType::Image);
+        
+        // Unsupported files
+        assert_eq!(get_content_type(Path::new("archive.zip")), ContentType::Archive);
+        assert_eq!(get_content_type(Path::new("unknown")), ContentType::Unsupported);
+    }
+    
+    #[test]
+    fn test_calculate_file_hash() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.txt");
+        
+        // Create a file with known content
+        let content = "Hello, world!";
+        let mut file = fs::File::create(&file_path).unwrap();
+        write!(file, "{}", content).unwrap();
+        
+        // Calculate hash from file
+        let file_hash = calculate_file_hash(&file_path).unwrap();
+        
+        // Calculate hash directly from content for comparison
+        let content_hash = calculate_hash(content);
+        
+        // Hashes should match
+        assert_eq!(file_hash, content_hash);
+    }
+
+    #[test]
+    fn test_extract_txt_with_invalid_utf8() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("latin1.txt");
+        fs::write(&file_path, b"caf\xe9 au lait").unwrap();
+
+        let result = extract_text(&file_path).unwrap();
+        assert!(result.text.starts_with("caf"));
+        assert!(result.text.ends_with(" au lait"));
+    }
+
+    #[test]
+    fn test_extract_corrupt_docx_returns_error() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("broken.docx");
+        fs::write(&file_path, b"this is not a zip archive").unwrap();
+
+        assert!(matches!(
+            extract_text(&file_path),
+            Err(ExtractorError::DocxExtractionFailed(_, _)) | Err(ExtractorError::BackendPanicked(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_worker_limit_caps_live_workers() {
+        let limit = WorkerLimit::new(1);
+        let slot = limit.acquire(Duration::from_millis(10)).unwrap();
+        assert!(limit.acquire(Duration::from_millis(10)).is_none());
+        drop(slot);
+        assert!(limit.acquire(Duration::from_millis(10)).is_some());
+    }
+
+    #[test]
+    fn test_timed_out_worker_keeps_its_slot() {
+        fn slow_extraction(_: &Path) -> Result<(), ExtractorError> {
+            thread::sleep(Duration::from_millis(500));
+            Ok(())
+        }
+
+        let result = run_with_timeout(Path::new("slow.txt"), Duration::from_millis(20), slow_extraction);
+        assert!(matches!(result, Err(ExtractorError::Timeout(_, _))));
+        // The abandoned worker is still running and still counts against the limit
+        assert!(*EXTRACTION_WORKERS.live.lock().unwrap() >= 1);
+    }
+
+    #[test]
+    fn test_catch_backend_panic() {
+        let result: Result<(), ExtractorError> = catch_backend_panic(Path::new("bad.pdf"), || panic!("boom"));
+        match result {
+            Err(ExtractorError::BackendPanicked(path, message)) => {
+                assert_eq!(path, "bad.pdf");
+                assert_eq!(message, "boom");
+            }
+            other => panic!("Expected BackendPanicked, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get_content_type_uses_registry() {
+        for name in ["slides.pptx", "book.epub", "page.html", "letter.rtf", "notes.odt", "data.csv"] {
+            assert_eq!(get_content_type(Path::new(name)), ContentType::Text, "{} should be Text", name);
+        }
+        assert_eq!(get_content_type(Path::new("main.rs")), ContentType::Code);
+        assert_eq!(get_content_type(Path::new("app.py")), ContentType::Code);
+    }
+
+    #[test]
+    fn test_extract_source_code() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("lib.rs");
+        fs::write(&file_path, "fn main() {}\n").unwrap();
+        assert_eq!(extract_text(&file_path).unwrap().text, "fn main() {}\n");
+    }
+}
@@ -0,0 +1,181 @@
+// src-tauri/src/extractor/ocr.rs
+
+//! Optional OCR through Tesseract. When enabled and a `tesseract` binary is installed,
+//! PDF pages without a text layer and image files are run through it so scanned
+//! documents and screenshots become searchable as text.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::RwLock;
+use std::time::Duration;
+use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::office::TIKA_MAX_STRING_LENGTH;
+use super::{detect_language_with_confidence, normalize_amharic, run_with_timeout, DocumentMetadata, ExtractorError, TextExtractionResult, EXTRACTION_TIMEOUT};
+
+/// User-configurable OCR settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrConfig {
+    /// Whether OCR runs at all; it is slow, so it is off by default
+    pub enabled: bool,
+    /// Tesseract language codes, e.g. `eng`, `amh`; the matching traineddata must be installed
+    pub languages: Vec<String>,
+    /// Longest time Tesseract may spend on one page or image
+    pub timeout_seconds: u32,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        OcrConfig {
+            enabled: false,
+            languages: vec!["eng".to_string(), "amh".to_string()],
+            timeout_seconds: 120,
+        }
+    }
+}
+
+impl OcrConfig {
+    /// Checks the configuration for values Tesseract would reject
+    pub fn validate(&self) -> Result<(), ExtractorError> {
+        if self.languages.is_empty() {
+            return Err(ExtractorError::InvalidOcrConfig("at least one language is required".to_string()));
+        }
+        if let Some(language) = self
+            .languages
+            .iter()
+            .find(|language| language.is_empty() || !language.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        {
+            return Err(ExtractorError::InvalidOcrConfig(format!("'{}' is not a Tesseract language code", language)));
+        }
+        if self.timeout_seconds == 0 {
+            return Err(ExtractorError::InvalidOcrConfig("timeout_seconds must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Languages in Tesseract's `-l` format, e.g. `eng+amh`
+    pub fn language_spec(&self) -> String {
+        self.languages.join("+")
+    }
+}
+
+// Active OCR configuration used by the extractors
+static OCR_CONFIG: Lazy<RwLock<OcrConfig>> = Lazy::new(|| RwLock::new(OcrConfig::default()));
+
+// Whether a working `tesseract` binary is on the PATH, checked once
+static TESSERACT_AVAILABLE: Lazy<bool> = Lazy::new(|| {
+    let available = Command::new("tesseract")
+        .arg("--version")
+        .output()
+        .map_or(false, |output| output.status.success());
+    info!("Tesseract OCR {}", if available { "is available" } else { "was not found on the PATH" });
+    available
+});
+
+/// Get the active OCR configuration
+pub fn get_ocr_config() -> OcrConfig {
+    OCR_CONFIG.read().unwrap().clone()
+}
+
+/// Replace the active OCR configuration
+pub fn set_ocr_config(config: OcrConfig) -> Result<(), ExtractorError> {
+    config.validate()?;
+    info!("Updating OCR configuration: {:?}", config);
+    *OCR_CONFIG.write().unwrap() = config;
+    Ok(())
+}
+
+/// Returns true if the `tesseract` binary can be run
+pub fn is_ocr_available() -> bool {
+    *TESSERACT_AVAILABLE
+}
+
+/// Returns true if OCR is enabled and Tesseract is installed
+pub fn is_ocr_active() -> bool {
+    get_ocr_config().enabled && is_ocr_available()
+}
+
+/// Adds Tesseract to an extractor when OCR is active. PDF pages are only OCRed when they
+/// have no text layer of their own.
+pub(super) fn with_ocr(extractor: Extractor) -> Extractor {
+    let config = get_ocr_config();
+    if !config.enabled || !is_ocr_available() {
+        return extractor;
+    }
+    extractor
+        .set_ocr_config(
+            TesseractOcrConfig::new()
+                .set_language(&config.language_spec())
+                .set_timeout_seconds(config.timeout_seconds as i32),
+        )
+        .set_pdf_config(PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::AUTO))
+}
+
+/// Recognizes the text in an image. Returns `Ok(None)` when OCR is not active or the
+/// image contains no text.
+pub fn extract_image_text(file_path: &Path) -> Result<Option<TextExtractionResult>, ExtractorError> {
+    if !is_ocr_active() {
+        return Ok(None);
+    }
+    let timeout = EXTRACTION_TIMEOUT.max(Duration::from_secs(get_ocr_config().timeout_seconds as u64));
+    let text = run_with_timeout(file_path, timeout, ocr_image_blocking)?;
+    let text = text.trim();
+    if text.is_empty() {
+        debug!("No text recognized in {}", file_path.display());
+        return Ok(None);
+    }
+    debug!("Recognized {} characters in {}", text.chars().count(), file_path.display());
+    let detection = detect_language_with_confidence(text);
+    Ok(Some(TextExtractionResult {
+        text: normalize_amharic(text).into_owned(),
+        language: detection.language,
+        language_confidence: detection.confidence,
+        sections: Vec::new(),
+        metadata: DocumentMetadata::default(),
+    }))
+}
+
+fn ocr_image_blocking(file_path: &Path) -> Result<String, ExtractorError> {
+    let path_str = file_path
+        .to_str()
+        .ok_or_else(|| ExtractorError::InvalidPath(file_path.display().to_string()))?;
+    let extractor = with_ocr(Extractor::new().set_extract_string_max_length(TIKA_MAX_STRING_LENGTH));
+    let (text, _metadata) = extractor.extract_file_to_string(path_str).map_err(|e| {
+        error!("OCR failed for {}: {}", file_path.display(), e);
+        ExtractorError::OcrFailed(file_path.display().to_string(), e.to_string())
+    })?;
+    if text.trim().is_empty() {
+        warn!("OCR produced no text for {}", file_path.display());
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_includes_amharic() {
+        let config = OcrConfig::default();
+        assert!(!config.enabled);
+        assert_eq!(config.language_spec(), "eng+amh");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        assert!(OcrConfig { languages: Vec::new(), ..OcrConfig::default() }.validate().is_err());
+        assert!(OcrConfig { languages: vec!["eng; rm".to_string()], ..OcrConfig::default() }.validate().is_err());
+        assert!(OcrConfig { timeout_seconds: 0, ..OcrConfig::default() }.validate().is_err());
+        assert!(OcrConfig { languages: vec!["chi_sim".to_string()], ..OcrConfig::default() }.validate().is_ok());
+    }
+
+    #[test]
+    fn test_image_text_is_skipped_when_disabled() {
+        // OCR is off by default, so no backend is started
+        assert!(extract_image_text(Path::new("/nonexistent/screenshot.png")).unwrap().is_none());
+    }
+}
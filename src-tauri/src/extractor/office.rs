@@ -0,0 +1,83 @@
+// src-tauri/src/extractor/office.rs
+
+//! Office and e-book formats extracted through Apache Tika (via `extractous`).
+
+use std::path::Path;
+use extractous::Extractor;
+use log::{error, info};
+
+use super::registry::TextExtractor;
+use super::{catch_backend_panic, DocumentMetadata, ExtractedText, ExtractorError};
+
+/// Upper bound on the characters Tika returns for one document
+pub(super) const TIKA_MAX_STRING_LENGTH: i32 = 64 * 1024 * 1024;
+
+/// A document family extracted with Tika
+pub struct TikaExtractor {
+    name: &'static str,
+    extensions: &'static [&'static str],
+    mime_types: &'static [&'static str],
+}
+
+impl TikaExtractor {
+    pub const fn new(
+        name: &'static str,
+        extensions: &'static [&'static str],
+        mime_types: &'static [&'static str],
+    ) -> Self {
+        TikaExtractor { name, extensions, mime_types }
+    }
+
+    /// OpenDocument text
+    pub const fn odt() -> Self {
+        TikaExtractor::new("odt", &["odt"], &["application/vnd.oasis.opendocument.text"])
+    }
+
+    /// Rich Text Format
+    pub const fn rtf() -> Self {
+        TikaExtractor::new("rtf", &["rtf"], &["application/rtf", "text/rtf"])
+    }
+
+    /// PowerPoint presentations
+    pub const fn pptx() -> Self {
+        TikaExtractor::new(
+            "pptx",
+            &["pptx"],
+            &["application/vnd.openxmlformats-officedocument.presentationml.presentation"],
+        )
+    }
+
+    /// EPUB e-books
+    pub const fn epub() -> Self {
+        TikaExtractor::new("epub", &["epub"], &["application/epub+zip"])
+    }
+}
+
+impl TextExtractor for TikaExtractor {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        self.extensions
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        self.mime_types
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting text from {} with Tika: {}", self.name, file_path.display());
+        let path_str = file_path
+            .to_str()
+            .ok_or_else(|| ExtractorError::InvalidPath(file_path.display().to_string()))?;
+
+        let extractor = Extractor::new().set_extract_string_max_length(TIKA_MAX_STRING_LENGTH);
+        let (text, metadata) = catch_backend_panic(file_path, || extractor.extract_file_to_string(path_str))?
+            .map_err(|e| {
+                error!("Failed to extract {} {}: {}", self.name, file_path.display(), e);
+                ExtractorError::ExtractionFailed(file_path.display().to_string(), e.to_string())
+            })?;
+        Ok(ExtractedText::from(text).with_metadata(DocumentMetadata::from_tika(&metadata)))
+    }
+}
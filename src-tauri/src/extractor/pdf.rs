@@ -0,0 +1,449 @@
+// src-tauri/src/extractor/pdf.rs
+
+//! PDF extraction. Pages are streamed from the extractor's XHTML output one at a
+//! time so long documents can be indexed in bounded memory.
+
+use std::io::Read;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use extractous::{Extractor, StreamReader};
+use log::{error, info};
+use tokio::sync::{mpsc, oneshot};
+
+use super::amharic::normalize_amharic;
+use super::markup::markup_to_text;
+use super::ocr::with_ocr;
+use super::registry::TextExtractor;
+use super::{catch_backend_panic, DocumentMetadata, ExtractedText, ExtractorError, EXTRACTION_WORKERS};
+
+/// Character used to separate pages in extracted text (form feed, as in `pdftotext`)
+pub const PAGE_BREAK: char = '\u{0C}';
+
+/// Marker Tika emits at the start of every PDF page in its XHTML output
+const PDF_PAGE_MARKER: &str = "<div class=\"page\">";
+
+/// Number of bytes read from the extractor stream at a time
+const STREAM_READ_SIZE: usize = 64 * 1024;
+
+/// Number of decoded pages the page worker may run ahead of its consumer
+const PAGE_CHANNEL_CAPACITY: usize = 4;
+
+/// Text of a single page of a paged document
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageText {
+    /// 1-based page number
+    pub page_number: u32,
+    pub text: String,
+}
+
+/// Returns true if the file is extracted page by page (currently PDFs)
+pub fn is_paged_document(file_path: &Path) -> bool {
+    file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("pdf"))
+}
+
+/// Opens a PDF and returns an iterator over its pages.
+///
+/// Pages are decoded from the extractor's XHTML stream one at a time, so memory
+/// use is bounded by the size of a single page rather than the whole document.
+pub fn stream_pdf_pages(file_path: &Path) -> Result<PdfPageStream<StreamReader>, ExtractorError> {
+    let path_str = file_path
+        .to_str()
+        .ok_or_else(|| ExtractorError::InvalidPath(file_path.display().to_string()))?;
+    // Pages without a text layer (scans) are OCRed when OCR is enabled
+    let extractor = with_ocr(Extractor::new().set_xml_output(true));
+    let (reader, metadata) = catch_backend_panic(file_path, || extractor.extract_file(path_str))?.map_err(|e| {
+        error!("Failed to open PDF stream {}: {}", file_path.display(), e);
+        ExtractorError::PdfExtractionFailed(file_path.display().to_string(), e.to_string())
+    })?;
+    Ok(PdfPageStream::new(reader, file_path.display().to_string()).with_metadata(DocumentMetadata::from_tika(&metadata)))
+}
+
+/// Iterator over the pages of an XHTML document stream produced by the extractor
+pub struct PdfPageStream<R: Read> {
+    reader: R,
+    source: String,
+    buffer: String,
+    undecoded: Vec<u8>,
+    page_number: u32,
+    in_page: bool,
+    exhausted: bool,
+    metadata: DocumentMetadata,
+}
+
+impl<R: Read> PdfPageStream<R> {
+    pub fn new(reader: R, source: String) -> Self {
+        PdfPageStream {
+            reader,
+            source,
+            buffer: String::new(),
+            undecoded: Vec::new(),
+            page_number: 0,
+            in_page: false,
+            exhausted: false,
+            metadata: DocumentMetadata::default(),
+        }
+    }
+
+    /// Sets the document properties reported alongside the stream
+    pub fn with_metadata(mut self, metadata: DocumentMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Title, author, creation date and keywords of the document
+    pub fn metadata(&self) -> &DocumentMetadata {
+        &self.metadata
+    }
+
+    /// Reads the next block of the stream into the buffer, keeping incomplete UTF-8 sequences for later
+    fn fill_buffer(&mut self) -> Result<(), ExtractorError> {
+        let mut block = vec![0u8; STREAM_READ_SIZE];
+        let read = self.reader.read(&mut block).map_err(|e| ExtractorError::IoError(self.source.clone(), e))?;
+        if read == 0 {
+            self.exhausted = true;
+            if !self.undecoded.is_empty() {
+                self.buffer.push_str(&String::from_utf8_lossy(&self.undecoded));
+                self.undecoded.clear();
+            }
+            return Ok(());
+        }
+
+        self.undecoded.extend_from_slice(&block[..read]);
+        loop {
+            match std::str::from_utf8(&self.undecoded) {
+                Ok(decoded) => {
+                    self.buffer.push_str(decoded);
+                    self.undecoded.clear();
+                    return Ok(());
+                }
+                Err(e) => {
+                    let valid = e.valid_up_to();
+                    // Safe: the first `valid` bytes were just validated
+                    self.buffer.push_str(std::str::from_utf8(&self.undecoded[..valid]).unwrap_or_default());
+                    match e.error_len() {
+                        Some(invalid) => {
+                            self.buffer.push(char::REPLACEMENT_CHARACTER);
+                            self.undecoded.drain(..valid + invalid);
+                        }
+                        None => {
+                            // Incomplete sequence at the end of the block, wait for more bytes
+                            self.undecoded.drain(..valid);
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn emit_page(&mut self, raw_end: usize, skip: usize) -> PageText {
+        let raw: String = self.buffer.drain(..raw_end).collect();
+        self.buffer.drain(..skip);
+        self.page_number += 1;
+        let text = markup_to_text(&raw);
+        PageText { page_number: self.page_number, text: normalize_amharic(&text).into_owned() }
+    }
+}
+
+impl<R: Read> Iterator for PdfPageStream<R> {
+    type Item = Result<PageText, ExtractorError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.in_page {
+                if let Some(start) = self.buffer.find(PDF_PAGE_MARKER) {
+                    self.buffer.drain(..start + PDF_PAGE_MARKER.len());
+                    self.in_page = true;
+                    continue;
+                }
+                // Keep only what could be the beginning of a split marker
+                let mut keep_from = self.buffer.len().saturating_sub(PDF_PAGE_MARKER.len());
+                while !self.buffer.is_char_boundary(keep_from) {
+                    keep_from -= 1;
+                }
+                self.buffer.drain(..keep_from);
+            } else {
+                let next_page = self.buffer.find(PDF_PAGE_MARKER);
+                let body_end = self.buffer.find("</body>");
+                match (next_page, body_end) {
+                    (Some(next), end) if end.map_or(true, |end| next < end) => {
+                        return Some(Ok(self.emit_page(next, PDF_PAGE_MARKER.len())));
+                    }
+                    (_, Some(end)) => {
+                        self.in_page = false;
+                        return Some(Ok(self.emit_page(end, "</body>".len())));
+                    }
+                    _ if self.exhausted => {
+                        self.in_page = false;
+                        let end = self.buffer.len();
+                        return Some(Ok(self.emit_page(end, 0)));
+                    }
+                    _ => {}
+                }
+            }
+
+            if self.exhausted {
+                return None;
+            }
+            if let Err(e) = self.fill_buffer() {
+                self.exhausted = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Pages of a PDF decoded on a worker thread and received with a timeout per page.
+///
+/// A read from the extractor can block indefinitely, so the stream is never iterated on
+/// the consumer's thread. When a page times out the worker is abandoned: it exits as soon
+/// as its read returns and finds the receiver closed. The worker holds one of the
+/// `MAX_EXTRACTION_WORKERS` extraction slots until it exits, so stuck workers cannot pile up.
+pub struct PdfPageReceiver {
+    pages: mpsc::Receiver<Result<PageText, ExtractorError>>,
+    source: String,
+    timeout: Duration,
+    page_number: u32,
+    finished: bool,
+}
+
+impl PdfPageReceiver {
+    /// Opens a PDF on a worker thread, see `stream_pdf_pages`.
+    /// Returns the document properties once the extractor has opened the file.
+    pub async fn open(file_path: &Path, timeout: Duration) -> Result<(DocumentMetadata, Self), ExtractorError> {
+        let worker_path = file_path.to_path_buf();
+        Self::spawn(file_path.display().to_string(), timeout, move || stream_pdf_pages(&worker_path)).await
+    }
+
+    /// Runs `open` and iterates the stream it returns on a worker thread.
+    /// Opening the stream and decoding every page must each finish within `timeout`.
+    pub async fn spawn<R: Read + 'static>(
+        source: String,
+        timeout: Duration,
+        open: impl FnOnce() -> Result<PdfPageStream<R>, ExtractorError> + Send + 'static,
+    ) -> Result<(DocumentMetadata, Self), ExtractorError> {
+        // Waiting for a slot blocks, so it happens on the blocking thread pool
+        let slot = tokio::task::spawn_blocking(move || EXTRACTION_WORKERS.acquire(timeout))
+            .await
+            .map_err(|e| ExtractorError::BackendPanicked(source.clone(), e.to_string()))?;
+        let Some(slot) = slot else {
+            error!("No extraction worker free after {:?}: {}", timeout, source);
+            return Err(ExtractorError::Timeout(source, timeout.as_secs()));
+        };
+
+        let (opened_sender, opened) = oneshot::channel();
+        let (page_sender, pages) = mpsc::channel(PAGE_CHANNEL_CAPACITY);
+        let worker_source = source.clone();
+        thread::Builder::new()
+            .name("pdf-pages".to_string())
+            .spawn(move || {
+                let _slot = slot;
+                let worker_path = Path::new(&worker_source);
+                let stream = match catch_backend_panic(worker_path, open).and_then(|r| r) {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        let _ = opened_sender.send(Err(e));
+                        return;
+                    }
+                };
+                if opened_sender.send(Ok(stream.metadata().clone())).is_err() {
+                    return;
+                }
+                let streamed = catch_backend_panic(worker_path, || {
+                    for page in stream {
+                        // The receiver is closed once the consumer timed out or gave up
+                        if page_sender.blocking_send(page).is_err() {
+                            break;
+                        }
+                    }
+                });
+                if let Err(e) = streamed {
+                    let _ = page_sender.blocking_send(Err(e));
+                }
+            })
+            .map_err(|e| ExtractorError::IoError(source.clone(), e))?;
+
+        let metadata = match tokio::time::timeout(timeout, opened).await {
+            Ok(Ok(opened)) => opened?,
+            Ok(Err(_)) => {
+                return Err(ExtractorError::BackendPanicked(source, "page worker exited without a result".to_string()))
+            }
+            Err(_) => {
+                error!("Timed out opening {}", source);
+                return Err(ExtractorError::Timeout(source, timeout.as_secs()));
+            }
+        };
+        Ok((metadata, PdfPageReceiver { pages, source, timeout, page_number: 0, finished: false }))
+    }
+
+    /// Waits for the next page. Fails with `ExtractorError::Timeout` when the page takes
+    /// longer than the timeout, after which the stream is over.
+    pub async fn next(&mut self) -> Option<Result<PageText, ExtractorError>> {
+        if self.finished {
+            return None;
+        }
+        match tokio::time::timeout(self.timeout, self.pages.recv()).await {
+            Ok(Some(Ok(page))) => {
+                self.page_number = page.page_number;
+                Some(Ok(page))
+            }
+            Ok(Some(Err(e))) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(_) => {
+                self.finished = true;
+                self.pages.close();
+                error!("Timed out waiting for page {} of {}", self.page_number + 1, self.source);
+                Some(Err(ExtractorError::Timeout(self.source.clone(), self.timeout.as_secs())))
+            }
+        }
+    }
+}
+
+/// Extracts the full text of a PDF, joining pages with `PAGE_BREAK` so the chunker can keep page numbers
+pub struct PdfExtractor;
+
+impl TextExtractor for PdfExtractor {
+    fn name(&self) -> &'static str {
+        "pdf"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["pdf"]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &["application/pdf"]
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting text from PDF: {}", file_path.display());
+        let mut stream = stream_pdf_pages(file_path)?;
+        let mut pages = Vec::new();
+        for page in stream.by_ref() {
+            pages.push(page?.text);
+        }
+        Ok(ExtractedText::from(pages.join(&PAGE_BREAK.to_string())).with_metadata(stream.metadata().clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader that hands out at most `step` bytes per read, to exercise split markers and UTF-8 sequences
+    struct TrickleReader {
+        data: Vec<u8>,
+        position: usize,
+        step: usize,
+    }
+
+    impl Read for TrickleReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let end = (self.position + self.step).min(self.data.len()).min(self.position + buf.len());
+            let count = end - self.position;
+            buf[..count].copy_from_slice(&self.data[self.position..end]);
+            self.position = end;
+            Ok(count)
+        }
+    }
+
+    fn sample_pdf_xhtml() -> String {
+        let mut xhtml = String::from("<html><head><title>t</title></head><body>");
+        for page in 1..=3 {
+            xhtml.push_str(&format!(
+                "<div class=\"page\"><p>Page {} ሰላም &amp; more</p><p>Second paragraph</p></div>",
+                page
+            ));
+        }
+        xhtml.push_str("</body></html>");
+        xhtml
+    }
+
+    #[test]
+    fn test_pdf_page_stream_yields_numbered_pages() {
+        let reader = std::io::Cursor::new(sample_pdf_xhtml().into_bytes());
+        let pages: Vec<PageText> = PdfPageStream::new(reader, "test.pdf".to_string())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(pages.len(), 3);
+        for (index, page) in pages.iter().enumerate() {
+            assert_eq!(page.page_number, index as u32 + 1);
+            assert!(page.text.contains(&format!("Page {} ሰላም & more", index + 1)));
+            assert!(page.text.contains("Second paragraph"));
+            assert!(!page.text.contains('<'));
+        }
+    }
+
+    #[test]
+    fn test_pdf_page_stream_handles_split_reads() {
+        // Reads of 7 bytes split both the page markers and the multi-byte Ethiopic characters
+        let reader = TrickleReader { data: sample_pdf_xhtml().into_bytes(), position: 0, step: 7 };
+        let pages: Vec<PageText> = PdfPageStream::new(reader, "test.pdf".to_string())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(pages.len(), 3);
+        assert!(pages[2].text.contains("Page 3 ሰላም & more"));
+        assert!(!pages.iter().any(|page| page.text.contains(char::REPLACEMENT_CHARACTER)));
+    }
+
+    #[tokio::test]
+    async fn test_page_receiver_times_out_on_blocked_read() {
+        /// Reader that serves one page and then blocks until the test releases it
+        struct BlockingReader {
+            data: std::io::Cursor<Vec<u8>>,
+            release: std::sync::mpsc::Receiver<()>,
+        }
+        impl Read for BlockingReader {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let read = self.data.read(buf)?;
+                if read == 0 {
+                    let _ = self.release.recv();
+                }
+                Ok(read)
+            }
+        }
+
+        let (release, blocked) = std::sync::mpsc::channel();
+        let data = "<html><body><div class=\"page\"><p>First</p><div class=\"page\"><p>Second".as_bytes().to_vec();
+        let reader = BlockingReader { data: std::io::Cursor::new(data), release: blocked };
+        let (_, mut pages) = PdfPageReceiver::spawn("slow.pdf".to_string(), Duration::from_millis(100), move || {
+            Ok(PdfPageStream::new(reader, "slow.pdf".to_string()))
+        })
+        .await
+        .unwrap();
+
+        let first = pages.next().await.unwrap().unwrap();
+        assert_eq!(first.page_number, 1);
+        assert!(first.text.contains("First"));
+        assert!(matches!(pages.next().await, Some(Err(ExtractorError::Timeout(_, _)))));
+        assert!(pages.next().await.is_none());
+        // The abandoned worker still holds its extraction slot
+        assert!(*EXTRACTION_WORKERS.live.lock().unwrap() >= 1);
+
+        // Unblock the abandoned worker so it can exit
+        drop(release);
+    }
+
+    #[tokio::test]
+    async fn test_page_receiver_times_out_opening() {
+        let (_release, blocked) = std::sync::mpsc::channel::<()>();
+        let result = PdfPageReceiver::spawn("stuck.pdf".to_string(), Duration::from_millis(50), move || {
+            let _ = blocked.recv();
+            Ok(PdfPageStream::new(std::io::empty(), "stuck.pdf".to_string()))
+        })
+        .await;
+        assert!(matches!(result, Err(ExtractorError::Timeout(_, _))));
+    }
+}
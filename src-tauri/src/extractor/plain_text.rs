@@ -0,0 +1,39 @@
+// src-tauri/src/extractor/plain_text.rs
+
+use std::fs;
+use std::path::Path;
+use log::{error, info};
+
+use super::registry::TextExtractor;
+use super::{ExtractedText, ExtractorError};
+
+/// Reads a file as text; invalid UTF-8 is replaced rather than failing the file
+pub fn read_text_lossy(file_path: &Path) -> Result<String, ExtractorError> {
+    let bytes = fs::read(file_path).map_err(|e| {
+        error!("Failed to read file {}: {}", file_path.display(), e);
+        ExtractorError::IoError(file_path.display().to_string(), e)
+    })?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Plain text, Markdown and delimited text files
+pub struct PlainTextExtractor;
+
+impl TextExtractor for PlainTextExtractor {
+    fn name(&self) -> &'static str {
+        "text"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["txt", "md", "markdown", "rst"]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &["text/plain", "text/markdown", "text/x-rst"]
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting plain text: {}", file_path.display());
+        read_text_lossy(file_path).map(ExtractedText::from)
+    }
+}
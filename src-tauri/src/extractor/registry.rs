@@ -0,0 +1,209 @@
+// src-tauri/src/extractor/registry.rs
+
+//! Registry of text extractors. Every extractor declares the file extensions and
+//! MIME types it handles; the indexer, watcher and `get_content_type` look files
+//! up here instead of in hard-coded extension lists.
+
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use once_cell::sync::Lazy;
+use log::info;
+
+use super::docx::DocxExtractor;
+use super::email::EmailExtractor;
+use super::html::HtmlExtractor;
+use super::office::TikaExtractor;
+use super::pdf::PdfExtractor;
+use super::code::SourceCodeExtractor;
+use super::plain_text::PlainTextExtractor;
+use super::spreadsheet::SpreadsheetExtractor;
+use super::{ContentType, ExtractedText, ExtractorError};
+
+/// A backend that turns one family of document formats into plain text
+pub trait TextExtractor: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Lowercase file extensions handled, without the leading dot
+    fn extensions(&self) -> &[&'static str];
+
+    /// MIME types handled
+    fn mime_types(&self) -> &[&'static str];
+
+    /// Content type reported for files handled by this extractor
+    fn content_type(&self) -> ContentType {
+        ContentType::Text
+    }
+
+    /// Extracts the text content of the file
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError>;
+}
+
+/// Ordered collection of extractors; extractors registered later take precedence
+#[derive(Default, Clone)]
+pub struct ExtractorRegistry {
+    extractors: Vec<Arc<dyn TextExtractor>>,
+}
+
+impl ExtractorRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        ExtractorRegistry::default()
+    }
+
+    /// Creates a registry with all built-in extractors
+    pub fn with_defaults() -> Self {
+        let mut registry = ExtractorRegistry::new();
+        registry.register(Arc::new(PlainTextExtractor));
+        registry.register(Arc::new(SourceCodeExtractor));
+        registry.register(Arc::new(HtmlExtractor));
+        registry.register(Arc::new(TikaExtractor::odt()));
+        registry.register(Arc::new(TikaExtractor::rtf()));
+        registry.register(Arc::new(TikaExtractor::pptx()));
+        registry.register(Arc::new(SpreadsheetExtractor));
+        registry.register(Arc::new(TikaExtractor::epub()));
+        registry.register(Arc::new(EmailExtractor));
+        registry.register(Arc::new(DocxExtractor));
+        registry.register(Arc::new(PdfExtractor));
+        registry
+    }
+
+    /// Adds an extractor, overriding earlier extractors for the same extensions and MIME types
+    pub fn register(&mut self, extractor: Arc<dyn TextExtractor>) {
+        self.extractors.insert(0, extractor);
+    }
+
+    /// Finds the extractor for a file extension (case-insensitive, without the dot)
+    pub fn for_extension(&self, extension: &str) -> Option<Arc<dyn TextExtractor>> {
+        let extension = extension.to_lowercase();
+        self.extractors
+            .iter()
+            .find(|extractor| extractor.extensions().contains(&extension.as_str()))
+            .cloned()
+    }
+
+    /// Finds the extractor for a MIME type; parameters such as `; charset=utf-8` are ignored
+    pub fn for_mime_type(&self, mime_type: &str) -> Option<Arc<dyn TextExtractor>> {
+        let essence = mime_type.split(';').next().unwrap_or("").trim().to_lowercase();
+        self.extractors
+            .iter()
+            .find(|extractor| extractor.mime_types().contains(&essence.as_str()))
+            .cloned()
+    }
+
+    /// Finds the extractor for a file based on its extension
+    pub fn for_path(&self, file_path: &Path) -> Option<Arc<dyn TextExtractor>> {
+        file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| self.for_extension(ext))
+    }
+
+    /// All extensions handled by the registered extractors, sorted
+    pub fn extensions(&self) -> Vec<&'static str> {
+        let mut extensions: Vec<&'static str> = self
+            .extractors
+            .iter()
+            .flat_map(|extractor| extractor.extensions().iter().copied())
+            .collect();
+        extensions.sort_unstable();
+        extensions.dedup();
+        extensions
+    }
+}
+
+// Process-wide registry consulted by the extraction pipeline
+static EXTRACTOR_REGISTRY: Lazy<RwLock<ExtractorRegistry>> =
+    Lazy::new(|| RwLock::new(ExtractorRegistry::with_defaults()));
+
+/// Registers an extractor with the global registry
+pub fn register_extractor(extractor: Arc<dyn TextExtractor>) {
+    info!("Registering text extractor '{}' for {:?}", extractor.name(), extractor.extensions());
+    EXTRACTOR_REGISTRY.write().unwrap().register(extractor);
+}
+
+/// Finds the extractor for a file in the global registry
+pub fn find_extractor(file_path: &Path) -> Option<Arc<dyn TextExtractor>> {
+    EXTRACTOR_REGISTRY.read().unwrap().for_path(file_path)
+}
+
+/// Finds the extractor for a MIME type in the global registry
+pub fn find_extractor_for_mime_type(mime_type: &str) -> Option<Arc<dyn TextExtractor>> {
+    EXTRACTOR_REGISTRY.read().unwrap().for_mime_type(mime_type)
+}
+
+/// All file extensions text can be extracted from
+pub fn supported_text_extensions() -> Vec<&'static str> {
+    EXTRACTOR_REGISTRY.read().unwrap().extensions()
+}
+
+/// Returns true if a registered extractor handles the file
+pub fn is_supported_text_file(file_path: &Path) -> bool {
+    find_extractor(file_path).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UppercaseExtractor;
+
+    impl TextExtractor for UppercaseExtractor {
+        fn name(&self) -> &'static str {
+            "uppercase"
+        }
+
+        fn extensions(&self) -> &[&'static str] {
+            &["txt"]
+        }
+
+        fn mime_types(&self) -> &[&'static str] {
+            &["text/plain"]
+        }
+
+        fn extract(&self, _file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+            Ok("UPPERCASE".to_string().into())
+        }
+    }
+
+    #[test]
+    fn test_default_registry_covers_formats() {
+        let registry = ExtractorRegistry::with_defaults();
+        for extension in ["pdf", "docx", "odt", "rtf", "pptx", "xlsx", "csv", "epub", "html", "htm", "txt", "md", "rs", "py", "eml", "mbox"] {
+            assert!(registry.for_extension(extension).is_some(), "No extractor for {}", extension);
+        }
+        assert!(registry.for_extension("zip").is_none());
+        assert_eq!(registry.for_extension("PDF").map(|e| e.name()), Some("pdf"));
+        assert_eq!(registry.for_path(Path::new("/docs/Report.HTML")).map(|e| e.name()), Some("html"));
+    }
+
+    #[test]
+    fn test_lookup_by_mime_type() {
+        let registry = ExtractorRegistry::with_defaults();
+        assert_eq!(registry.for_mime_type("application/pdf").map(|e| e.name()), Some("pdf"));
+        assert_eq!(registry.for_mime_type("text/html; charset=utf-8").map(|e| e.name()), Some("html"));
+        assert_eq!(
+            registry.for_mime_type("application/vnd.oasis.opendocument.text").map(|e| e.name()),
+            Some("odt")
+        );
+        assert!(registry.for_mime_type("application/zip").is_none());
+    }
+
+    #[test]
+    fn test_later_registration_takes_precedence() {
+        let mut registry = ExtractorRegistry::with_defaults();
+        registry.register(Arc::new(UppercaseExtractor));
+        assert_eq!(registry.for_extension("txt").map(|e| e.name()), Some("uppercase"));
+        assert_eq!(registry.for_extension("md").map(|e| e.name()), Some("text"));
+    }
+
+    #[test]
+    fn test_extensions_are_sorted_and_unique() {
+        let extensions = ExtractorRegistry::with_defaults().extensions();
+        let mut expected = extensions.clone();
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(extensions, expected);
+        assert!(extensions.contains(&"epub"));
+    }
+}
@@ -0,0 +1,348 @@
+// src-tauri/src/extractor/spreadsheet.rs
+
+//! Spreadsheet and delimited-text extraction. Each sheet is turned into groups of
+//! rows written as "Column: value" pairs, and every group records its sheet name
+//! and row range so search results can point at the matching rows.
+
+use std::path::Path;
+use extractous::Extractor;
+use log::{error, info};
+
+use crate::chunker::{get_chunking_config, ChunkingConfig, TextChunk};
+use super::markup::decode_entities;
+use super::office::TIKA_MAX_STRING_LENGTH;
+use super::plain_text::read_text_lossy;
+use super::registry::TextExtractor;
+use super::{catch_backend_panic, DocumentMetadata, ExtractedText, ExtractorError};
+
+/// A worksheet as a grid of cell texts; the first non-empty row is the header
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Sheet {
+    name: String,
+    rows: Vec<Vec<String>>,
+    numbering: RowNumbering,
+}
+
+/// How the rows of a sheet are numbered in `row_start`/`row_end`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+enum RowNumbering {
+    /// Position in the file, header and blank rows included, as a spreadsheet application
+    /// shows it. Delimited text keeps every row, so the position is known.
+    #[default]
+    File,
+    /// Data rows: the first non-blank row below the header is row 1. Tika leaves empty rows
+    /// out of workbooks, so their sheet row numbers cannot be recovered.
+    Data,
+}
+
+/// Extracts XLSX/XLS/ODS workbooks (through Tika) and CSV/TSV files as row groups
+pub struct SpreadsheetExtractor;
+
+impl TextExtractor for SpreadsheetExtractor {
+    fn name(&self) -> &'static str {
+        "spreadsheet"
+    }
+
+    fn extensions(&self) -> &[&'static str] {
+        &["xlsx", "xls", "ods", "csv", "tsv"]
+    }
+
+    fn mime_types(&self) -> &[&'static str] {
+        &[
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "application/vnd.ms-excel",
+            "application/vnd.oasis.opendocument.spreadsheet",
+            "text/csv",
+            "text/tab-separated-values",
+        ]
+    }
+
+    fn extract(&self, file_path: &Path) -> Result<ExtractedText, ExtractorError> {
+        info!("Extracting spreadsheet: {}", file_path.display());
+        let extension = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+
+        let mut metadata = DocumentMetadata::default();
+        let sheets = match extension.as_str() {
+            "csv" | "tsv" => {
+                let delimiter = if extension == "tsv" { '\t' } else { ',' };
+                let name = file_path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or("Sheet1")
+                    .to_string();
+                let rows = parse_delimited(&read_text_lossy(file_path)?, delimiter);
+                vec![Sheet { name, rows, numbering: RowNumbering::File }]
+            }
+            _ => {
+                let (xhtml, workbook_metadata) = extract_workbook_xhtml(file_path)?;
+                metadata = workbook_metadata;
+                parse_workbook_xhtml(&xhtml)
+            }
+        };
+
+        let config = get_chunking_config();
+        let sections = sheets
+            .iter()
+            .flat_map(|sheet| {
+                // Token row groups are sized for the model that embeds the language of the sheet
+                let text = sheet.rows.iter().flatten().map(String::as_str).collect::<Vec<_>>().join(" ");
+                row_groups(sheet, &config.for_text(&text))
+            })
+            .collect();
+        Ok(ExtractedText::from_sections(sections).with_metadata(metadata))
+    }
+}
+
+/// Runs Tika over a workbook and returns its XHTML rendering (one table per sheet)
+/// together with the workbook properties
+fn extract_workbook_xhtml(file_path: &Path) -> Result<(String, DocumentMetadata), ExtractorError> {
+    let path_str = file_path
+        .to_str()
+        .ok_or_else(|| ExtractorError::InvalidPath(file_path.display().to_string()))?;
+    let extractor = Extractor::new()
+        .set_xml_output(true)
+        .set_extract_string_max_length(TIKA_MAX_STRING_LENGTH);
+    let (xhtml, metadata) = catch_backend_panic(file_path, || extractor.extract_file_to_string(path_str))?
+        .map_err(|e| {
+            error!("Failed to extract workbook {}: {}", file_path.display(), e);
+            ExtractorError::ExtractionFailed(file_path.display().to_string(), e.to_string())
+        })?;
+    Ok((xhtml, DocumentMetadata::from_tika(&metadata)))
+}
+
+/// Parses Tika's workbook XHTML: every sheet is an `<h1>` name followed by a `<table>`
+fn parse_workbook_xhtml(xhtml: &str) -> Vec<Sheet> {
+    let mut sheets: Vec<Sheet> = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut row: Option<Vec<String>> = None;
+    let mut cell: Option<String> = None;
+    let mut rest = xhtml;
+
+    while let Some(tag_start) = rest.find('<') {
+        let text = &rest[..tag_start];
+        if let Some(cell) = cell.as_mut() {
+            cell.push_str(&decode_entities(text));
+        } else if let Some(heading) = heading.as_mut() {
+            heading.push_str(&decode_entities(text));
+        }
+
+        let Some(tag_len) = rest[tag_start..].find('>') else { break };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let is_empty_element = tag.ends_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h1", false) => heading = Some(String::new()),
+            ("h1", true) => {
+                let name = heading.take().unwrap_or_default().trim().to_string();
+                sheets.push(Sheet { name, rows: Vec::new(), numbering: RowNumbering::Data });
+            }
+            ("tr", false) => row = Some(Vec::new()),
+            ("tr", true) => {
+                if let Some(row) = row.take() {
+                    if sheets.is_empty() {
+                        sheets.push(Sheet { name: "Sheet1".to_string(), rows: Vec::new(), numbering: RowNumbering::Data });
+                    }
+                    if let Some(sheet) = sheets.last_mut() {
+                        sheet.rows.push(row);
+                    }
+                }
+            }
+            ("td" | "th", false) if is_empty_element => {
+                if let Some(row) = row.as_mut() {
+                    row.push(String::new());
+                }
+            }
+            ("td" | "th", false) => cell = Some(String::new()),
+            ("td" | "th", true) => {
+                if let (Some(row), Some(cell)) = (row.as_mut(), cell.take()) {
+                    row.push(cell.trim().to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    sheets
+}
+
+/// Parses delimited text (RFC 4180 quoting) into rows of cells
+fn parse_delimited(text: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if cell.is_empty() => in_quotes = true,
+            c if c == delimiter && !in_quotes => row.push(std::mem::take(&mut cell).trim().to_string()),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut cell).trim().to_string());
+                rows.push(std::mem::take(&mut row));
+            }
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell.trim().to_string());
+        rows.push(row);
+    }
+    rows
+}
+
+/// Groups the data rows of a sheet into sections of at most `config.max_size`,
+/// measured with the configured sizer (characters or model tokens).
+///
+/// Rows are rendered as `Header: value; Header: value` using the first non-empty row as
+/// the header. Groups record their first and last row numbered as `sheet.numbering` says.
+fn row_groups(sheet: &Sheet, config: &ChunkingConfig) -> Vec<TextChunk> {
+    let is_blank = |row: &Vec<String>| row.iter().all(|cell| cell.is_empty());
+    let Some(header_index) = sheet.rows.iter().position(|row| !is_blank(row)) else {
+        return Vec::new();
+    };
+    let header: Vec<String> = sheet.rows[header_index]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| if name.is_empty() { format!("Column {}", i + 1) } else { name.clone() })
+        .collect();
+
+    let sheet_name = if sheet.name.is_empty() { None } else { Some(sheet.name.clone()) };
+    let new_group = |row_number: u32| TextChunk {
+        sheet_name: sheet_name.clone(),
+        row_start: Some(row_number),
+        row_end: Some(row_number),
+        ..TextChunk::default()
+    };
+
+    let mut groups = Vec::new();
+    let mut current: Option<TextChunk> = None;
+    let mut current_size = 0;
+    let mut data_rows = 0;
+    for (index, row) in sheet.rows.iter().enumerate().skip(header_index + 1) {
+        if is_blank(row) {
+            continue;
+        }
+        data_rows += 1;
+        let row_number = match sheet.numbering {
+            RowNumbering::File => index as u32 + 1,
+            RowNumbering::Data => data_rows,
+        };
+        let line = row
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(i, value)| match header.get(i) {
+                Some(column) => format!("{}: {}", column, value),
+                None => format!("Column {}: {}", i + 1, value),
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let line_size = config.measure(&line);
+        let group = current.get_or_insert_with(|| new_group(row_number));
+        if !group.text.is_empty() && current_size + line_size + 1 > config.max_size {
+            groups.extend(current.replace(new_group(row_number)));
+            current_size = 0;
+        }
+        let group = current.get_or_insert_with(|| new_group(row_number));
+        if !group.text.is_empty() {
+            group.text.push('\n');
+            current_size += 1;
+        }
+        group.text.push_str(&line);
+        group.row_end = Some(row_number);
+        current_size += line_size;
+    }
+    groups.extend(current);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_delimited_handles_quotes() {
+        let rows = parse_delimited("Team,Note\r\nMarketing,\"Q3, \"\"final\"\"\nline two\"\n", ',');
+        assert_eq!(rows, vec![
+            vec!["Team".to_string(), "Note".to_string()],
+            vec!["Marketing".to_string(), "Q3, \"final\"\nline two".to_string()],
+        ]);
+    }
+
+    #[test]
+    fn test_parse_workbook_xhtml() {
+        let xhtml = "<html><body><div class=\"page\"><h1>Budget</h1><table><tbody>\
+                     <tr><td>Team</td><td>Q3</td></tr><tr><td>R&amp;D</td><td/></tr>\
+                     </tbody></table></div><div class=\"page\"><h1>Notes</h1><table><tbody>\
+                     <tr><td>Text</td></tr></tbody></table></div></body></html>";
+        let sheets = parse_workbook_xhtml(xhtml);
+        assert_eq!(sheets.len(), 2);
+        assert_eq!(sheets[0].name, "Budget");
+        assert_eq!(sheets[0].rows[1], vec!["R&D".to_string(), String::new()]);
+        assert_eq!(sheets[1].name, "Notes");
+    }
+
+    #[test]
+    fn test_row_groups_are_header_aware() {
+        let sheet = Sheet {
+            name: "Budget".to_string(),
+            rows: vec![
+                vec!["Team".to_string(), "Quarter".to_string(), "Amount".to_string()],
+                vec!["Marketing".to_string(), "Q3".to_string(), "12000".to_string()],
+                vec![String::new(), String::new(), String::new()],
+                vec!["Sales".to_string(), "Q3".to_string(), String::new()],
+            ],
+            numbering: RowNumbering::Data,
+        };
+        let groups = row_groups(&sheet, &ChunkingConfig { max_size: 1000, ..ChunkingConfig::default() });
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].text, "Team: Marketing; Quarter: Q3; Amount: 12000\nTeam: Sales; Quarter: Q3");
+        assert_eq!(groups[0].sheet_name.as_deref(), Some("Budget"));
+        assert_eq!((groups[0].row_start, groups[0].row_end), (Some(1), Some(2)));
+
+        let small = row_groups(&sheet, &ChunkingConfig { max_size: 50, ..ChunkingConfig::default() });
+        assert_eq!(small.len(), 2);
+        assert_eq!((small[1].row_start, small[1].row_end), (Some(2), Some(2)));
+
+        // Rows of delimited text keep their position in the file, header and blank rows included
+        let file = Sheet { numbering: RowNumbering::File, ..sheet };
+        let small = row_groups(&file, &ChunkingConfig { max_size: 50, ..ChunkingConfig::default() });
+        assert_eq!((small[0].row_start, small[0].row_end), (Some(2), Some(2)));
+        assert_eq!((small[1].row_start, small[1].row_end), (Some(4), Some(4)));
+    }
+
+    #[test]
+    fn test_extract_csv() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("budget.csv");
+        std::fs::write(&file_path, "Team,Quarter,Amount\n\nMarketing,Q3,12000\n").unwrap();
+
+        let extracted = SpreadsheetExtractor.extract(&file_path).unwrap();
+        assert_eq!(extracted.sections.len(), 1);
+        assert_eq!(extracted.sections[0].sheet_name.as_deref(), Some("budget"));
+        assert_eq!((extracted.sections[0].row_start, extracted.sections[0].row_end), (Some(3), Some(3)));
+        assert_eq!(extracted.text, "Team: Marketing; Quarter: Q3; Amount: 12000");
+    }
+}
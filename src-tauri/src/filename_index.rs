@@ -0,0 +1,675 @@
+// src-tauri/src/filename_index.rs
+
+//! Persistent index of file names for instant filename search.
+//!
+//! Entries live in memory and are saved as JSON in the app data directory, so a
+//! search never touches the file system. Names are matched fuzzily (see `fuzzy`);
+//! a bit set of the characters of every name rules out most entries before the
+//! matcher runs. Results are ranked by match quality, then boosted for recently
+//! modified and frequently opened files. The index is filled by scanning
+//! directories and kept current by the file watcher.
+
+use crate::core::indexer::is_walked_entry;
+use crate::db::APP_DATA_DIR_NAME;
+use crate::fuzzy::{char_mask, fold_name, FuzzyQuery};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use walkdir::WalkDir;
+
+/// Name of the index file in the app data directory
+const INDEX_FILE_NAME: &str = "filename_index.json";
+
+/// Version of the saved format; a file with another version is ignored and rebuilt by scanning
+const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// How long changes are collected before the index is written back to disk
+const SAVE_DELAY: Duration = Duration::from_secs(5);
+
+/// Weights of match quality, recency and open frequency in the score of a result
+const QUALITY_WEIGHT: f32 = 0.8;
+const RECENCY_WEIGHT: f32 = 0.1;
+const FREQUENCY_WEIGHT: f32 = 0.1;
+
+/// Age in days at which the recency boost of a file has halved
+const RECENCY_HALF_LIFE_DAYS: f32 = 30.0;
+
+/// Number of opens at which the frequency boost of a file is at its maximum
+const FREQUENT_OPENS: u32 = 20;
+
+/// The process-wide filename index, loaded from disk on first use
+pub static FILENAME_INDEX: Lazy<RwLock<FilenameIndex>> = Lazy::new(|| RwLock::new(load_default_index()));
+
+/// Set while a save is scheduled, so bursts of changes are written once
+static SAVE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Error, Debug)]
+pub enum FilenameIndexError {
+    #[error("I/O error for {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("Failed to read or write the filename index: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Unsupported filename index version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Failed to get application data directory: {0}")]
+    AppDataDir(String),
+}
+
+/// One indexed file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FilenameEntry {
+    pub path: String,
+    /// Modification time in seconds since the Unix epoch
+    pub last_modified: u64,
+    pub size: u64,
+    /// How often the file was opened from the app
+    #[serde(default)]
+    pub open_count: u32,
+}
+
+impl FilenameEntry {
+    /// File name of the entry, as shown in search results
+    pub fn name(&self) -> &str {
+        Path::new(&self.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.path)
+    }
+}
+
+/// A search hit with its relevance in (0, 1]
+#[derive(Debug, Clone)]
+pub struct FilenameMatch<'a> {
+    pub entry: &'a FilenameEntry,
+    pub score: f32,
+    /// Matched characters of the file name as half-open `[start, end)` character ranges
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Counts of a directory scan applied to the index
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScanSummary {
+    pub files_added: usize,
+    pub files_updated: usize,
+    pub files_removed: usize,
+}
+
+/// Files found by walking a directory, and the entries that could not be read
+#[derive(Debug, Default)]
+pub struct ScannedFiles {
+    pub entries: Vec<FilenameEntry>,
+    pub errors: Vec<String>,
+    /// Paths the errors were about: what is indexed under them is kept, since the scan
+    /// could not tell whether it still exists
+    pub unreadable: Vec<PathBuf>,
+}
+
+/// Layout of the index file
+#[derive(Serialize, Deserialize)]
+struct SavedIndex {
+    version: u32,
+    entries: Vec<FilenameEntry>,
+}
+
+/// An entry with where its file name starts in the path, and the name prepared for matching
+#[derive(Debug)]
+struct Slot {
+    entry: FilenameEntry,
+    name_start: usize,
+    folded_name: Box<str>,
+    name_mask: u64,
+}
+
+impl Slot {
+    fn new(entry: FilenameEntry) -> Self {
+        let name = entry.name();
+        let name_start = entry.path.len() - name.len();
+        let name_start = if entry.path[name_start..] == *name { name_start } else { 0 };
+        let folded_name = fold_name(name).into_boxed_str();
+        let name_mask = char_mask(folded_name.chars());
+        Self { entry, name_start, folded_name, name_mask }
+    }
+
+    fn name(&self) -> &str {
+        &self.entry.path[self.name_start..]
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FilenameIndex {
+    /// Entries by id; removed entries leave a hole that the next insert reuses
+    slots: Vec<Option<Slot>>,
+    free: Vec<u32>,
+    by_path: HashMap<String, u32>,
+}
+
+impl FilenameIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_path.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_path.is_empty()
+    }
+
+    pub fn get(&self, path: &str) -> Option<&FilenameEntry> {
+        self.by_path.get(path).and_then(|&id| self.slot(id)).map(|slot| &slot.entry)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &FilenameEntry> {
+        self.slots.iter().flatten().map(|slot| &slot.entry)
+    }
+
+    /// Adds a file, or updates its metadata if it is already indexed, keeping its
+    /// open count. Returns true if the file was not indexed before.
+    pub fn insert(&mut self, entry: FilenameEntry) -> bool {
+        if let Some(&id) = self.by_path.get(&entry.path) {
+            if let Some(slot) = self.slots[id as usize].as_mut() {
+                let open_count = slot.entry.open_count.max(entry.open_count);
+                slot.entry = FilenameEntry { open_count, ..entry };
+            }
+            return false;
+        }
+
+        let id = match self.free.pop() {
+            Some(id) => id,
+            None => {
+                self.slots.push(None);
+                (self.slots.len() - 1) as u32
+            }
+        };
+        self.by_path.insert(entry.path.clone(), id);
+        self.slots[id as usize] = Some(Slot::new(entry));
+        true
+    }
+
+    /// Counts an open of an indexed file. Returns false if the file is not indexed.
+    pub fn record_open(&mut self, path: &str) -> bool {
+        let Some(&id) = self.by_path.get(path) else {
+            return false;
+        };
+        match self.slots[id as usize].as_mut() {
+            Some(slot) => {
+                slot.entry.open_count = slot.entry.open_count.saturating_add(1);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes a file, or every file under a folder. Returns how many entries were removed.
+    pub fn remove(&mut self, path: &str) -> usize {
+        let mut removed = usize::from(self.remove_entry(path));
+        let folder = Path::new(path);
+        let under_folder: Vec<String> = self
+            .by_path
+            .keys()
+            .filter(|indexed| indexed.len() > path.len() && Path::new(indexed).starts_with(folder))
+            .cloned()
+            .collect();
+        for indexed in under_folder {
+            removed += usize::from(self.remove_entry(&indexed));
+        }
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Makes the index agree with a fresh scan of `dir`: scanned files are added or
+    /// updated, and indexed files under `dir` that the scan did not find are removed,
+    /// unless they are under a path the scan could not read
+    pub fn apply_scan(&mut self, dir: &Path, entries: Vec<FilenameEntry>, unreadable: &[PathBuf]) -> ScanSummary {
+        let mut summary = ScanSummary::default();
+        let found: HashSet<String> = entries.iter().map(|entry| entry.path.clone()).collect();
+        for entry in entries {
+            if self.insert(entry) {
+                summary.files_added += 1;
+            } else {
+                summary.files_updated += 1;
+            }
+        }
+
+        let vanished: Vec<String> = self
+            .by_path
+            .keys()
+            .filter(|path| {
+                let path = Path::new(path);
+                path.starts_with(dir) && !unreadable.iter().any(|skipped| path.starts_with(skipped))
+            })
+            .filter(|path| !found.contains(*path))
+            .cloned()
+            .collect();
+        for path in vanished {
+            summary.files_removed += usize::from(self.remove_entry(&path));
+        }
+        summary
+    }
+
+    /// Finds files whose name matches `query` fuzzily, best first. A query with a
+    /// path separator also requires the folder of the file to contain what comes
+    /// before the last separator, in order (`docs/rep` finds `~/Documents/report.pdf`).
+    pub fn search<F>(&self, query: &str, limit: usize, filter: F) -> Vec<FilenameMatch<'_>>
+    where
+        F: Fn(&FilenameEntry) -> bool,
+    {
+        let query = query.trim();
+        let (folder_query, name_query) = match query.rfind(['/', std::path::MAIN_SEPARATOR]) {
+            Some(separator) => (Some(query[..separator].to_lowercase()), &query[separator + 1..]),
+            None => (None, query),
+        };
+        let name_query = FuzzyQuery::new(name_query);
+        if name_query.is_empty() || limit == 0 {
+            return Vec::new();
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let mut matches: Vec<FilenameMatch> = self
+            .slots
+            .iter()
+            .flatten()
+            .filter(|slot| name_query.may_match(slot.name_mask))
+            .filter(|slot| {
+                folder_query
+                    .as_deref()
+                    .is_none_or(|folder| is_subsequence(folder, &slot.entry.path[..slot.name_start]))
+            })
+            .filter_map(|slot| {
+                let found = name_query.match_folded(slot.name(), &slot.folded_name)?;
+                filter(&slot.entry).then(|| FilenameMatch {
+                    entry: &slot.entry,
+                    score: rank(found.quality, &slot.entry, now),
+                    ranges: found.ranges,
+                })
+            })
+            .collect();
+
+        let best_first = |a: &FilenameMatch, b: &FilenameMatch| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| b.entry.last_modified.cmp(&a.entry.last_modified))
+                .then_with(|| a.entry.path.cmp(&b.entry.path))
+        };
+        if matches.len() > limit {
+            matches.select_nth_unstable_by(limit - 1, best_first);
+            matches.truncate(limit);
+        }
+        matches.sort_by(best_first);
+        matches
+    }
+
+    /// Loads a saved index. A missing file gives an empty index.
+    pub fn load(path: &Path) -> Result<Self, FilenameIndexError> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+        let file = File::open(path).map_err(|e| FilenameIndexError::Io(path.display().to_string(), e))?;
+        let saved: SavedIndex = serde_json::from_reader(BufReader::new(file))?;
+        if saved.version != INDEX_FORMAT_VERSION {
+            return Err(FilenameIndexError::UnsupportedVersion(saved.version));
+        }
+        let mut index = Self::new();
+        for entry in saved.entries {
+            index.insert(entry);
+        }
+        Ok(index)
+    }
+
+    /// Saves the index, replacing the file only once it is completely written
+    pub fn save(&self, path: &Path) -> Result<(), FilenameIndexError> {
+        let io_error = |e| FilenameIndexError::Io(path.display().to_string(), e);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let saved = SavedIndex {
+            version: INDEX_FORMAT_VERSION,
+            entries: self.entries().cloned().collect(),
+        };
+        let temp_path = path.with_extension("json.tmp");
+        let file = File::create(&temp_path).map_err(io_error)?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer(&mut writer, &saved)?;
+        writer.flush().map_err(io_error)?;
+        fs::rename(&temp_path, path).map_err(io_error)
+    }
+
+    fn slot(&self, id: u32) -> Option<&Slot> {
+        self.slots.get(id as usize).and_then(Option::as_ref)
+    }
+
+    fn remove_entry(&mut self, path: &str) -> bool {
+        match self.by_path.remove(path) {
+            Some(id) => {
+                self.slots[id as usize] = None;
+                self.free.push(id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Score of a match: its quality, boosted for files modified recently and files opened often
+fn rank(quality: f32, entry: &FilenameEntry, now: u64) -> f32 {
+    let age_days = now.saturating_sub(entry.last_modified) as f32 / 86_400.0;
+    let recency = 0.5f32.powf(age_days / RECENCY_HALF_LIFE_DAYS);
+    let opens = entry.open_count.min(FREQUENT_OPENS) as f32;
+    let frequency = opens.ln_1p() / (FREQUENT_OPENS as f32).ln_1p();
+    QUALITY_WEIGHT * quality + RECENCY_WEIGHT * recency + FREQUENCY_WEIGHT * frequency
+}
+
+/// Whether the characters of a lowercased `query` appear in `text` in order, ignoring case
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    query.chars().all(|c| text.any(|t| t == c))
+}
+
+/// Reads the index entry of a file from its metadata
+pub fn entry_for_path(path: &Path) -> std::io::Result<FilenameEntry> {
+    let meta = fs::metadata(path)?;
+    let last_modified = meta
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs());
+    Ok(FilenameEntry {
+        path: path.to_string_lossy().to_string(),
+        last_modified,
+        size: meta.len(),
+        open_count: 0,
+    })
+}
+
+/// Walks `dir` and collects an entry for every file in it. This touches the file
+/// system for every file, so async callers run it on a blocking thread.
+pub fn scan_directory(dir: &Path) -> ScannedFiles {
+    let mut scanned = ScannedFiles::default();
+    for entry in WalkDir::new(dir).into_iter().filter_entry(is_walked_entry) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                // An error without a path leaves nothing under `dir` safe to remove
+                scanned.unreadable.push(e.path().unwrap_or(dir).to_path_buf());
+                scanned.errors.push(e.to_string());
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match entry_for_path(entry.path()) {
+            Ok(indexed) => scanned.entries.push(indexed),
+            Err(e) => {
+                scanned.unreadable.push(entry.path().to_path_buf());
+                scanned.errors.push(format!("{}: {}", entry.path().display(), e));
+            }
+        }
+    }
+    scanned
+}
+
+/// Location of the index file in the app data directory
+pub fn get_index_path() -> Result<PathBuf, FilenameIndexError> {
+    let app_data_dir = dirs::config_dir()
+        .or_else(dirs::data_local_dir)
+        .ok_or_else(|| FilenameIndexError::AppDataDir("Failed to locate application data directory".to_string()))?;
+    Ok(app_data_dir.join(APP_DATA_DIR_NAME).join(INDEX_FILE_NAME))
+}
+
+/// Loads the saved index, starting empty if there is none or it cannot be read
+fn load_default_index() -> FilenameIndex {
+    let path = match get_index_path() {
+        Ok(path) => path,
+        Err(e) => {
+            error!("Filename index will not be persisted: {}", e);
+            return FilenameIndex::new();
+        }
+    };
+    match FilenameIndex::load(&path) {
+        Ok(index) => {
+            info!("Loaded filename index with {} files from {}", index.len(), path.display());
+            index
+        }
+        Err(e) => {
+            warn!("Starting with an empty filename index, failed to load {}: {}", path.display(), e);
+            FilenameIndex::new()
+        }
+    }
+}
+
+/// Writes the filename index to the app data directory now
+pub fn save_index() -> Result<(), FilenameIndexError> {
+    let path = get_index_path()?;
+    let index = FILENAME_INDEX.read().unwrap();
+    index.save(&path)?;
+    info!("Saved filename index with {} files to {}", index.len(), path.display());
+    Ok(())
+}
+
+/// Counts an open of a file in the filename index, for ranking. Must be called from
+/// within a Tokio runtime.
+pub fn record_open(path: &str) {
+    let recorded = match FILENAME_INDEX.write() {
+        Ok(mut index) => index.record_open(path),
+        Err(e) => {
+            error!("Filename index is unavailable: {}", e);
+            false
+        }
+    };
+    if recorded {
+        schedule_save();
+    }
+}
+
+/// Saves the filename index after a short delay, so that a burst of watcher
+/// updates is written once. Must be called from within a Tokio runtime.
+pub fn schedule_save() {
+    if SAVE_SCHEDULED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tokio::spawn(async {
+        tokio::time::sleep(SAVE_DELAY).await;
+        SAVE_SCHEDULED.store(false, Ordering::SeqCst);
+        match tokio::task::spawn_blocking(save_index).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => error!("Failed to save filename index: {}", e),
+            Err(e) => error!("Filename index save task failed: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(path: &str, last_modified: u64) -> FilenameEntry {
+        FilenameEntry { path: path.to_string(), last_modified, size: 10, open_count: 0 }
+    }
+
+    fn search_paths(index: &FilenameIndex, query: &str) -> Vec<String> {
+        index
+            .search(query, 10, |_| true)
+            .into_iter()
+            .map(|m| m.entry.path.clone())
+            .collect()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn test_search_ranks_fuzzy_matches() {
+        let mut index = FilenameIndex::new();
+        index.insert(entry("/docs/old-report.pdf", 100));
+        index.insert(entry("/docs/Report.docx", 50));
+        index.insert(entry("/docs/report", 10));
+        index.insert(entry("/docs/annual_report_2024.xlsx", 300));
+        index.insert(entry("/docs/misreporting.txt", 400));
+        index.insert(entry("/docs/notes.txt", 500));
+        // The folder name is not part of the match
+        index.insert(entry("/report/summary.md", 600));
+
+        assert_eq!(
+            search_paths(&index, "REPORT"),
+            vec![
+                "/docs/report",
+                "/docs/Report.docx",
+                "/docs/old-report.pdf",
+                "/docs/annual_report_2024.xlsx",
+                "/docs/misreporting.txt",
+            ]
+        );
+        assert_eq!(search_paths(&index, "no"), vec!["/docs/notes.txt", "/docs/annual_report_2024.xlsx"]);
+        assert_eq!(search_paths(&index, "anrep"), vec!["/docs/annual_report_2024.xlsx"]);
+        // Equally good matches with a typo go to the most recently modified file first
+        assert_eq!(search_paths(&index, "raport")[..2], ["/docs/Report.docx", "/docs/report"]);
+        assert!(search_paths(&index, "missing").is_empty());
+
+        let found = index.search("anrep", 1, |_| true);
+        assert_eq!(found[0].ranges, vec![(0, 2), (7, 10)]);
+        assert!(found[0].score > 0.0 && found[0].score <= 1.0);
+
+        let only_pdf = index.search("report", 10, |e| e.path.ends_with(".pdf"));
+        assert_eq!(only_pdf.len(), 1);
+        assert_eq!(
+            index.search("report", 2, |_| true).iter().map(|m| m.entry.path.as_str()).collect::<Vec<_>>(),
+            vec!["/docs/report", "/docs/Report.docx"]
+        );
+    }
+
+    #[test]
+    fn test_search_by_folder_and_name() {
+        let mut index = FilenameIndex::new();
+        index.insert(entry("/home/me/Documents/report.pdf", 1));
+        index.insert(entry("/home/me/Downloads/report.pdf", 1));
+        index.insert(entry("/home/me/Documents/taxes/receipt.pdf", 1));
+
+        assert_eq!(search_paths(&index, "docs/report"), vec!["/home/me/Documents/report.pdf"]);
+        assert_eq!(search_paths(&index, "dl/rep"), vec!["/home/me/Downloads/report.pdf"]);
+        assert_eq!(search_paths(&index, "doc/tax/rec"), vec!["/home/me/Documents/taxes/receipt.pdf"]);
+        assert!(search_paths(&index, "music/rep").is_empty());
+        assert!(search_paths(&index, "docs/").is_empty());
+    }
+
+    #[test]
+    fn test_recent_and_frequently_opened_files_rank_higher() {
+        let now = now();
+        let mut index = FilenameIndex::new();
+        index.insert(entry("/old/budget.xlsx", now - 365 * 86_400));
+        index.insert(entry("/new/budget.xlsx", now - 60 * 86_400));
+        let found = index.search("budget", 10, |_| true);
+        assert_eq!(found[0].entry.path, "/new/budget.xlsx");
+        assert!(found[0].score > found[1].score);
+
+        for _ in 0..10 {
+            assert!(index.record_open("/old/budget.xlsx"));
+        }
+        assert!(!index.record_open("/missing/budget.xlsx"));
+        assert_eq!(search_paths(&index, "budget"), vec!["/old/budget.xlsx", "/new/budget.xlsx"]);
+
+        // Updating the metadata of a file keeps its open count
+        index.insert(entry("/old/budget.xlsx", now - 365 * 86_400));
+        assert_eq!(index.get("/old/budget.xlsx").unwrap().open_count, 10);
+
+        // A better match still beats a file that is only opened more often
+        index.insert(entry("/new/budget-2023-draft-copy.xlsx", now));
+        assert_eq!(search_paths(&index, "budget")[0], "/old/budget.xlsx");
+        assert_eq!(search_paths(&index, "budget 2023")[0], "/new/budget-2023-draft-copy.xlsx");
+    }
+
+    #[test]
+    fn test_insert_update_and_remove() {
+        let mut index = FilenameIndex::new();
+        assert!(index.insert(entry("/a/budget.xlsx", 1)));
+        assert!(!index.insert(entry("/a/budget.xlsx", 2)));
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get("/a/budget.xlsx").unwrap().last_modified, 2);
+
+        index.insert(entry("/a/sub/budget-draft.xlsx", 1));
+        index.insert(entry("/a/subway.txt", 1));
+        // Removing a folder removes what is under it, not its siblings with the same prefix
+        assert_eq!(index.remove("/a/sub"), 1);
+        assert_eq!(search_paths(&index, "budget"), vec!["/a/budget.xlsx"]);
+        assert_eq!(index.remove("/a/budget.xlsx"), 1);
+        assert_eq!(index.remove("/a/budget.xlsx"), 0);
+
+        // A reused slot only matches its new name
+        index.insert(entry("/a/invoice.pdf", 1));
+        assert!(search_paths(&index, "budget").is_empty());
+        assert_eq!(search_paths(&index, "invoice"), vec!["/a/invoice.pdf"]);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_index_persists() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("filename_index.json");
+        assert!(FilenameIndex::load(&path).unwrap().is_empty());
+
+        let mut index = FilenameIndex::new();
+        index.insert(entry("/a/budget.xlsx", 1));
+        index.insert(entry("/a/notes.txt", 2));
+        index.remove("/a/notes.txt");
+        index.save(&path).unwrap();
+
+        let loaded = FilenameIndex::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get("/a/budget.xlsx"), Some(&entry("/a/budget.xlsx", 1)));
+        assert_eq!(search_paths(&loaded, "budget"), vec!["/a/budget.xlsx"]);
+
+        fs::write(&path, r#"{"version":99,"entries":[]}"#).unwrap();
+        assert!(matches!(FilenameIndex::load(&path), Err(FilenameIndexError::UnsupportedVersion(99))));
+    }
+
+    #[test]
+    fn test_scan_directory_syncs_index() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("projects")).unwrap();
+        fs::create_dir_all(root.join("node_modules")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("notes.txt"), "notes").unwrap();
+        fs::write(root.join("projects").join("plan.md"), "plan").unwrap();
+        fs::write(root.join("node_modules").join("lib.js"), "").unwrap();
+        fs::write(root.join(".git").join("HEAD"), "").unwrap();
+        fs::write(root.join(".hidden"), "").unwrap();
+
+        let scanned = scan_directory(root);
+        assert!(scanned.errors.is_empty());
+        let mut index = FilenameIndex::new();
+        let summary = index.apply_scan(root, scanned.entries, &scanned.unreadable);
+        assert_eq!(summary, ScanSummary { files_added: 2, files_updated: 0, files_removed: 0 });
+        assert_eq!(index.get(&root.join("notes.txt").to_string_lossy()).unwrap().size, 5);
+
+        // A file outside the scanned folder is left alone
+        index.insert(entry("/elsewhere/plan-b.md", 1));
+        fs::remove_file(root.join("notes.txt")).unwrap();
+        let summary = index.apply_scan(root, scan_directory(root).entries, &[]);
+        assert_eq!(summary, ScanSummary { files_added: 0, files_updated: 1, files_removed: 1 });
+        assert_eq!(search_paths(&index, "plan").len(), 2);
+        assert!(search_paths(&index, "notes").is_empty());
+
+        // Files under a folder that could not be read are kept
+        let unreadable = vec![root.join("projects")];
+        let summary = index.apply_scan(root, Vec::new(), &unreadable);
+        assert_eq!(summary, ScanSummary { files_added: 0, files_updated: 0, files_removed: 0 });
+        assert_eq!(search_paths(&index, "plan").len(), 2);
+    }
+}
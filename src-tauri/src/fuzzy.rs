@@ -0,0 +1,538 @@
+// src-tauri/src/fuzzy.rs
+
+//! Fuzzy matching of search queries against file names, in the spirit of fzf.
+//!
+//! A query matches a name when its characters appear in the name in order, not
+//! necessarily next to each other (`qrep` matches `quarterly_report.pdf`). The best
+//! alignment is found with dynamic programming: matches at the start of a word or
+//! a camelCase hump and runs of consecutive characters score higher, skipped
+//! characters cost a little. A query that is not a subsequence of the name can
+//! still match a part of it with a typo or two (`raport` matches `report.pdf`);
+//! short words and numbers must match exactly.
+//!
+//! Words of a query are matched separately, and matching ignores case. Positions
+//! and ranges count characters, not bytes.
+
+/// Score of each matched character
+const SCORE_MATCH: i32 = 16;
+/// Penalty for the first character skipped between two matched characters
+const PENALTY_GAP_START: i32 = 3;
+/// Penalty for every further skipped character
+const PENALTY_GAP_EXTENSION: i32 = 1;
+/// Bonus for a match at the start of the name or after a separator such as `_`, `-`, `.` or a space
+const BONUS_BOUNDARY: i32 = 8;
+/// Bonus for a match at a camelCase hump or the first digit after letters
+const BONUS_CAMEL: i32 = 7;
+/// Bonus for a match right after the previous matched character
+const BONUS_CONSECUTIVE: i32 = 4;
+/// The bonus of the position of the first query character counts this many times
+const FIRST_CHAR_BONUS_MULTIPLIER: i32 = 2;
+
+/// Longest part of a name that is matched; longer names are matched on their first characters
+const MAX_MATCHED_CHARS: usize = 512;
+
+/// Quality of a name that equals the query, and of one whose stem (the name without
+/// its extension) does
+const QUALITY_EXACT: f32 = 1.0;
+const QUALITY_EXACT_STEM: f32 = 0.95;
+/// Highest quality of a subsequence match, reached by a query that is a prefix of the name
+const QUALITY_SUBSEQUENCE: f32 = 0.9;
+/// Highest quality of a match with typos
+const QUALITY_TYPO: f32 = 0.6;
+/// Share of the quality that depends on how much of the name the query covers,
+/// so that of two equally good matches the shorter name ranks first
+const COVERAGE_WEIGHT: f32 = 0.15;
+
+const NONE: i32 = i32::MIN / 2;
+
+/// How well a query matched a name, and which characters of the name it matched
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// Quality of the match in (0, 1]
+    pub quality: f32,
+    /// Matched characters as half-open `[start, end)` character ranges, in order
+    pub ranges: Vec<(usize, usize)>,
+    /// Number of edits needed to match, 0 for a subsequence match
+    pub typos: usize,
+}
+
+/// A query prepared for matching against many names. Words separated by spaces
+/// are matched separately, and a name must match all of them in any order.
+#[derive(Debug, Clone)]
+pub struct FuzzyQuery {
+    chars: Vec<char>,
+    terms: Vec<Term>,
+}
+
+/// One word of a query
+#[derive(Debug, Clone)]
+struct Term {
+    chars: Vec<char>,
+    /// For each distinct character of the word, the bit set of its positions
+    positions: Vec<(char, u64)>,
+    mask: u64,
+    max_typos: usize,
+}
+
+impl FuzzyQuery {
+    pub fn new(query: &str) -> Self {
+        let query = query.trim();
+        Self {
+            chars: query.chars().map(fold_case).collect(),
+            terms: query.split_whitespace().map(Term::new).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Cheap check against the `char_mask` of a name: false means the name cannot
+    /// match, because it lacks more characters of a word than typos are allowed
+    pub fn may_match(&self, name_mask: u64) -> bool {
+        self.terms.iter().all(|term| term.may_match(name_mask))
+    }
+
+    /// Matches the query against a name
+    pub fn match_name(&self, name: &str) -> Option<FuzzyMatch> {
+        self.match_folded(name, &fold_name(name))
+    }
+
+    /// Matches the query against a name whose `fold_name` is already known. Names
+    /// that cannot match are rejected without allocating.
+    pub fn match_folded(&self, name: &str, folded_name: &str) -> Option<FuzzyMatch> {
+        if !self.terms.iter().all(|term| term.could_match(folded_name)) {
+            return None;
+        }
+        let original: Vec<char> = name.chars().take(MAX_MATCHED_CHARS).collect();
+        let folded: Vec<char> = folded_name.chars().take(MAX_MATCHED_CHARS).collect();
+        match self.terms.as_slice() {
+            [] => None,
+            [term] => term.match_chars(&original, &folded),
+            _ if folded == self.chars => {
+                Some(FuzzyMatch { quality: QUALITY_EXACT, ranges: vec![(0, folded.len())], typos: 0 })
+            }
+            terms => {
+                let mut matches = Vec::with_capacity(terms.len());
+                for term in terms {
+                    matches.push(term.match_chars(&original, &folded)?);
+                }
+                let mut positions: Vec<usize> = matches
+                    .iter()
+                    .flat_map(|m| m.ranges.iter().flat_map(|&(start, end)| start..end))
+                    .collect();
+                positions.sort_unstable();
+                positions.dedup();
+                Some(FuzzyMatch {
+                    quality: matches.iter().map(|m| m.quality).sum::<f32>() / matches.len() as f32,
+                    ranges: to_ranges(&positions),
+                    typos: matches.iter().map(|m| m.typos).sum(),
+                })
+            }
+        }
+    }
+}
+
+impl Term {
+    fn new(word: &str) -> Self {
+        let chars: Vec<char> = word.chars().map(fold_case).collect();
+        // A number with a typo is another number, like the year or version of another file
+        let is_number = chars.iter().all(char::is_ascii_digit);
+        let max_typos = match chars.len() {
+            _ if is_number => 0,
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        let mut positions: Vec<(char, u64)> = Vec::new();
+        for (i, &c) in chars.iter().enumerate().take(64) {
+            match positions.iter_mut().find(|(known, _)| *known == c) {
+                Some((_, bits)) => *bits |= 1 << i,
+                None => positions.push((c, 1 << i)),
+            }
+        }
+        Self { mask: char_mask(chars.iter().copied()), chars, positions, max_typos }
+    }
+
+    fn may_match(&self, name_mask: u64) -> bool {
+        (self.mask & !name_mask).count_ones() as usize <= self.max_typos
+    }
+
+    /// Quick check that a folded name contains the word as a subsequence, or a part
+    /// close enough to it to match with typos. A swap of two characters is two edits
+    /// for `levenshtein_within`, so twice the typos are allowed there.
+    fn could_match(&self, folded: &str) -> bool {
+        is_subsequence(&self.chars, folded.chars())
+            || (self.max_typos > 0 && self.levenshtein_within(folded, 2 * self.max_typos))
+    }
+
+    /// Whether some part of the name is within `limit` insertions, deletions or
+    /// substitutions of the word, with Myers' bit-parallel algorithm: one pass over
+    /// the name and a few word operations per character
+    fn levenshtein_within(&self, folded: &str, limit: usize) -> bool {
+        let m = self.chars.len();
+        if m > 64 {
+            return true;
+        }
+        let last = 1u64 << (m - 1);
+        let (mut plus, mut minus) = (u64::MAX >> (64 - m), 0u64);
+        let mut distance = m;
+        for c in folded.chars() {
+            let eq = self.positions.iter().find(|(known, _)| *known == c).map_or(0, |&(_, bits)| bits);
+            let x_vertical = eq | minus;
+            let x_horizontal = (((eq & plus).wrapping_add(plus)) ^ plus) | eq;
+            let mut plus_horizontal = minus | !(x_horizontal | plus);
+            let mut minus_horizontal = plus & x_horizontal;
+            if plus_horizontal & last != 0 {
+                distance += 1;
+            } else if minus_horizontal & last != 0 {
+                distance -= 1;
+            }
+            if distance <= limit {
+                return true;
+            }
+            plus_horizontal <<= 1;
+            minus_horizontal <<= 1;
+            plus = minus_horizontal | !(x_vertical | plus_horizontal);
+            minus = plus_horizontal & x_vertical;
+        }
+        distance <= limit
+    }
+
+    fn match_chars(&self, original: &[char], folded: &[char]) -> Option<FuzzyMatch> {
+        let query = self.chars.as_slice();
+        if folded == query {
+            return Some(FuzzyMatch { quality: QUALITY_EXACT, ranges: vec![(0, folded.len())], typos: 0 });
+        }
+        let stem_len = folded.iter().rposition(|&c| c == '.').filter(|&dot| dot > 0).unwrap_or(folded.len());
+        if &folded[..stem_len] == query {
+            return Some(FuzzyMatch { quality: QUALITY_EXACT_STEM, ranges: vec![(0, stem_len)], typos: 0 });
+        }
+
+        let coverage = query.len() as f32 / stem_len.max(query.len()) as f32;
+        let weigh = |quality: f32| quality * (1.0 - COVERAGE_WEIGHT + COVERAGE_WEIGHT * coverage);
+
+        if is_subsequence(query, folded.iter().copied()) {
+            let (score, positions) = best_subsequence(query, original, folded)?;
+            let normalized = (score as f32 / perfect_score(query.len()) as f32).clamp(0.05, 1.0);
+            return Some(FuzzyMatch {
+                quality: weigh(QUALITY_SUBSEQUENCE * normalized),
+                ranges: to_ranges(&positions),
+                typos: 0,
+            });
+        }
+
+        if self.max_typos == 0 {
+            return None;
+        }
+        let (typos, positions) = best_approximate(query, folded)?;
+        if typos > self.max_typos || positions.is_empty() {
+            return None;
+        }
+        let normalized = 1.0 - typos as f32 / (query.len() + 1) as f32;
+        Some(FuzzyMatch {
+            quality: weigh(QUALITY_TYPO * normalized),
+            ranges: to_ranges(&positions),
+            typos,
+        })
+    }
+}
+
+/// Bit set of the characters in a text, ignoring case, used to rule out names
+/// quickly. Letters and digits have a bit each; other characters share the rest.
+pub fn char_mask(chars: impl IntoIterator<Item = char>) -> u64 {
+    chars.into_iter().fold(0, |mask, c| {
+        let c = fold_case(c);
+        let bit = match c {
+            'a'..='z' => c as u32 - 'a' as u32,
+            '0'..='9' => 26 + c as u32 - '0' as u32,
+            _ => 36 + c as u32 % 28,
+        };
+        mask | 1 << bit
+    })
+}
+
+/// Lowercases a name the way queries are, for `FuzzyQuery::match_folded`
+pub fn fold_name(name: &str) -> String {
+    name.chars().map(fold_case).collect()
+}
+
+/// Lowercases a character, keeping one character per character so positions line up
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Bonus for matching the character at `index`, from the character before it
+fn position_bonus(chars: &[char], index: usize) -> i32 {
+    let current = chars[index];
+    let Some(&previous) = index.checked_sub(1).and_then(|i| chars.get(i)) else {
+        return BONUS_BOUNDARY;
+    };
+    if !previous.is_alphanumeric() && current.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if (previous.is_lowercase() && current.is_uppercase())
+        || (previous.is_alphabetic() && current.is_ascii_digit())
+    {
+        BONUS_CAMEL
+    } else {
+        0
+    }
+}
+
+/// Whether the characters of `query` appear in `folded` in order
+fn is_subsequence(query: &[char], folded: impl Iterator<Item = char>) -> bool {
+    let mut rest = folded;
+    query.iter().all(|&c| rest.any(|f| f == c))
+}
+
+/// Score of a query matched as a prefix of a name
+fn perfect_score(query_len: usize) -> i32 {
+    let query_len = query_len as i32;
+    query_len * SCORE_MATCH + BONUS_BOUNDARY * FIRST_CHAR_BONUS_MULTIPLIER + (query_len - 1) * BONUS_CONSECUTIVE
+}
+
+/// Finds the highest scoring way to match `query` as a subsequence of `folded`, and
+/// returns its score and the matched positions
+fn best_subsequence(query: &[char], original: &[char], folded: &[char]) -> Option<(i32, Vec<usize>)> {
+    let (m, n) = (query.len(), folded.len());
+    if m > n {
+        return None;
+    }
+    let bonus: Vec<i32> = (0..n).map(|j| position_bonus(original, j)).collect();
+
+    // score[i * n + j]: best score with query[..=i] matched and query[i] at position j;
+    // from[i * n + j]: where query[i - 1] was matched in that alignment
+    let mut score = vec![NONE; m * n];
+    let mut from = vec![usize::MAX; m * n];
+    for j in 0..n {
+        if folded[j] == query[0] {
+            score[j] = SCORE_MATCH + bonus[j] * FIRST_CHAR_BONUS_MULTIPLIER;
+        }
+    }
+    for i in 1..m {
+        let (previous, current) = score.split_at_mut(i * n);
+        let previous = &previous[(i - 1) * n..];
+        let current = &mut current[..n];
+        // Best previous match followed by a gap, over the positions before j - 1
+        let (mut gapped, mut gapped_from) = (NONE, usize::MAX);
+        for j in i..n {
+            if j >= 2 && previous[j - 2] > NONE {
+                let extended = gapped - PENALTY_GAP_EXTENSION;
+                let started = previous[j - 2] - PENALTY_GAP_START;
+                if started >= extended {
+                    (gapped, gapped_from) = (started, j - 2);
+                } else {
+                    gapped = extended;
+                }
+            } else if gapped > NONE {
+                gapped -= PENALTY_GAP_EXTENSION;
+            }
+            if folded[j] != query[i] {
+                continue;
+            }
+            let consecutive = if previous[j - 1] > NONE { previous[j - 1] + BONUS_CONSECUTIVE } else { NONE };
+            let (best, best_from) = if consecutive >= gapped { (consecutive, j - 1) } else { (gapped, gapped_from) };
+            if best > NONE {
+                current[j] = best + SCORE_MATCH + bonus[j];
+                from[i * n + j] = best_from;
+            }
+        }
+    }
+
+    let last = &score[(m - 1) * n..];
+    let (end, &best) = last.iter().enumerate().max_by_key(|&(j, &s)| (s, std::cmp::Reverse(j)))?;
+    if best <= NONE {
+        return None;
+    }
+    let mut positions = vec![end; m];
+    for i in (1..m).rev() {
+        positions[i - 1] = from[i * n + positions[i]];
+    }
+    Some((best, positions))
+}
+
+/// Finds the part of `folded` closest to `query` by edit distance, counting a swap
+/// of two neighbouring characters as one edit. Returns the distance and the
+/// positions of the characters that matched exactly.
+fn best_approximate(query: &[char], folded: &[char]) -> Option<(usize, Vec<usize>)> {
+    let (m, n) = (query.len(), folded.len());
+    if n == 0 {
+        return None;
+    }
+    let width = n + 1;
+    // distance[i * width + j]: edits to match query[..i] with a part of the name ending before j
+    let mut distance = vec![0usize; (m + 1) * width];
+    for i in 1..=m {
+        distance[i * width] = i;
+        for j in 1..=n {
+            let substituted = distance[(i - 1) * width + j - 1] + usize::from(query[i - 1] != folded[j - 1]);
+            let mut best = substituted
+                .min(distance[(i - 1) * width + j] + 1)
+                .min(distance[i * width + j - 1] + 1);
+            if i > 1 && j > 1 && query[i - 1] == folded[j - 2] && query[i - 2] == folded[j - 1] {
+                best = best.min(distance[(i - 2) * width + j - 2] + 1);
+            }
+            distance[i * width + j] = best;
+        }
+    }
+
+    let end = (1..=n).min_by_key(|&j| distance[m * width + j])?;
+    let typos = distance[m * width + end];
+
+    // Walk the alignment back and keep the characters that matched as they are
+    let mut positions = Vec::new();
+    let (mut i, mut j) = (m, end);
+    while i > 0 && j > 0 {
+        let here = distance[i * width + j];
+        if query[i - 1] == folded[j - 1] && here == distance[(i - 1) * width + j - 1] {
+            positions.push(j - 1);
+            i -= 1;
+            j -= 1;
+        } else if here == distance[(i - 1) * width + j - 1] + 1 {
+            i -= 1;
+            j -= 1;
+        } else if i > 1
+            && j > 1
+            && query[i - 1] == folded[j - 2]
+            && query[i - 2] == folded[j - 1]
+            && here == distance[(i - 2) * width + j - 2] + 1
+        {
+            positions.extend([j - 1, j - 2]);
+            i -= 2;
+            j -= 2;
+        } else if here == distance[(i - 1) * width + j] + 1 {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    positions.reverse();
+    Some((typos, positions))
+}
+
+/// Merges sorted positions into half-open ranges of consecutive positions
+fn to_ranges(positions: &[usize]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &position in positions {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == position => *end += 1,
+            _ => ranges.push((position, position + 1)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(query: &str, name: &str) -> Option<FuzzyMatch> {
+        FuzzyQuery::new(query).match_name(name)
+    }
+
+    fn quality(query: &str, name: &str) -> f32 {
+        matched(query, name).map_or(0.0, |m| m.quality)
+    }
+
+    #[test]
+    fn test_subsequence_matching_and_ranges() {
+        let found = matched("qrep", "quarterly_report.pdf").unwrap();
+        assert_eq!(found.ranges, vec![(0, 1), (10, 13)]);
+        assert_eq!(found.typos, 0);
+
+        // camelCase humps and word starts are preferred over earlier letters
+        assert_eq!(matched("fb", "fooBar.rs").unwrap().ranges, vec![(0, 1), (3, 4)]);
+        assert_eq!(matched("mr", "my-summer-report").unwrap().ranges, vec![(0, 1), (10, 11)]);
+        assert_eq!(matched("ÉTÉ", "été.txt").unwrap().ranges, vec![(0, 3)]);
+
+        assert_eq!(matched("report", "Report.docx").unwrap().quality, QUALITY_EXACT_STEM);
+        assert_eq!(matched("report.docx", "Report.docx").unwrap().quality, QUALITY_EXACT);
+        assert!(matched("xyz", "report.docx").is_none());
+        assert!(matched("", "report.docx").is_none());
+    }
+
+    #[test]
+    fn test_better_alignments_score_higher() {
+        // A prefix beats a word start, which beats letters inside words
+        assert!(quality("rep", "report.pdf") > quality("rep", "old_report.pdf"));
+        assert!(quality("rep", "old_report.pdf") > quality("rep", "misreporting.pdf"));
+        // Consecutive characters beat scattered ones
+        assert!(quality("note", "notes.txt") > quality("note", "n_o_t_e.txt"));
+        // Word starts and humps beat the same letters elsewhere
+        assert!(quality("qr", "quick_run.sh") > quality("qr", "quickrun.sh"));
+        assert!(quality("gfs", "getFileSize.ts") > quality("gfs", "giftsforsale.ts"));
+        // Of two names with the same match, the shorter ranks first
+        assert!(quality("budget", "budget-2024.xlsx") > quality("budget", "budget-2024-final-v2.xlsx"));
+        // Subsequence matches beat matches with typos
+        assert!(quality("report", "rxexpxoxrxt.pdf") > quality("raport", "report.pdf"));
+    }
+
+    #[test]
+    fn test_typos_are_tolerated() {
+        let found = matched("raport", "report.pdf").unwrap();
+        assert_eq!(found.typos, 1);
+        assert_eq!(found.ranges, vec![(0, 1), (2, 6)]);
+
+        // Swapped letters count as one typo
+        assert_eq!(matched("reprot", "report.pdf").unwrap().typos, 1);
+        assert_eq!(matched("invocie", "2024-invoice.pdf").unwrap().typos, 1);
+        assert_eq!(matched("quartrly_raport", "quarterly_report.pdf").unwrap().typos, 2);
+
+        // Short queries must match exactly, and a typo budget is not exceeded
+        assert!(matched("rpt", "apt.txt").is_none());
+        assert!(matched("raqort", "report.pdf").is_none());
+    }
+
+    #[test]
+    fn test_char_mask_rules_out_names() {
+        let query = FuzzyQuery::new("Budget");
+        assert!(query.may_match(char_mask("my budget.xlsx".chars())));
+        // One missing letter is allowed as a typo, two are not
+        assert!(query.may_match(char_mask("bugdet".chars())));
+        assert!(query.may_match(char_mask("budet".chars())));
+        assert!(!query.may_match(char_mask("bdt".chars())));
+        assert!(!FuzzyQuery::new("abc").may_match(char_mask("ab".chars())));
+        assert!(!FuzzyQuery::new("budget 2023").may_match(char_mask("budget.xlsx".chars())));
+    }
+
+    #[test]
+    fn test_bit_parallel_levenshtein() {
+        // Smallest Levenshtein distance between the word and any part of the text
+        fn naive(word: &[char], text: &[char]) -> usize {
+            let mut previous: Vec<usize> = vec![0; text.len() + 1];
+            for (i, &w) in word.iter().enumerate() {
+                let mut current = vec![i + 1; text.len() + 1];
+                for (j, &t) in text.iter().enumerate() {
+                    current[j + 1] = (previous[j] + usize::from(w != t))
+                        .min(previous[j + 1] + 1)
+                        .min(current[j] + 1);
+                }
+                previous = current;
+            }
+            previous.into_iter().min().unwrap()
+        }
+
+        let words = ["report", "raport", "budget", "zzzz", "quarterly", "abcabc"];
+        let texts = ["report.pdf", "export_budget.xlsx", "2024_quarterly_report", "abc", "", "bugdet", "cabcab"];
+        for word in words {
+            let term = Term::new(word);
+            for text in texts {
+                let expected = naive(&term.chars, &text.chars().collect::<Vec<_>>());
+                for limit in 0..5 {
+                    assert_eq!(term.levenshtein_within(text, limit), expected <= limit, "{word} in {text} within {limit}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_words_match_separately() {
+        let found = matched("report 2024", "2024_quarterly_report.pdf").unwrap();
+        assert_eq!(found.ranges, vec![(0, 4), (15, 21)]);
+        assert!(matched("report 2023", "2024_quarterly_report.pdf").is_none());
+        assert!(matched("  ", "report.pdf").is_none());
+        // Words with typos, and names that contain the spaces of the query
+        assert_eq!(matched("raport 2024", "2024_quarterly_report.pdf").unwrap().typos, 1);
+        assert_eq!(matched("My Notes", "my notes").unwrap().quality, QUALITY_EXACT);
+    }
+}
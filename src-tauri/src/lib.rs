@@ -8,15 +8,22 @@ use commands::fs_commands::{
     list_directory_command, load_custom_locations, open_path_command, save_custom_locations,
 };
 use commands::indexing_commands::{
-    clear_index_command, get_indexing_stats_command, get_vector_db_stats_command,
+    clear_index_command, get_archive_config_command, get_chunking_config_command,
+    get_cross_lingual_config_command, get_indexing_stats_command, get_ocr_config_command,
+    get_vector_db_stats_command,
     index_downloads_command, index_folder_command, run_startup_indexing,
+    set_archive_config_command, set_chunking_config_command, set_cross_lingual_config_command,
+    set_ocr_config_command,
 };
 use commands::search_commands::{
     add_file_to_index, clear_filename_index, filename_search_command, get_filename_index_stats,
     initialize_filename_index, remove_file_from_index, scan_directory_for_filename_index,
 };
 use commands::search_commands::{get_document_count, semantic_search_command};
-use commands::watcher_commands::{get_watcher_status, start_watcher_command, stop_watcher_command};
+use commands::watcher_commands::{
+    add_watched_root_command, get_watched_roots_command, get_watcher_config_command, get_watcher_status,
+    remove_watched_root_command, set_watcher_config_command, start_watcher_command, stop_watcher_command,
+};
 pub mod benchmark;
 pub mod chunker;
 pub mod commands;
@@ -25,9 +32,12 @@ pub mod db;
 pub mod embedder;
 pub mod embedding;
 pub mod extractor;
+pub mod filename_index;
+pub mod fuzzy;
 pub mod image_embedder;
 pub mod repair_db;
 pub mod search;
+pub mod settings;
 pub mod watcher;
 #[tauri::command]
 async fn repair_database_command() -> Result<String, String> {
@@ -56,14 +66,30 @@ pub fn run() {
             tracing::info!("Starting Downloads folder indexing for semantic search");
             // run_startup_indexing().await;
 
-            // Initialize the filename index with common directories
+            // Load the saved filename index, building it from the common directories on first run
             tracing::info!("Starting filename index initialization");
+            let is_empty = filename_index::FILENAME_INDEX.read().map(|index| index.is_empty()).unwrap_or(false);
+            if is_empty {
+                if let Err(e) = initialize_filename_index().await {
+                    tracing::error!("Failed to initialize filename index: {}", e);
+                }
+            }
         });
     });
 
     let builder = tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_opener::init());
+        .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // Apply the saved settings and watch the indexed roots again; this needs the app
+            // data dir, so it runs once the app is set up. The watchers use the restored settings.
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                settings::restore_settings(&app_handle).await;
+                watcher::restore_watched_roots(app_handle).await;
+            });
+            Ok(())
+        });
 
     let builder = register_commands(builder);
 
@@ -101,6 +127,14 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         get_indexing_stats_command,
         clear_index_command,
         get_vector_db_stats_command,
+        get_chunking_config_command,
+        set_chunking_config_command,
+        get_archive_config_command,
+        set_archive_config_command,
+        get_ocr_config_command,
+        set_ocr_config_command,
+        get_cross_lingual_config_command,
+        set_cross_lingual_config_command,
         // Benchmark commands
         run_benchmarks,
         // File operations commands
@@ -115,6 +149,11 @@ pub fn register_commands(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<
         // Watcher commands
         start_watcher_command,
         stop_watcher_command,
-        get_watcher_status
+        get_watcher_status,
+        get_watcher_config_command,
+        set_watcher_config_command,
+        add_watched_root_command,
+        remove_watched_root_command,
+        get_watched_roots_command
     ])
 }
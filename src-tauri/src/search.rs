@@ -1,18 +1,21 @@
 use crate::db::{
-    connect_db, open_or_create_amharic_text_table, open_or_create_image_table, open_or_create_text_table, DbError
+    connect_db, escape_like_pattern, open_or_create_amharic_text_table, open_or_create_image_table,
+    open_or_create_text_table, DbError
+};
+use crate::embedder::{embed_amharic_text, embed_text, EmbeddingError};
+use crate::extractor::{
+    detect_chunk_language, get_content_type, normalize_amharic, split_tags, ContentType, DetectedLanguage, LanguageDetection,
 };
-use crate::embedder::{embed_text, EmbeddingError};
-use crate::extractor::{ContentType, DetectedLanguage}; // Added import
 use crate::image_embedder::{embed_text_for_image_search, ImageEmbeddingError};
-use arrow_array::{Array, Float32Array, StringArray, TimestampSecondArray};
+use arrow_array::{Array, Float32Array, Float64Array, Int32Array, StringArray, TimestampSecondArray};
 use futures_util::TryStreamExt;
 use lancedb::connection::Connection;
 use lancedb::query::{ExecutableQuery, QueryBase, Select};
 use lancedb::table::Table;
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
-use whatlang::{detect, Lang};
 use std::cmp::Ordering;
+use std::path::Path;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -49,9 +52,49 @@ pub enum SearchError {
 pub enum SearchContentType {
     All,
     TextOnly,
+    CodeOnly,
     ImageOnly,
 }
 
+impl SearchContentType {
+    /// Returns true if results of the given content type pass this filter
+    pub fn includes(&self, content_type: &ContentType) -> bool {
+        match self {
+            SearchContentType::All => true,
+            SearchContentType::TextOnly => *content_type == ContentType::Text,
+            SearchContentType::CodeOnly => *content_type == ContentType::Code,
+            SearchContentType::ImageOnly => *content_type == ContentType::Image,
+        }
+    }
+}
+
+/// Which languages a text query is matched against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchLanguage {
+    /// Embed the query with the model of its own language and search the content embedded
+    /// with that model
+    #[default]
+    Auto,
+    /// Cross-lingual: embed the query with the multilingual model and search all
+    /// multilingual-embedded content, whatever its language, along with the English table
+    Any,
+}
+
+impl SearchLanguage {
+    /// Parses the mode requested by the frontend; unknown values fall back to `Auto`
+    pub fn from_request(value: Option<&str>) -> Self {
+        match value {
+            Some("any") => SearchLanguage::Any,
+            Some("auto") | None => SearchLanguage::Auto,
+            Some(unknown) => {
+                warn!("Unknown search language mode: {}", unknown);
+                SearchLanguage::Auto
+            }
+        }
+    }
+}
+
 /// Represents a single search result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -72,6 +115,275 @@ pub struct SearchResult {
 
     /// Optional image-specific data
     pub image_data: Option<ImageData>,
+
+    /// Heading path of the best matching chunk (e.g. `Guide > Install > Linux`)
+    pub heading_path: Option<String>,
+
+    /// Page of the best matching chunk, for paged documents such as PDFs
+    pub page_number: Option<u32>,
+
+    /// Worksheet and row range of the best matching chunk, for spreadsheets
+    pub sheet_name: Option<String>,
+    pub row_start: Option<u32>,
+    pub row_end: Option<u32>,
+
+    /// Symbol name and line range of the best matching chunk, for source code
+    pub symbol_name: Option<String>,
+    pub line_start: Option<u32>,
+    pub line_end: Option<u32>,
+
+    /// Message of the best matching chunk, for email files and archives
+    pub message_index: Option<u32>,
+    pub message_subject: Option<String>,
+    pub message_from: Option<String>,
+    pub message_to: Option<String>,
+    pub message_date: Option<String>,
+
+    /// Title, author, creation date and tags of the document, from its metadata
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub created: Option<String>,
+    pub tags: Vec<String>,
+
+    /// Language code of the best matching chunk (`en`, `am`, ...), for text results
+    pub language: Option<String>,
+}
+
+/// Document metadata filters written into the query, e.g. `author:"Jane Doe" budget`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataFilter {
+    /// Case-insensitive substring of the title (or email subject)
+    pub title: Option<String>,
+    /// Case-insensitive substring of the author (or email sender)
+    pub author: Option<String>,
+    /// Prefix of the creation date (or email date), e.g. `2024` or `2024-03`
+    pub created: Option<String>,
+    /// Tags that must all be present, each matched as a whole tag, ignoring case
+    pub tags: Vec<String>,
+}
+
+impl MetadataFilter {
+    /// Splits `title:`, `author:`, `tag:` and `created:` filters off a query and returns the
+    /// remaining search text. Values may be quoted to include spaces.
+    pub fn parse_query(query: &str) -> (String, MetadataFilter) {
+        let mut filter = MetadataFilter::default();
+        let mut text: Vec<String> = Vec::new();
+        let mut rest = query.trim_start();
+
+        while !rest.is_empty() {
+            let token_end = token_end(rest);
+            let token = &rest[..token_end];
+            let parsed = token.split_once(':').and_then(|(key, value)| {
+                let value = value.trim_matches('"');
+                if value.is_empty() {
+                    return None;
+                }
+                let slot = match key.to_lowercase().as_str() {
+                    "title" => &mut filter.title,
+                    "author" => &mut filter.author,
+                    "created" => &mut filter.created,
+                    "tag" | "tags" => {
+                        filter.tags.push(value.to_string());
+                        return Some(());
+                    }
+                    _ => return None,
+                };
+                *slot = Some(value.to_string());
+                Some(())
+            });
+            if parsed.is_none() {
+                text.push(token.to_string());
+            }
+            rest = rest[token_end..].trim_start();
+        }
+        (text.join(" "), filter)
+    }
+
+    /// Returns true if no filter is set
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.author.is_none() && self.created.is_none() && self.tags.is_empty()
+    }
+
+    /// SQL predicate selecting the chunks that match every filter, `None` without filters
+    pub fn to_predicate(&self) -> Option<String> {
+        let contains = |column: &str, value: &str| {
+            format!("lower({}) LIKE '%{}%' ESCAPE '\\'", column, escape_like_pattern(&value.to_lowercase()))
+        };
+        let mut clauses = Vec::new();
+        if let Some(title) = &self.title {
+            clauses.push(format!("({} OR {})", contains("title", title), contains("message_subject", title)));
+        }
+        if let Some(author) = &self.author {
+            clauses.push(format!("({} OR {})", contains("author", author), contains("message_from", author)));
+        }
+        if let Some(created) = &self.created {
+            let prefix = escape_like_pattern(created);
+            clauses.push(format!(
+                "(created LIKE '{0}%' ESCAPE '\\' OR message_date LIKE '{0}%' ESCAPE '\\')",
+                prefix
+            ));
+        }
+        // The tags column joins the tags with ", ", so a tag is either the whole value or
+        // delimited by ", " on each side that is not the start or end of the value
+        for tag in &self.tags {
+            let tag = tag.trim().to_lowercase();
+            let pattern = escape_like_pattern(&tag);
+            clauses.push(format!(
+                "(lower(tags) = '{0}' OR lower(tags) LIKE '{1}, %' ESCAPE '\\' OR lower(tags) LIKE '%, {1}' ESCAPE '\\' \
+                 OR lower(tags) LIKE '%, {1}, %' ESCAPE '\\')",
+                tag.replace('\'', "''"),
+                pattern
+            ));
+        }
+        if clauses.is_empty() {
+            None
+        } else {
+            Some(clauses.join(" AND "))
+        }
+    }
+}
+
+/// Image property filters written into the query, e.g. `beach taken in 2024 landscape orientation`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageFilter {
+    /// Prefix of the capture date, e.g. `2024` or `2024-05`
+    pub taken: Option<String>,
+    /// `landscape`, `portrait` or `square`
+    pub orientation: Option<String>,
+    /// Case-insensitive substring of the camera make and model
+    pub camera: Option<String>,
+    /// Only images with a GPS position
+    pub has_location: bool,
+}
+
+const IMAGE_ORIENTATIONS: &[&str] = &["landscape", "portrait", "square"];
+
+impl ImageFilter {
+    /// Splits image filters off a query and returns the remaining search text. Understood forms:
+    /// `taken in 2024`, `taken 2024-05`, `taken:2024`, `landscape orientation`,
+    /// `orientation:portrait`, `camera:"EOS R6"` and `has:location`.
+    pub fn parse_query(query: &str) -> (String, ImageFilter) {
+        let mut tokens = Vec::new();
+        let mut rest = query.trim_start();
+        while !rest.is_empty() {
+            let end = token_end(rest);
+            tokens.push(&rest[..end]);
+            rest = rest[end..].trim_start();
+        }
+
+        let mut filter = ImageFilter::default();
+        let mut text: Vec<&str> = Vec::new();
+        let mut index = 0;
+        while index < tokens.len() {
+            let token = tokens[index].to_lowercase();
+            let next = |offset: usize| tokens.get(index + offset).map(|token| token.to_lowercase());
+
+            if token == "taken" {
+                let date_offset = if next(1).as_deref() == Some("in") { 2 } else { 1 };
+                if let Some(date) = next(date_offset).filter(|date| is_date_prefix(date)) {
+                    filter.taken = Some(date);
+                    index += date_offset + 1;
+                    continue;
+                }
+            }
+            if IMAGE_ORIENTATIONS.contains(&token.as_str()) && next(1).as_deref() == Some("orientation") {
+                filter.orientation = Some(token);
+                index += 2;
+                continue;
+            }
+            let parsed = token.split_once(':').is_some_and(|(key, value)| {
+                let value = value.trim_matches('"');
+                match key {
+                    "taken" if is_date_prefix(value) => filter.taken = Some(value.to_string()),
+                    "orientation" if IMAGE_ORIENTATIONS.contains(&value) => {
+                        filter.orientation = Some(value.to_string())
+                    }
+                    "camera" if !value.is_empty() => filter.camera = Some(value.to_string()),
+                    "has" if value == "location" || value == "gps" => filter.has_location = true,
+                    _ => return false,
+                }
+                true
+            });
+            if !parsed {
+                text.push(tokens[index]);
+            }
+            index += 1;
+        }
+        (text.join(" "), filter)
+    }
+
+    /// Returns true if no filter is set
+    pub fn is_empty(&self) -> bool {
+        self.taken.is_none() && self.orientation.is_none() && self.camera.is_none() && !self.has_location
+    }
+
+    /// SQL predicate selecting the images that match every filter, `None` without filters
+    pub fn to_predicate(&self) -> Option<String> {
+        let mut clauses = Vec::new();
+        if let Some(taken) = &self.taken {
+            clauses.push(format!("taken_at LIKE '{}%' ESCAPE '\\'", escape_like_pattern(taken)));
+        }
+        if let Some(orientation) = &self.orientation {
+            clauses.push(format!("orientation = '{}'", orientation.replace('\'', "''")));
+        }
+        if let Some(camera) = &self.camera {
+            clauses.push(format!(
+                "lower(camera_model) LIKE '%{}%' ESCAPE '\\'",
+                escape_like_pattern(&camera.to_lowercase())
+            ));
+        }
+        if self.has_location {
+            clauses.push("latitude IS NOT NULL AND longitude IS NOT NULL".to_string());
+        }
+        if clauses.is_empty() {
+            None
+        } else {
+            Some(clauses.join(" AND "))
+        }
+    }
+}
+
+/// Query text and filters for each table searched by `multimodal_search`
+#[derive(Debug, PartialEq)]
+struct SearchLegs {
+    text: Option<(String, MetadataFilter)>,
+    images: Option<(String, ImageFilter)>,
+}
+
+impl SearchLegs {
+    /// Each leg takes only its own filters out of the query. Phrases meant for the other
+    /// leg stay search text, so `contract taken in 2024` still searches documents.
+    fn plan(query: &str, content_filter: SearchContentType) -> Self {
+        let text = (content_filter != SearchContentType::ImageOnly).then(|| MetadataFilter::parse_query(query));
+        let images = matches!(content_filter, SearchContentType::All | SearchContentType::ImageOnly)
+            .then(|| ImageFilter::parse_query(query));
+        SearchLegs { text, images }
+    }
+}
+
+/// Returns true for `YYYY`, `YYYY-MM` and `YYYY-MM-DD`
+fn is_date_prefix(value: &str) -> bool {
+    let parts: Vec<&str> = value.split('-').collect();
+    let digits = |part: &str, len: usize| part.len() == len && part.chars().all(|c| c.is_ascii_digit());
+    match parts.as_slice() {
+        [year] => digits(year, 4),
+        [year, month] => digits(year, 4) && digits(month, 2),
+        [year, month, day] => digits(year, 4) && digits(month, 2) && digits(day, 2),
+        _ => false,
+    }
+}
+
+/// Length of the next whitespace-separated token, treating double-quoted spans as one token
+fn token_end(input: &str) -> usize {
+    let mut in_quotes = false;
+    for (index, c) in input.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => return index,
+            _ => {}
+        }
+    }
+    input.len()
 }
 
 /// Additional data for image results
@@ -80,6 +392,14 @@ pub struct ImageData {
     pub width: Option<i32>,
     pub height: Option<i32>,
     pub thumbnail_path: Option<String>,
+    /// EXIF capture date (`YYYY-MM-DDTHH:MM:SS`)
+    pub taken_at: Option<String>,
+    pub camera_model: Option<String>,
+    /// `landscape`, `portrait` or `square`
+    pub orientation: Option<String>,
+    /// GPS position in decimal degrees
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
 }
 
 /// Performs a semantic search using the given query across both text and image tables
@@ -90,12 +410,14 @@ pub struct ImageData {
 /// * `limit` - Maximum number of results to return (default: DEFAULT_SEARCH_LIMIT)
 /// * `min_score` - Minimum score threshold (0.0 to 1.0, default: DEFAULT_MIN_SCORE)
 /// * `content_type` - Filter to specific content type (default: SearchContentType::All)
+/// * `language` - Language mode for text results (default: SearchLanguage::Auto)
 pub async fn multimodal_search(
     conn: &Connection,
     query: &str,
     limit: Option<usize>,
     min_score: Option<f32>,
     content_type: Option<SearchContentType>,
+    language: Option<SearchLanguage>,
 ) -> Result<Vec<SearchResult>, SearchError> {
     // Validate input
     if query.trim().is_empty() {
@@ -116,9 +438,9 @@ pub async fn multimodal_search(
         result_limit, score_threshold, content_filter
     );
 
-    // Open tables and decide which ones to search
-    let search_text = true;
-    let search_images = true;
+    // Metadata filters only apply to documents and image filters only to images
+    let legs = SearchLegs::plan(query, content_filter);
+    debug!("Search legs: {:?}", legs);
 
     // Store all results in a single vector
     let mut combined_results = Vec::new();
@@ -128,15 +450,15 @@ pub async fn multimodal_search(
     let fetch_limit = result_limit * 2;
 
     // Search for text content if requested
-    if search_text {
+    if let Some((query, metadata_filter)) = &legs.text {
         debug!("Searching text content for: {}", query);
         #[cfg(test)]
         println!("Searching text content for: {}", query);
 
         
-        let query = format!("{}", query);
-        let text_results =
-            search_text_content(&query, fetch_limit, score_threshold).await?;
+        let mut text_results =
+            search_text_content(query, metadata_filter, language.unwrap_or_default(), fetch_limit, score_threshold).await?;
+        text_results.retain(|result| content_filter.includes(&result.content_type));
 
         debug!("Found {} text results", text_results.len());
         #[cfg(test)]
@@ -146,7 +468,7 @@ pub async fn multimodal_search(
     }
 
     // Search for images if requested
-    if search_images {
+    if let Some((query, image_filter)) = &legs.images {
         debug!("Searching image content for: {}", query);
         println!("Searching image content for: {}", query);
         #[cfg(test)]
@@ -155,7 +477,7 @@ pub async fn multimodal_search(
         let image_table = open_or_create_image_table(conn).await?;
 
         println!("the image table connected successfully");
-        match search_image_content(&image_table, query, fetch_limit, score_threshold).await {
+        match search_image_content(&image_table, query, image_filter, fetch_limit, score_threshold).await {
             Ok(image_results) => {
                 debug!("Found {} image results", image_results.len());
 
@@ -213,69 +535,162 @@ pub async fn multimodal_search(
     Ok(combined_results)
 }
 
-/// Search for text content using the given query
+/// Columns read from the chunk tables for every text result
+const TEXT_RESULT_COLUMNS: &[&str] = &[
+    "file_path",
+    "content_hash",
+    "chunk_id",
+    "last_modified",
+    "heading_path",
+    "page_number",
+    "sheet_name",
+    "row_start",
+    "row_end",
+    "symbol_name",
+    "line_start",
+    "line_end",
+    "message_index",
+    "message_subject",
+    "message_from",
+    "message_to",
+    "message_date",
+    "title",
+    "author",
+    "created",
+    "tags",
+    "language",
+];
+
+/// Excludes the multilingual copies of English chunks, which only cross-lingual queries should see
+const EXCLUDE_ENGLISH_COPIES: &str = "(language IS NULL OR language <> 'en')";
+
+/// Search for text content using the given query, restricted to chunks matching `filter`.
+/// A query made only of filters lists the matching documents without ranking them.
 async fn search_text_content(
     query: &str,
+    filter: &MetadataFilter,
+    language: SearchLanguage,
     limit: usize,
     min_score: f32,
 ) -> Result<Vec<SearchResult>, SearchError> {
-    let lang_info = detect(&query);
-    let detected_lang = match lang_info {
-        Some(info) if info.lang() == Lang::Eng => DetectedLanguage::English,
-        Some(info) if info.lang() == Lang::Amh => DetectedLanguage::Amharic,
-        _ => DetectedLanguage::Other,
-    };
-    println!("Detected language: {:?}", detected_lang);
-    // Generate embedding for the query
-    let query_vec = vec![query.to_string()];
-    // For search queries, assume English for now. This could be enhanced later.
-    let embeddings = embed_text(&query_vec, &detected_lang, true)?;
-
-    if embeddings.is_empty() {
-        return Err(SearchError::OperationFailed(
-            "Failed to generate embedding for query".to_string(),
-        ));
-    }
-
-    // Use the first embedding for the query (since it may be chunked)
-    let query_embedding = &embeddings[0];
+    let conn = connect_db().await?;
+    let predicate = filter.to_predicate();
+
+    let record_batches = if query.trim().is_empty() {
+        let Some(predicate) = predicate else {
+            return Err(SearchError::EmptyQuery);
+        };
+        // Without a query vector there is no language to pick a table by: list matches from both
+        let mut record_batches = Vec::new();
+        for table in [open_or_create_text_table(&conn).await?, open_or_create_amharic_text_table(&conn).await?] {
+            let batches = table
+                .query()
+                .only_if(predicate.clone())
+                .select(Select::columns(TEXT_RESULT_COLUMNS))
+                .limit(limit)
+                .execute()
+                .await
+                .map_err(|e| DbError::from(e))?
+                .try_collect::<Vec<_>>()
+                .await
+                .map_err(|e| SearchError::OperationFailed(e.to_string()))?;
+            record_batches.extend(batches);
+        }
+        record_batches
+    } else if language == SearchLanguage::Any {
+        // One multilingual query vector matches content in every language in the multilingual
+        // table. English chunks indexed without multilingual copies are only in the English
+        // table, so it is searched too with the English query vector; each file keeps its
+        // best match below.
+        let query = normalize_amharic(query);
+        debug!("Cross-lingual search for: {}", query);
+        let first_vector = |embeddings: Vec<Vec<f32>>| {
+            embeddings
+                .into_iter()
+                .next()
+                .ok_or_else(|| SearchError::OperationFailed("Failed to generate embedding for query".to_string()))
+        };
+        let multilingual_vec = first_vector(embed_amharic_text(&[query.to_string()], true)?)?;
+        let english_vec = first_vector(embed_text(&[query.to_string()], &DetectedLanguage::English, true)?)?;
+
+        let mut record_batches = Vec::new();
+        for (table, query_vec) in [
+            (open_or_create_amharic_text_table(&conn).await?, multilingual_vec),
+            (open_or_create_text_table(&conn).await?, english_vec),
+        ] {
+            let mut vector_query = table
+                .query()
+                .nearest_to(query_vec)
+                .map_err(|e| DbError::from(e))?
+                .select(Select::columns(TEXT_RESULT_COLUMNS));
+            if let Some(predicate) = predicate.clone() {
+                vector_query = vector_query.only_if(predicate);
+            }
 
-    // Convert Vec<f32> to a format LanceDB can use
-    let query_vec = query_embedding.clone();
+            let batches = vector_query
+                .limit(limit)
+                .execute()
+                .await
+                .map_err(|e| DbError::from(e))?
+                .try_collect::<Vec<_>>()
+                .await
+                .map_err(|e| SearchError::OperationFailed(e.to_string()))?;
+            record_batches.extend(batches);
+        }
+        record_batches
+    } else {
+        // The query is embedded with the model of its language and compared against the
+        // table holding chunks embedded with that model. Queries too short to detect are
+        // treated as English. Amharic queries are normalized like the indexed text.
+        let query = normalize_amharic(query);
+        let detected_lang = detect_chunk_language(
+            &query,
+            &LanguageDetection { language: DetectedLanguage::English, confidence: 0.0 },
+        )
+        .language;
+        debug!("Detected query language: {:?}", detected_lang);
+        let (table, predicate) = if detected_lang.uses_multilingual_model() {
+            let predicate = match predicate {
+                Some(predicate) => format!("({}) AND {}", predicate, EXCLUDE_ENGLISH_COPIES),
+                None => EXCLUDE_ENGLISH_COPIES.to_string(),
+            };
+            (open_or_create_amharic_text_table(&conn).await?, Some(predicate))
+        } else {
+            (open_or_create_text_table(&conn).await?, predicate)
+        };
+
+        // Generate embedding for the query
+        let query_vec = vec![query.to_string()];
+        let embeddings = embed_text(&query_vec, &detected_lang, true)?;
+
+        if embeddings.is_empty() {
+            return Err(SearchError::OperationFailed(
+                "Failed to generate embedding for query".to_string(),
+            ));
+        }
 
-    // Use the query() method with vector similarity
-    let conn = connect_db().await?;
-    let table = open_or_create_amharic_text_table(&conn).await?;
-    // let table = if detected_lang == DetectedLanguage::Amharic {
-    //     open_or_create_amharic_text_table(&conn).await?
-    // } else {
-    //     open_or_create_text_table(&conn).await?
-    // };
-    println!("table name: {}", table.name());
-    println!("table schema: {:?}", detected_lang);
-    // Include all necessary columns
-    let vector_query = table
-        .query()
-        .nearest_to(query_vec)
-        .map_err(|e| DbError::from(e))?
-        .select(Select::columns(&[
-            "file_path",
-            "content_hash",
-            "chunk_id",
-            "last_modified",
-        ]));
-
-    let query_result = vector_query
-        .limit(limit)
-        .execute()
-        .await
-        .map_err(|e| DbError::from(e))?;
+        // Use the first embedding for the query (since it may be chunked)
+        let query_vec = embeddings[0].clone();
+
+        // Use the query() method with vector similarity
+        let mut vector_query = table
+            .query()
+            .nearest_to(query_vec)
+            .map_err(|e| DbError::from(e))?
+            .select(Select::columns(TEXT_RESULT_COLUMNS));
+        if let Some(predicate) = predicate {
+            vector_query = vector_query.only_if(predicate);
+        }
 
-    // Collect all batches from the stream
-    let record_batches = query_result
-        .try_collect::<Vec<_>>()
-        .await
-        .map_err(|e| SearchError::OperationFailed(e.to_string()))?;
+        vector_query
+            .limit(limit)
+            .execute()
+            .await
+            .map_err(|e| DbError::from(e))?
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|e| SearchError::OperationFailed(e.to_string()))?
+    };
 
     // A map to track the best result for each file path
     let mut best_results: std::collections::HashMap<String, SearchResult> =
@@ -303,18 +718,64 @@ async fn search_text_content(
                 SearchError::OperationFailed("Missing last_modified column".to_string())
             })?;
 
-        // The distance column name might vary by LanceDB version, try both common names
+        // Optional columns
+        let heading_paths = batch
+            .column_by_name("heading_path")
+            .and_then(|array| array.as_any().downcast_ref::<StringArray>());
+        let page_numbers = batch
+            .column_by_name("page_number")
+            .and_then(|array| array.as_any().downcast_ref::<Int32Array>());
+        let sheet_names = batch
+            .column_by_name("sheet_name")
+            .and_then(|array| array.as_any().downcast_ref::<StringArray>());
+        let row_starts = batch
+            .column_by_name("row_start")
+            .and_then(|array| array.as_any().downcast_ref::<Int32Array>());
+        let row_ends = batch
+            .column_by_name("row_end")
+            .and_then(|array| array.as_any().downcast_ref::<Int32Array>());
+        let symbol_names = batch
+            .column_by_name("symbol_name")
+            .and_then(|array| array.as_any().downcast_ref::<StringArray>());
+        let line_starts = batch
+            .column_by_name("line_start")
+            .and_then(|array| array.as_any().downcast_ref::<Int32Array>());
+        let line_ends = batch
+            .column_by_name("line_end")
+            .and_then(|array| array.as_any().downcast_ref::<Int32Array>());
+        let message_indexes = batch
+            .column_by_name("message_index")
+            .and_then(|array| array.as_any().downcast_ref::<Int32Array>());
+        let string_column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .and_then(|array| array.as_any().downcast_ref::<StringArray>())
+        };
+        let message_subjects = string_column("message_subject");
+        let message_froms = string_column("message_from");
+        let message_tos = string_column("message_to");
+        let message_dates = string_column("message_date");
+
+        let titles = string_column("title");
+        let authors = string_column("author");
+        let createds = string_column("created");
+        let tags = string_column("tags");
+        let languages = string_column("language");
+
+        // The distance column name might vary by LanceDB version, try both common names.
+        // Filter-only queries have no distance at all.
         let distances = batch
             .column_by_name("distance")
             .or_else(|| batch.column_by_name("_distance"))
-            .and_then(|array| array.as_any().downcast_ref::<Float32Array>())
-            .ok_or_else(|| SearchError::OperationFailed("Missing distance column".to_string()))?;
+            .and_then(|array| array.as_any().downcast_ref::<Float32Array>());
+        if distances.is_none() && !query.trim().is_empty() {
+            return Err(SearchError::OperationFailed("Missing distance column".to_string()));
+        }
 
         // Process each row in the batch
         for i in 0..batch.num_rows() {
-            // Convert distance to score (0-1 scale, higher is better)
-            let distance = distances.value(i);
-            let score = 1.0 - (distance / 2.0);
+            // Convert distance to score (0-1 scale, higher is better); unranked matches score 1.0
+            let score = distances.map_or(1.0, |distances| 1.0 - (distances.value(i) / 2.0));
 
             // Skip results below threshold
             if score < min_score {
@@ -324,14 +785,67 @@ async fn search_text_content(
             let file_path = files.value(i).to_string();
             let content_hash = content_hashes.value(i).to_string();
             let last_modified = last_modified.value(i);
+            let heading_path = heading_paths
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i).to_string());
+            let page_number = page_numbers
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i) as u32);
+            let sheet_name = sheet_names
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i).to_string());
+            let row_start = row_starts
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i) as u32);
+            let row_end = row_ends
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i) as u32);
+            let symbol_name = symbol_names
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i).to_string());
+            let line_start = line_starts
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i) as u32);
+            let line_end = line_ends
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i) as u32);
+            let message_index = message_indexes
+                .filter(|array| !array.is_null(i))
+                .map(|array| array.value(i) as u32);
+            let string_value = |array: Option<&StringArray>| {
+                array.filter(|array| !array.is_null(i)).map(|array| array.value(i).to_string())
+            };
+            // Chunks of source files are reported as code, everything else as text
+            let content_type = match get_content_type(Path::new(&file_path)) {
+                ContentType::Code => ContentType::Code,
+                _ => ContentType::Text,
+            };
 
             let result = SearchResult {
                 file_path: file_path.clone(),
                 score,
                 content_hash,
                 last_modified,
-                content_type: ContentType::Text,
+                content_type,
                 image_data: None,
+                heading_path,
+                page_number,
+                sheet_name,
+                row_start,
+                row_end,
+                symbol_name,
+                line_start,
+                line_end,
+                message_index,
+                message_subject: string_value(message_subjects),
+                message_from: string_value(message_froms),
+                message_to: string_value(message_tos),
+                message_date: string_value(message_dates),
+                title: string_value(titles),
+                author: string_value(authors),
+                created: string_value(createds),
+                tags: string_value(tags).map(|tags| split_tags(&tags)).unwrap_or_default(),
+                language: string_value(languages),
             };
 
             // Keep only the highest scoring chunk for each file
@@ -350,40 +864,67 @@ async fn search_text_content(
     Ok(search_results)
 }
 
-/// Search for image content using the given query
+/// Columns read from the image table for every image result
+const IMAGE_RESULT_COLUMNS: &[&str] = &[
+    "file_path",
+    "file_hash",
+    "last_modified",
+    "width",
+    "height",
+    "thumbnail_path",
+    "taken_at",
+    "camera_model",
+    "orientation",
+    "latitude",
+    "longitude",
+];
+
+/// Search for image content using the given query, restricted to images matching `filter`.
+/// A query made only of filters lists the matching images without ranking them.
 async fn search_image_content(
     table: &Table,
     query: &str,
+    filter: &ImageFilter,
     limit: usize,
     _min_score: f32,
 ) -> Result<Vec<SearchResult>, SearchError> {
-    // Generate embedding for the query text to search image embeddings
-    // We use the special text-to-image embedding function to ensure compatibility
-
-    let embedding = embed_text_for_image_search(query).map_err(|e| {
-        warn!("Failed to generate image-compatible text embedding: {}", e);
-        SearchError::ImageEmbeddingError(e)
-    })?;
-
-    // Use the query() method with vector similarity
-    // Include all necessary columns and use column configuration to specify the vector column
-    let vector_query = table
-        .query()
-        .nearest_to(embedding)
-        .map_err(|e| DbError::from(e))?
-        .select(Select::columns(&[
-            "file_path",
-            "file_hash",
-            "last_modified",
-            "width",
-            "height",
-            "thumbnail_path",
-        ]));
-    let query_result = vector_query
-        .limit(limit)
-        .execute()
-        .await
-        .map_err(|e| DbError::from(e))?;
+    let predicate = filter.to_predicate();
+    let query_result = if query.trim().is_empty() {
+        let Some(predicate) = predicate else {
+            return Err(SearchError::EmptyQuery);
+        };
+        table
+            .query()
+            .only_if(predicate)
+            .select(Select::columns(IMAGE_RESULT_COLUMNS))
+            .limit(limit)
+            .execute()
+            .await
+            .map_err(|e| DbError::from(e))?
+    } else {
+        // Generate embedding for the query text to search image embeddings
+        // We use the special text-to-image embedding function to ensure compatibility
+        let embedding = embed_text_for_image_search(query).map_err(|e| {
+            warn!("Failed to generate image-compatible text embedding: {}", e);
+            SearchError::ImageEmbeddingError(e)
+        })?;
+
+        // Use the query() method with vector similarity
+        // Include all necessary columns and use column configuration to specify the vector column
+        let mut vector_query = table
+            .query()
+            .nearest_to(embedding)
+            .map_err(|e| DbError::from(e))?
+            .select(Select::columns(IMAGE_RESULT_COLUMNS));
+        if let Some(predicate) = predicate {
+            vector_query = vector_query.only_if(predicate);
+        }
+        vector_query
+            .limit(limit)
+            .execute()
+            .await
+            .map_err(|e| DbError::from(e))?
+    };
 
     // Collect all batches from the stream
     let record_batches = query_result
@@ -425,21 +966,39 @@ async fn search_image_content(
         let thumbnail_paths = batch
             .column_by_name("thumbnail_path")
             .and_then(|array| array.as_any().downcast_ref::<StringArray>());
-
-        // The distance column name might vary by LanceDB version, try both common names
+        let string_column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .and_then(|array| array.as_any().downcast_ref::<StringArray>())
+        };
+        let float_column = |name: &str| {
+            batch
+                .column_by_name(name)
+                .and_then(|array| array.as_any().downcast_ref::<Float64Array>())
+        };
+        let taken_ats = string_column("taken_at");
+        let camera_models = string_column("camera_model");
+        let orientations = string_column("orientation");
+        let latitudes = float_column("latitude");
+        let longitudes = float_column("longitude");
+
+        // The distance column name might vary by LanceDB version, try both common names.
+        // Filter-only queries have no distance at all.
         let distances = batch
             .column_by_name("distance")
             .or_else(|| batch.column_by_name("_distance"))
-            .and_then(|array| array.as_any().downcast_ref::<Float32Array>())
-            .ok_or_else(|| SearchError::OperationFailed("Missing distance column".to_string()))?;
+            .and_then(|array| array.as_any().downcast_ref::<Float32Array>());
+        if distances.is_none() && !query.trim().is_empty() {
+            return Err(SearchError::OperationFailed("Missing distance column".to_string()));
+        }
 
         // Process each row in the batch
         for i in 0..batch.num_rows() {
-            // Convert distance to score (0-1 scale, higher is better)
-            let distance = distances.value(i);
-            println!("distances: {:?}", distances);
-            let score = 1.0 - (distance / 2.0);
-            let score = score * 10.0;
+            // Convert distance to score (0-1 scale, higher is better); unranked matches score 1.0
+            let score = match distances {
+                Some(distances) => (1.0 - (distances.value(i) / 2.0)) * 10.0,
+                None => 1.0,
+            };
             if score < 0.5 {
                 continue;
             }
@@ -476,10 +1035,22 @@ async fn search_image_content(
                 })
                 .flatten();
 
+            let string_value = |array: Option<&StringArray>| {
+                array.filter(|array| !array.is_null(i)).map(|array| array.value(i).to_string())
+            };
+            let float_value = |array: Option<&Float64Array>| {
+                array.filter(|array| !array.is_null(i)).map(|array| array.value(i))
+            };
+
             let image_data = Some(ImageData {
                 width,
                 height,
                 thumbnail_path,
+                taken_at: string_value(taken_ats),
+                camera_model: string_value(camera_models),
+                orientation: string_value(orientations),
+                latitude: float_value(latitudes),
+                longitude: float_value(longitudes),
             });
 
             let result = SearchResult {
@@ -489,6 +1060,24 @@ async fn search_image_content(
                 last_modified,
                 content_type: ContentType::Image,
                 image_data,
+                heading_path: None,
+                page_number: None,
+                sheet_name: None,
+                row_start: None,
+                row_end: None,
+                symbol_name: None,
+                line_start: None,
+                line_end: None,
+                message_index: None,
+                message_subject: None,
+                message_from: None,
+                message_to: None,
+                message_date: None,
+                title: None,
+                author: None,
+                created: None,
+                tags: Vec::new(),
+                language: None,
             };
 
             // Keep only the highest scoring result for each file
@@ -515,7 +1104,9 @@ pub async fn semantic_search(
 ) -> Result<Vec<SearchResult>, SearchError> {
     let result_limit = limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
     let score_threshold = min_score.unwrap_or(DEFAULT_MIN_SCORE);
-    let results = search_text_content(query, result_limit, score_threshold).await?;
+    let (query, metadata_filter) = MetadataFilter::parse_query(query);
+    let results =
+        search_text_content(&query, &metadata_filter, SearchLanguage::Auto, result_limit, score_threshold).await?;
 
     Ok(results)
 }
@@ -525,6 +1116,7 @@ mod tests {
     use super::*;
     use crate::db::TestDb;
     use crate::db::{connect_db_with_path, upsert_document, upsert_image};
+    use crate::extractor::ImageMetadata;
 
     // Setup test database with both text and image data
     async fn setup_test_multimodal_db() -> (Connection, TestDb) {
@@ -599,9 +1191,9 @@ mod tests {
                 &image_table,
                 &file_path,
                 &file_hash,
+                None,
                 &embedding,
-                Some(640),
-                Some(480),
+                &ImageMetadata { width: Some(640), height: Some(480), ..ImageMetadata::default() },
                 Some("/thumbnails/thumb.jpg"),
             )
             .await
@@ -611,13 +1203,98 @@ mod tests {
         (conn, test_db)
     }
 
+    #[test]
+    fn test_parse_query_extracts_metadata_filters() {
+        let (text, filter) =
+            MetadataFilter::parse_query(r#"budget author:"Abebe Kebede" tag:finance created:2024 Tag:Q3 http://x"#);
+        assert_eq!(text, "budget http://x");
+        assert_eq!(filter.author.as_deref(), Some("Abebe Kebede"));
+        assert_eq!(filter.created.as_deref(), Some("2024"));
+        assert_eq!(filter.tags, vec!["finance", "Q3"]);
+        assert_eq!(filter.title, None);
+
+        // Tags match whole entries of the comma-joined column, not substrings
+        let (_, filter) = MetadataFilter::parse_query("tag:Q3");
+        assert_eq!(
+            filter.to_predicate().unwrap(),
+            "(lower(tags) = 'q3' OR lower(tags) LIKE 'q3, %' ESCAPE '\\' OR lower(tags) LIKE '%, q3' ESCAPE '\\' \
+             OR lower(tags) LIKE '%, q3, %' ESCAPE '\\')"
+        );
+
+        // A key without a value is ordinary search text
+        let (text, filter) = MetadataFilter::parse_query("author: notes");
+        assert_eq!(text, "author: notes");
+        assert!(filter.is_empty());
+        assert_eq!(filter.to_predicate(), None);
+    }
+
+    #[test]
+    fn test_parse_query_extracts_image_filters() {
+        let (text, filter) = ImageFilter::parse_query("beach sunset taken in 2024 Landscape orientation camera:\"EOS R6\"");
+        assert_eq!(text, "beach sunset");
+        assert_eq!(filter.taken.as_deref(), Some("2024"));
+        assert_eq!(filter.orientation.as_deref(), Some("landscape"));
+        assert_eq!(filter.camera.as_deref(), Some("eos r6"));
+        assert_eq!(
+            filter.to_predicate().unwrap(),
+            "taken_at LIKE '2024%' ESCAPE '\\' AND orientation = 'landscape' AND lower(camera_model) LIKE '%eos r6%' ESCAPE '\\'"
+        );
+
+        // Ordinary words are left alone
+        let (text, filter) = ImageFilter::parse_query("landscape painting taken yesterday");
+        assert_eq!(text, "landscape painting taken yesterday");
+        assert!(filter.is_empty());
+
+        let (text, filter) = ImageFilter::parse_query("taken:2023-07 has:location");
+        assert_eq!(text, "");
+        assert_eq!(filter.taken.as_deref(), Some("2023-07"));
+        assert!(filter.has_location);
+    }
+
+    #[test]
+    fn test_filters_do_not_disable_the_other_leg() {
+        let legs = SearchLegs::plan("contract taken in 2024", SearchContentType::TextOnly);
+        assert_eq!(legs.text, Some(("contract taken in 2024".to_string(), MetadataFilter::default())));
+        assert_eq!(legs.images, None);
+
+        let legs = SearchLegs::plan("contract taken in 2024", SearchContentType::All);
+        assert_eq!(legs.text, Some(("contract taken in 2024".to_string(), MetadataFilter::default())));
+        let (image_text, image_filter) = legs.images.unwrap();
+        assert_eq!(image_text, "contract");
+        assert_eq!(image_filter.taken.as_deref(), Some("2024"));
+
+        let legs = SearchLegs::plan("author:Abebe beach", SearchContentType::All);
+        let (text, metadata_filter) = legs.text.unwrap();
+        assert_eq!(text, "beach");
+        assert_eq!(metadata_filter.author.as_deref(), Some("Abebe"));
+        assert_eq!(legs.images, Some(("author:Abebe beach".to_string(), ImageFilter::default())));
+    }
+
+    #[test]
+    fn test_metadata_filter_predicate_escapes_values() {
+        let filter = MetadataFilter { author: Some("O'Brien_100%".to_string()), ..MetadataFilter::default() };
+        assert_eq!(
+            filter.to_predicate().unwrap(),
+            r"(lower(author) LIKE '%o''brien\_100\%%' ESCAPE '\' OR lower(message_from) LIKE '%o''brien\_100\%%' ESCAPE '\')"
+        );
+    }
+
+    #[test]
+    fn test_search_language_from_request() {
+        assert_eq!(SearchLanguage::from_request(Some("any")), SearchLanguage::Any);
+        assert_eq!(SearchLanguage::from_request(Some("auto")), SearchLanguage::Auto);
+        assert_eq!(SearchLanguage::from_request(None), SearchLanguage::Auto);
+        assert_eq!(SearchLanguage::from_request(Some("klingon")), SearchLanguage::Auto);
+        assert_eq!(serde_json::to_string(&SearchLanguage::Any).unwrap(), "\"any\"");
+    }
+
     #[tokio::test]
     async fn test_multimodal_search_validates_input() {
         let (conn, _test_db) = setup_test_multimodal_db().await;
         conn.drop_db();
 
         // Empty query should return error
-        let empty_result = multimodal_search(&conn, "", None, None, None).await;
+        let empty_result = multimodal_search(&conn, "", None, None, None, None).await;
         assert!(empty_result.is_err());
         assert!(matches!(empty_result.unwrap_err(), SearchError::EmptyQuery));
     }
@@ -634,6 +1311,7 @@ mod tests {
             None,
             Some(0.01), // Use a very low threshold to ensure we get results
             Some(SearchContentType::TextOnly), // Focus on text search only for reliable testing
+            None,
         )
         .await;
 
@@ -661,6 +1339,7 @@ mod tests {
             None,
             Some(0.01), // Use a very low threshold for tests
             Some(SearchContentType::TextOnly),
+            None,
         )
         .await;
 
@@ -684,6 +1363,7 @@ mod tests {
             None,
             Some(0.01), // Use a very low threshold for tests
             Some(SearchContentType::ImageOnly),
+            None,
         )
         .await;
 
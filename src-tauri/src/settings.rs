@@ -0,0 +1,145 @@
+use crate::chunker::{get_chunking_config, set_chunking_config, ChunkingConfig};
+use crate::embedder::{get_cross_lingual_config, set_cross_lingual_config, CrossLingualConfig};
+use crate::extractor::{get_archive_config, get_ocr_config, set_archive_config, set_ocr_config, ArchiveConfig, OcrConfig};
+use crate::watcher::{get_watcher_config, set_watcher_config, WatcherConfig};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use thiserror::Error;
+
+// Settings file in the app data directory
+const SETTINGS_FILE: &str = "settings.json";
+
+// Serializes writes of the settings file, so concurrent changes do not interleave
+static SETTINGS_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+#[derive(Error, Debug)]
+pub enum SettingsError {
+    #[error("Settings storage error: {0}")]
+    Storage(String),
+}
+
+/// The indexing and watcher configurations chosen by the user, persisted in the app data
+/// directory and applied again at startup. Sections missing from the file keep their defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub chunking: ChunkingConfig,
+    pub archive: ArchiveConfig,
+    pub ocr: OcrConfig,
+    pub cross_lingual: CrossLingualConfig,
+    pub watcher: WatcherConfig,
+}
+
+impl Settings {
+    /// The configurations currently in use
+    pub fn current() -> Self {
+        Settings {
+            chunking: get_chunking_config(),
+            archive: get_archive_config(),
+            ocr: get_ocr_config(),
+            cross_lingual: get_cross_lingual_config(),
+            watcher: get_watcher_config(),
+        }
+    }
+
+    /// Makes these configurations the active ones. A section that no longer validates, e.g.
+    /// after a hand edit, is skipped and keeps its default.
+    pub fn apply(self) {
+        if let Err(e) = set_chunking_config(self.chunking) {
+            warn!("Ignoring saved chunking configuration: {}", e);
+        }
+        if let Err(e) = set_archive_config(self.archive) {
+            warn!("Ignoring saved archive configuration: {}", e);
+        }
+        if let Err(e) = set_ocr_config(self.ocr) {
+            warn!("Ignoring saved OCR configuration: {}", e);
+        }
+        set_cross_lingual_config(self.cross_lingual);
+        if let Err(e) = set_watcher_config(self.watcher) {
+            warn!("Ignoring saved watcher configuration: {}", e);
+        }
+    }
+
+    /// Reads the settings; a missing file means nothing was changed yet
+    pub async fn load(file: &Path) -> Result<Self, SettingsError> {
+        match tokio::fs::read_to_string(file).await {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| SettingsError::Storage(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Settings::default()),
+            Err(e) => Err(SettingsError::Storage(e.to_string())),
+        }
+    }
+
+    pub async fn save(&self, file: &Path) -> Result<(), SettingsError> {
+        if let Some(parent) = file.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| SettingsError::Storage(e.to_string()))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| SettingsError::Storage(e.to_string()))?;
+        tokio::fs::write(file, content).await.map_err(|e| SettingsError::Storage(e.to_string()))
+    }
+}
+
+/// Path of the settings file in the app data directory
+fn settings_file(app_handle: &AppHandle) -> Result<PathBuf, SettingsError> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(SETTINGS_FILE))
+        .map_err(|e| SettingsError::Storage(format!("Failed to get app data dir: {}", e)))
+}
+
+/// Writes the configurations currently in use to the settings file; called after each change
+pub async fn save_settings(app_handle: &AppHandle) -> Result<(), SettingsError> {
+    let file = settings_file(app_handle)?;
+    let _guard = SETTINGS_LOCK.lock().await;
+    Settings::current().save(&file).await
+}
+
+/// Applies the saved settings at startup, before any folder is indexed or watched
+pub async fn restore_settings(app_handle: &AppHandle) {
+    let settings = match settings_file(app_handle) {
+        Ok(file) => Settings::load(&file).await,
+        Err(e) => Err(e),
+    };
+    match settings {
+        Ok(settings) => {
+            info!("Restoring saved settings");
+            settings.apply();
+        }
+        Err(e) => error!("Failed to load settings, using defaults: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_settings_round_trip() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("nested").join(SETTINGS_FILE);
+
+        // Nothing saved yet
+        assert_eq!(Settings::load(&file).await.unwrap(), Settings::default());
+
+        let mut settings = Settings::default();
+        settings.ocr.enabled = true;
+        settings.cross_lingual.index_english = false;
+        settings.watcher.debounce_ms = 2_000;
+        settings.save(&file).await.unwrap();
+        assert_eq!(Settings::load(&file).await.unwrap(), settings);
+
+        // Sections missing from the file keep their defaults
+        tokio::fs::write(&file, r#"{"cross_lingual": {"index_english": false}}"#).await.unwrap();
+        let loaded = Settings::load(&file).await.unwrap();
+        assert!(!loaded.cross_lingual.index_english);
+        assert_eq!(loaded.chunking, ChunkingConfig::default());
+        assert_eq!(loaded.watcher, WatcherConfig::default());
+
+        tokio::fs::write(&file, "not json").await.unwrap();
+        assert!(Settings::load(&file).await.is_err());
+    }
+}
@@ -1,43 +1,195 @@
 // src-tauri/src/watcher.rs
 
 use crate::commands::search_commands::{add_file_to_index, remove_file_from_index};
+use crate::core::indexer::{index_file, is_excluded_path, is_walked_entry, remove_file, remove_folder};
 use crate::db::{
-    connect_db, delete_document, open_or_create_amharic_text_table, upsert_amharic_document,
-    DbError,
+    connect_db, list_indexed_files, move_documents, open_or_create_amharic_text_table,
+    open_or_create_image_table, open_or_create_text_table, DbError, IndexedFile,
 };
-use crate::embedder::embed_text;
-use crate::extractor::{calculate_hash, extract_text, SUPPORTED_TEXT_EXTENSIONS};
+use crate::extractor::{calculate_file_hash, get_content_type, ContentType, ARCHIVE_MEMBER_SEPARATOR};
+use futures::future::join_all;
 use lancedb::Table;
-use log::{error, info, warn};
-use notify::event::{CreateKind, DataChange, ModifyKind, RenameMode};
+use log::{debug, error, info, warn};
+use notify::event::{CreateKind, DataChange, ModifyKind, RemoveKind, RenameMode};
 use notify::{
     Event, EventKind, RecommendedWatcher, RecursiveMode, Result as NotifyResult,
     Watcher as NotifyWatcher,
 };
-use serde::Serialize;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::metadata;
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::sync::Mutex;
-use std::time::Instant;
-use tauri::{AppHandle, Emitter};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
 use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use walkdir::{DirEntry, WalkDir};
 
-// Used in tests for timeouts
-#[cfg(test)]
-use crate::db::TestDb;
-#[cfg(test)]
-use std::io::Write;
-#[cfg(test)]
-use std::time::Duration;
+// Active watchers, keyed by the canonical path of the root they watch
+static WATCHERS: Lazy<Mutex<HashMap<String, WatchedRoot>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Serializes read-modify-write cycles of the indexed roots file
+static INDEXED_ROOTS_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// File in the app data directory listing the roots restored at startup
+const INDEXED_ROOTS_FILE: &str = "indexed_roots.json";
+
+struct WatchedRoot {
+    _watcher: RootWatcher,
+    backend: WatcherBackend,
+    shutdown_sender: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+    stats: Arc<RootStats>,
+}
+
+/// The source of a root's events; dropping it stops them
+pub enum RootWatcher {
+    Native(RecommendedWatcher),
+    Polling(PollingWatcher),
+}
+
+/// Watches a root by comparing snapshots of its files every interval
+pub struct PollingWatcher {
+    task: JoinHandle<()>,
+}
+
+impl Drop for PollingWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Modification time and size of a file, enough to notice most changes without reading it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    size: u64,
+}
+
+/// The files under a root as seen by one poll
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    files: HashMap<PathBuf, FileStamp>,
+    /// Paths the poll could not read; their files are neither listed nor gone
+    unreadable: Vec<PathBuf>,
+}
+
+impl Snapshot {
+    /// Records the stamp of every file under `root`, skipping the folders indexing skips.
+    /// Only metadata is read, so even large trees are cheap to scan.
+    pub fn scan(root: &Path) -> Self {
+        let mut files = HashMap::new();
+        let unreadable = walk_root(root, |path, meta| {
+            files.insert(path, FileStamp { modified: meta.modified().ok(), size: meta.len() });
+        });
+        Snapshot { files, unreadable }
+    }
+
+    /// Keeps the stamps `previous` has under the paths this poll could not read, so their
+    /// files are compared again once they can be read instead of showing up as new
+    fn keep_unreadable(&mut self, previous: &Snapshot) {
+        for (path, stamp) in &previous.files {
+            if is_under_any(path, &self.unreadable) {
+                self.files.entry(path.clone()).or_insert(*stamp);
+            }
+        }
+    }
 
-// Global watcher state
-static WATCHER_STATE: Mutex<Option<WatcherState>> = Mutex::new(None);
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
 
-struct WatcherState {
-    _watcher: RecommendedWatcher,
-    shutdown_sender: Option<Sender<()>>,
+    /// The events that turn this snapshot into `next`, in path order. Renames show up as
+    /// a removal and a creation, so polled roots re-index renamed files.
+    pub fn changes(&self, next: &Snapshot) -> Vec<Event> {
+        let mut changes: Vec<(&PathBuf, EventKind)> = next
+            .files
+            .iter()
+            .filter_map(|(path, stamp)| match self.files.get(path) {
+                None => Some((path, EventKind::Create(CreateKind::File))),
+                Some(previous) if previous != stamp => {
+                    Some((path, EventKind::Modify(ModifyKind::Data(DataChange::Any))))
+                }
+                Some(_) => None,
+            })
+            .chain(
+                self.files
+                    .keys()
+                    .filter(|path| !next.files.contains_key(*path) && !is_under_any(path, &next.unreadable))
+                    .map(|path| (path, EventKind::Remove(RemoveKind::File))),
+            )
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(b.0));
+        changes.into_iter().map(|(path, kind)| Event::new(kind).add_path(path.clone())).collect()
+    }
+}
+
+/// Scans a root every `interval` and sends the differences between consecutive snapshots
+/// as notify events, until the receiver is dropped
+async fn poll_root(root: PathBuf, interval: Duration, tx: mpsc::UnboundedSender<NotifyResult<Event>>) {
+    let scan = |root: PathBuf| async move {
+        tokio::task::spawn_blocking(move || root.is_dir().then(|| Snapshot::scan(&root))).await.ok().flatten()
+    };
+    let mut snapshot = scan(root.clone()).await.unwrap_or_default();
+    debug!("Polling {} every {:?} ({} files)", root.display(), interval, snapshot.len());
+
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    ticker.tick().await; // The first tick completes immediately
+    loop {
+        ticker.tick().await;
+        // An unmounted share must not look like every file was deleted: keep the last
+        // snapshot until the root is back
+        let Some(mut next) = scan(root.clone()).await else {
+            if tx.send(Err(notify::Error::path_not_found().add_path(root.clone()))).is_err() {
+                return;
+            }
+            continue;
+        };
+        for event in snapshot.changes(&next) {
+            if tx.send(Ok(event)).is_err() {
+                return;
+            }
+        }
+        next.keep_unreadable(&snapshot);
+        snapshot = next;
+    }
+}
+
+/// Event counters of one watched root, updated by its event loop
+#[derive(Debug, Default)]
+pub struct RootStats {
+    events_processed: AtomicU64,
+    /// Unix time in seconds of the last event, 0 before the first one
+    last_event_time: AtomicU64,
+}
+
+impl RootStats {
+    /// Counts one file system event received at `timestamp`
+    pub fn record_event(&self, timestamp: u64) {
+        self.events_processed.fetch_add(1, Ordering::Relaxed);
+        self.last_event_time.store(timestamp, Ordering::Relaxed);
+    }
+
+    pub fn events_processed(&self) -> u64 {
+        self.events_processed.load(Ordering::Relaxed)
+    }
+
+    pub fn last_event_time(&self) -> Option<u64> {
+        match self.last_event_time.load(Ordering::Relaxed) {
+            0 => None,
+            timestamp => Some(timestamp),
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -48,55 +200,391 @@ pub enum WatcherError {
     PathWatchFailed(String, notify::Error),
     #[error("Database error: {0}")]
     DatabaseError(#[from] DbError),
-    #[error("Watcher already running")]
-    AlreadyRunning,
-    #[error("Watcher not running")]
-    NotRunning,
+    #[error("'{0}' is already being watched")]
+    AlreadyRunning(String),
+    #[error("'{0}' is not being watched")]
+    NotRunning(String),
+    #[error("'{0}' overlaps the watched root '{1}'")]
+    OverlappingRoot(String, String),
+    #[error("Invalid root '{0}': {1}")]
+    InvalidRoot(String, String),
+    #[error("Invalid watcher configuration: {0}")]
+    InvalidConfig(String),
+    #[error("Failed to store indexed roots: {0}")]
+    Storage(String),
+}
+
+/// How changes under a root are detected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatcherBackend {
+    /// Operating system notifications (inotify, FSEvents, ReadDirectoryChangesW)
+    #[default]
+    Native,
+    /// Rescans the root every `interval_ms`, for network shares and FUSE filesystems
+    /// that do not deliver notifications
+    Polling { interval_ms: u64 },
+}
+
+impl WatcherBackend {
+    pub fn validate(&self) -> Result<(), WatcherError> {
+        match self {
+            WatcherBackend::Native => Ok(()),
+            WatcherBackend::Polling { interval_ms } if !(100..=3_600_000).contains(interval_ms) => Err(
+                WatcherError::InvalidConfig("polling interval_ms must be between 100 and 3600000".to_string()),
+            ),
+            WatcherBackend::Polling { .. } => Ok(()),
+        }
+    }
+}
+
+/// A folder that is indexed and kept up to date by a watcher
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedRoot {
+    pub path: String,
+    #[serde(default)]
+    pub backend: WatcherBackend,
+}
+
+/// The indexed roots, persisted in the app data directory and watched again at startup
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedRootsConfig {
+    pub roots: Vec<IndexedRoot>,
+}
+
+impl IndexedRootsConfig {
+    /// Reads the configuration; a missing file means no roots yet
+    pub async fn load(file: &Path) -> Result<Self, WatcherError> {
+        match tokio::fs::read_to_string(file).await {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| WatcherError::Storage(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(IndexedRootsConfig::default()),
+            Err(e) => Err(WatcherError::Storage(e.to_string())),
+        }
+    }
+
+    pub async fn save(&self, file: &Path) -> Result<(), WatcherError> {
+        if let Some(parent) = file.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| WatcherError::Storage(e.to_string()))?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(|e| WatcherError::Storage(e.to_string()))?;
+        tokio::fs::write(file, content).await.map_err(|e| WatcherError::Storage(e.to_string()))
+    }
+
+    /// Adds a root, or changes the backend of a listed one; returns whether anything changed
+    pub fn add(&mut self, path: &str, backend: WatcherBackend) -> bool {
+        match self.roots.iter_mut().find(|root| root.path == path) {
+            Some(root) if root.backend == backend => false,
+            Some(root) => {
+                root.backend = backend;
+                true
+            }
+            None => {
+                self.roots.push(IndexedRoot { path: path.to_string(), backend });
+                true
+            }
+        }
+    }
+
+    /// Removes a root; returns whether it was listed
+    pub fn remove(&mut self, path: &str) -> bool {
+        let before = self.roots.len();
+        self.roots.retain(|root| root.path != path);
+        self.roots.len() != before
+    }
+
+    /// Lists a folder that was just indexed as a root, unless a listed root already contains
+    /// it. Listed roots inside the folder are replaced by it, since roots must not overlap.
+    /// Returns the replaced roots, or `None` when the folder was already covered.
+    pub fn add_indexed_folder(&mut self, path: &str) -> Option<Vec<String>> {
+        if self.roots.iter().any(|root| Path::new(path).starts_with(&root.path)) {
+            return None;
+        }
+        let (nested, kept): (Vec<IndexedRoot>, Vec<IndexedRoot>) =
+            std::mem::take(&mut self.roots).into_iter().partition(|root| Path::new(&root.path).starts_with(path));
+        self.roots = kept;
+        self.roots.push(IndexedRoot { path: path.to_string(), backend: WatcherBackend::default() });
+        Some(nested.into_iter().map(|root| root.path).collect())
+    }
+}
+
+/// How file system events are debounced before they reach the indexer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatcherConfig {
+    /// A path is processed once it has had no events for this long, so the bursts of
+    /// events editors and `git checkout` produce are applied once
+    pub debounce_ms: u64,
+    /// Number of changed files handed to the indexer at a time
+    pub batch_size: usize,
+}
+
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        WatcherConfig { debounce_ms: 500, batch_size: 16 }
+    }
+}
+
+impl WatcherConfig {
+    /// Checks the configuration for values the event loop cannot work with
+    pub fn validate(&self) -> Result<(), WatcherError> {
+        if self.debounce_ms > 60_000 {
+            return Err(WatcherError::InvalidConfig("debounce_ms must be at most 60000".to_string()));
+        }
+        if self.batch_size == 0 {
+            return Err(WatcherError::InvalidConfig("batch_size must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+
+    fn quiet_period(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms)
+    }
+}
+
+// Active watcher configuration, read when a watcher starts
+static WATCHER_CONFIG: Lazy<RwLock<WatcherConfig>> = Lazy::new(|| RwLock::new(WatcherConfig::default()));
+
+/// Get the active watcher configuration
+pub fn get_watcher_config() -> WatcherConfig {
+    WATCHER_CONFIG.read().unwrap().clone()
+}
+
+/// Replace the active watcher configuration
+pub fn set_watcher_config(config: WatcherConfig) -> Result<(), WatcherError> {
+    config.validate()?;
+    info!("Updating watcher configuration: {:?}", config);
+    *WATCHER_CONFIG.write().unwrap() = config;
+    Ok(())
 }
 
 #[derive(Debug, Serialize, Clone)]
 pub struct FileWatcherEvent {
-    pub event_type: String, // "created", "modified", "deleted"
+    pub event_type: String, // "created", "modified", "deleted", "renamed"
     pub file_path: String,
     pub timestamp: u64,
     pub success: bool,
     pub message: String,
 }
 
+/// Status of one watched root
+#[derive(Debug, Serialize)]
+pub struct RootStatus {
+    pub path: String,
+    pub backend: WatcherBackend,
+    pub is_running: bool,
+    pub events_processed: u64,
+    pub last_event_time: Option<u64>,
+}
+
+/// Status of all watched roots; the top-level counters are totals over the roots
 #[derive(Debug, Serialize)]
 pub struct WatcherStatus {
     pub is_running: bool,
-    pub watched_path: Option<String>,
     pub events_processed: u64,
     pub last_event_time: Option<u64>,
+    pub roots: Vec<RootStatus>,
+}
+
+/// The tables the watcher keeps up to date
+pub struct WatcherTables {
+    pub text_table: Table,
+    pub amharic_text_table: Table,
+    pub image_table: Table,
+}
+
+/// What a file system event means for one file in the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileAction {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl FileAction {
+    /// Name used in the `file-watcher-event` sent to the frontend
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            FileAction::Created => "created",
+            FileAction::Modified => "modified",
+            FileAction::Deleted => "deleted",
+        }
+    }
+
+    /// The net effect of this action followed by `next` on the same path
+    pub fn then(self, next: FileAction) -> FileAction {
+        match (self, next) {
+            (_, FileAction::Deleted) => FileAction::Deleted,
+            // Deleted and written again, e.g. an editor replacing the file: re-index it
+            (FileAction::Deleted, _) => FileAction::Modified,
+            (FileAction::Created, _) => FileAction::Created,
+            (FileAction::Modified, _) => FileAction::Modified,
+        }
+    }
+}
+
+/// Coalesces file actions per path until the path has been quiet for the debounce period
+pub struct EventDebouncer {
+    quiet_period: Duration,
+    pending: HashMap<PathBuf, (FileAction, Instant)>,
+}
+
+impl EventDebouncer {
+    pub fn new(quiet_period: Duration) -> Self {
+        EventDebouncer { quiet_period, pending: HashMap::new() }
+    }
+
+    /// Records an action, merging it with the action already pending for the path
+    pub fn push(&mut self, path: PathBuf, action: FileAction, now: Instant) {
+        self.pending
+            .entry(path)
+            .and_modify(|(pending, last_seen)| {
+                *pending = pending.then(action);
+                *last_seen = now;
+            })
+            .or_insert((action, now));
+    }
+
+    /// When the next pending path becomes quiet, if any path is pending
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().map(|(_, last_seen)| *last_seen + self.quiet_period).min()
+    }
+
+    /// Removes and returns the paths that have been quiet for the debounce period, in path order
+    pub fn take_ready(&mut self, now: Instant) -> Vec<(PathBuf, FileAction)> {
+        let quiet_period = self.quiet_period;
+        let ready: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, (_, last_seen))| now.duration_since(*last_seen) >= quiet_period)
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut changes: Vec<(PathBuf, FileAction)> = ready
+            .into_iter()
+            .filter_map(|path| self.pending.remove(&path).map(|(action, _)| (path, action)))
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes
+    }
+
+    /// Removes and returns every pending path, e.g. when the watcher stops
+    pub fn take_all(&mut self) -> Vec<(PathBuf, FileAction)> {
+        let mut changes: Vec<(PathBuf, FileAction)> =
+            self.pending.drain().map(|(path, (action, _))| (path, action)).collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Moves the actions pending for a renamed path, and for everything under it, to the
+    /// new path. Whatever was pending for the new path is superseded by what moved there.
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        self.pending.retain(|path, _| !path.starts_with(to));
+        let moved: Vec<PathBuf> = self.pending.keys().filter(|path| path.starts_with(from)).cloned().collect();
+        for path in moved {
+            let Some(entry) = self.pending.remove(&path) else { continue };
+            let new_path = match path.strip_prefix(from) {
+                Ok(rest) if !rest.as_os_str().is_empty() => to.join(rest),
+                _ => to.to_path_buf(),
+            };
+            self.pending.insert(new_path, entry);
+        }
+    }
+}
+
+/// How long the first half of a rename waits for its second half. A `From` left unpaired
+/// was moved out of the watched root; a `To` without a `From` was moved into it.
+const RENAME_PAIR_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The part a notify event plays in a rename
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenamePart {
+    /// Both names in one event (FSEvents, some inotify setups)
+    Both(PathBuf, PathBuf),
+    /// The old name; the new name follows in a `To` event with the same tracker
+    From(PathBuf, Option<usize>),
+    To(PathBuf, Option<usize>),
+}
+
+/// Returns the rename part of a rename event, or `None` for other events and for renames
+/// that do not say which name is which
+pub fn rename_part(event: &Event) -> Option<RenamePart> {
+    let EventKind::Modify(ModifyKind::Name(mode)) = event.kind else {
+        return None;
+    };
+    match (mode, event.paths.as_slice()) {
+        (RenameMode::Both, [from, to]) => Some(RenamePart::Both(from.clone(), to.clone())),
+        (RenameMode::From, [from]) => Some(RenamePart::From(from.clone(), event.tracker())),
+        (RenameMode::To, [to]) => Some(RenamePart::To(to.clone(), event.tracker())),
+        _ => None,
+    }
+}
+
+/// Pairs renames reported as separate `From` and `To` events
+#[derive(Default)]
+pub struct RenamePairer {
+    pending: Vec<(PathBuf, Option<usize>, Instant)>,
 }
 
-/// Sets up a file system watcher for the given path.
+impl RenamePairer {
+    pub fn push_from(&mut self, path: PathBuf, tracker: Option<usize>, now: Instant) {
+        self.pending.push((path, tracker, now));
+    }
+
+    /// Returns the old name for a `To` event: the `From` with the same tracker, or the
+    /// oldest untracked one on backends that do not report trackers
+    pub fn match_to(&mut self, tracker: Option<usize>) -> Option<PathBuf> {
+        let index = self.pending.iter().position(|(_, from_tracker, _)| *from_tracker == tracker)?;
+        Some(self.pending.remove(index).0)
+    }
+
+    /// When the oldest unpaired `From` times out, if there is one
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.iter().map(|(_, _, seen)| *seen + RENAME_PAIR_TIMEOUT).min()
+    }
+
+    /// Removes and returns the old names that waited longer than the pairing timeout
+    pub fn take_expired(&mut self, now: Instant) -> Vec<PathBuf> {
+        let (expired, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|(_, _, seen)| now.duration_since(*seen) >= RENAME_PAIR_TIMEOUT);
+        self.pending = waiting;
+        expired.into_iter().map(|(path, _, _)| path).collect()
+    }
+
+    /// Removes and returns every unpaired old name, e.g. when the watcher stops
+    pub fn take_all(&mut self) -> Vec<PathBuf> {
+        self.pending.drain(..).map(|(path, _, _)| path).collect()
+    }
+}
+
+/// Sets up a file system watcher for the given path with the given backend.
 ///
-/// Returns the watcher instance and a receiver channel for events.
-pub async fn setup_watcher(
+/// Returns the watcher instance and a receiver for its events. The watcher stops
+/// sending (and the receiver yields `None`) once the watcher is dropped. The polling
+/// backend runs on the Tokio runtime, so this must be called from within it.
+pub fn setup_watcher(
     path_to_watch: &str,
-    _app_handle: AppHandle,
-) -> Result<
-    (
-        RecommendedWatcher,
-        Receiver<NotifyResult<Event>>,
-        Sender<()>,
-    ),
-    WatcherError,
-> {
+    backend: WatcherBackend,
+) -> Result<(RootWatcher, mpsc::UnboundedReceiver<NotifyResult<Event>>), WatcherError> {
     let path = Path::new(path_to_watch);
-    info!("Setting up file watcher for path: {:?}", path);
+    info!("Setting up {:?} file watcher for path: {:?}", backend, path);
 
-    // Create a channel to receive events
-    let (tx, rx) = channel();
-    let (shutdown_tx, _shutdown_rx) = channel();
+    // Events are sent from notify's own thread (or the polling task) and drained by the
+    // async event loop
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    if let WatcherBackend::Polling { interval_ms } = backend {
+        let task = tokio::spawn(poll_root(path.to_path_buf(), Duration::from_millis(interval_ms), tx));
+        return Ok((RootWatcher::Polling(PollingWatcher { task }), rx));
+    }
 
     // Create a file system watcher instance.
     let mut watcher = RecommendedWatcher::new(
         move |res: NotifyResult<Event>| {
             if let Err(e) = tx.send(res) {
-                error!("Failed to send watcher event through channel: {}", e);
+                debug!("Dropping watcher event, the event loop has stopped: {}", e);
             }
         },
         notify::Config::default(),
@@ -110,526 +598,850 @@ pub async fn setup_watcher(
 
     info!("Successfully watching path: {:?}", path);
 
-    Ok((watcher, rx, shutdown_tx))
+    Ok((RootWatcher::Native(watcher), rx))
+}
+
+/// Maps a notify event to the actions to apply to each of its paths. Creations, content
+/// changes and renames re-index the paths that still exist and remove the ones that are gone.
+/// A folder that is created or moved in queues every file inside it, since its files may
+/// have arrived before the watch covered the folder.
+pub fn classify_event(event: &Event) -> Vec<(PathBuf, FileAction)> {
+    let present_action = match event.kind {
+        EventKind::Remove(_) => {
+            return event.paths.iter().map(|path| (path.clone(), FileAction::Deleted)).collect();
+        }
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)) => FileAction::Created,
+        EventKind::Modify(ModifyKind::Data(_)) | EventKind::Modify(ModifyKind::Any) => FileAction::Modified,
+        _ => return Vec::new(),
+    };
+
+    event
+        .paths
+        .iter()
+        .flat_map(|path| {
+            if path.is_file() {
+                vec![(path.clone(), present_action)]
+            } else if !path.exists() {
+                vec![(path.clone(), FileAction::Deleted)]
+            } else if present_action == FileAction::Created && !is_excluded_path(path, true) {
+                folder_files(path).into_iter().map(|file| (file, FileAction::Created)).collect()
+            } else {
+                Vec::new() // Content changes of a folder are covered by the events of its files
+            }
+        })
+        .collect()
 }
 
-/// Processes file system events received from the watcher channel.
+/// Lists the files under a folder that a walk of the root would reach
+fn folder_files(folder: &Path) -> Vec<PathBuf> {
+    WalkDir::new(folder)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(is_walked_entry)
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(DirEntry::into_path)
+        .collect()
+}
+
+/// Drains file system events until the watcher is dropped or a shutdown signal arrives.
+/// Events are coalesced per path and applied once the path has been quiet for the
+/// configured debounce period, in batches; every change is reported to the frontend.
+/// Changes still pending when the loop stops are applied before it returns.
+///
+/// With `reconcile` set, the root is first compared with the index and the differences
+/// are queued like events; events arriving meanwhile wait in the channel.
 pub async fn process_events(
-    rx: Receiver<NotifyResult<Event>>,
-    table: Arc<Table>,
+    mut rx: mpsc::UnboundedReceiver<NotifyResult<Event>>,
+    tables: Arc<WatcherTables>,
     app_handle: AppHandle,
-    shutdown_rx: Receiver<()>,
+    stats: Arc<RootStats>,
+    reconcile: Option<PathBuf>,
+    mut shutdown_rx: oneshot::Receiver<()>,
 ) {
-    info!("Starting event processing loop...");
-    let mut events_processed = 0u64;
+    let config = get_watcher_config();
+    info!("Starting event processing loop ({:?})...", config);
+    let mut debouncer = EventDebouncer::new(config.quiet_period());
+    let mut renames = RenamePairer::default();
+
+    if let Some(root) = reconcile {
+        match reconcile_root(&root, &tables).await {
+            Ok((changes, summary)) => {
+                info!("Reconciled {}: {:?}", root.display(), summary);
+                let now = Instant::now();
+                for (path, action) in changes {
+                    debouncer.push(path, action, now);
+                }
+                if let Err(e) = app_handle.emit("watcher-reconciliation", &summary) {
+                    error!("Failed to emit reconciliation summary: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to reconcile {}: {}", root.display(), e),
+        }
+    }
 
     loop {
-        // Check for shutdown signal first
-        if shutdown_rx.try_recv().is_ok() {
-            info!("Received shutdown signal, stopping event processing");
-            break;
-        }
-
-        // Try to receive an event without blocking indefinitely
-        match rx.try_recv() {
-            Ok(result) => {
-                match result {
-                    Ok(event) => {
-                        events_processed += 1;
-
-                        // We only care about events with valid paths
-                        if event.paths.is_empty() {
-                            continue;
-                        }
-
-                        // Detect action based on event kind
-                        let (action, paths_to_check) = match event.kind {
-                            // Files created, data modified, or renamed TO this path -> UPSERT
-                            EventKind::Create(CreateKind::File)
-                            | EventKind::Modify(ModifyKind::Data(DataChange::Content))
-                            | EventKind::Modify(ModifyKind::Name(RenameMode::To))
-                            | EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
-                                ("Upsert", event.paths)
-                            }
-
-                            // Files removed or renamed FROM this path -> DELETE
-                            EventKind::Remove(_)
-                            | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
-                                ("Delete", event.paths)
-                            }
-
-                            // Other events we don't currently handle
-                            _ => {
-                                warn!("Ignoring event kind: {:?}", event.kind);
-                                continue;
-                            }
-                        };
-
-                        info!(
-                            "Processing {} event with {} paths",
-                            action,
-                            paths_to_check.len()
-                        );
-
-                        // Process each path from the event
-                        for path_buf in paths_to_check {
-                            let mut success = false;
-                            let mut message = String::new();
-
-                            // Update the filename index for all files, regardless of content type
-                            if action == "Upsert" {
-                                let path_clone = path_buf.clone();
-                                tokio::spawn(async move {
-                                    match metadata(&path_clone) {
-                                        Ok(meta) => {
-                                            let last_modified = meta
-                                                .modified()
-                                                .map(|time| {
-                                                    time.duration_since(std::time::UNIX_EPOCH)
-                                                        .unwrap_or_default()
-                                                        .as_secs()
-                                                })
-                                                .unwrap_or(0);
-                                            let size = meta.len();
-                                            if let Some(path_str) = path_clone.to_str() {
-                                                match add_file_to_index(path_str.to_string(), last_modified, size).await {
-                                                Ok(_) => info!("Updated Tantivy index (add/update) for {}", path_clone.display()),
-                                                Err(e) => error!("Failed to update Tantivy index (add/update) for {}: {}", path_clone.display(), e),
-                                            }
-                                            } else {
-                                                error!(
-                                                    "Invalid path string for Tantivy add: {}",
-                                                    path_clone.display()
-                                                );
-                                            }
-                                        }
-                                        Err(e) => error!(
-                                            "Failed to get metadata for Tantivy add {}: {}",
-                                            path_clone.display(),
-                                            e
-                                        ),
-                                    }
-                                });
-                            } else if action == "Delete" {
-                                let path_clone = path_buf.clone();
-                                tokio::spawn(async move {
-                                    if let Some(path_str) = path_clone.to_str() {
-                                        match remove_file_from_index(path_str.to_string()).await {
-                                        Ok(_) => info!("Updated Tantivy index (remove) for {}", path_clone.display()),
-                                        Err(e) => error!("Failed to update Tantivy index (remove) for {}: {}", path_clone.display(), e),
-                                    }
-                                    } else {
-                                        error!(
-                                            "Invalid path string for Tantivy remove: {}",
-                                            path_clone.display()
-                                        );
-                                    }
-                                });
-                            }
-
-                            // Check if file is relevant for semantic indexing
-                            let is_relevant = match action {
-                                "Upsert" => is_relevant_file_for_upsert(&path_buf),
-                                "Delete" => is_relevant_file(&path_buf), // For deletes, we can't check if file exists
-                                _ => false,
-                            };
-
-                            if !is_relevant {
-                                info!(
-                                    "Skipping non-relevant file for semantic index: {}",
-                                    path_buf.display()
-                                );
-                                continue;
-                            }
-
-                            // Perform action based on event type for semantic search
-                            match action {
-                                "Upsert" => {
-                                    info!("Action [Upsert] detected for: {}", path_buf.display());
-                                    match process_file_upsert(&path_buf, &table).await {
-                                        Ok(_) => {
-                                            success = true;
-                                            message = format!(
-                                                "Successfully indexed {}",
-                                                path_buf.display()
-                                            );
-                                            info!(
-                                                "Successfully processed upsert for {}",
-                                                path_buf.display()
-                                            );
-                                        }
-                                        Err(e) => {
-                                            message = format!(
-                                                "Error indexing {}: {}",
-                                                path_buf.display(),
-                                                e
-                                            );
-                                            error!(
-                                                "Error processing upsert for {}: {}",
-                                                path_buf.display(),
-                                                e
-                                            );
-                                        }
-                                    }
-                                }
-                                "Delete" => {
-                                    info!("Action [Delete] detected for: {}", path_buf.display());
-                                    if let Some(path_str) = path_buf.to_str() {
-                                        match delete_document(&table, path_str).await {
-                                            Ok(_) => {
-                                                success = true;
-                                                message = format!(
-                                                    "Successfully removed {} from index",
-                                                    path_buf.display()
-                                                );
-                                                info!(
-                                                    "Successfully deleted DB entry for {}",
-                                                    path_buf.display()
-                                                );
-                                            }
-                                            Err(DbError::RecordNotFound(_)) => {
-                                                success = true; // Not an error if record doesn't exist
-                                                message = format!(
-                                                    "File {} was not in index",
-                                                    path_buf.display()
-                                                );
-                                                warn!("Attempted to delete non-existent DB entry for {}", path_buf.display());
-                                            }
-                                            Err(e) => {
-                                                message = format!(
-                                                    "Error removing {} from index: {}",
-                                                    path_buf.display(),
-                                                    e
-                                                );
-                                                error!(
-                                                    "Error deleting DB entry for {}: {}",
-                                                    path_buf.display(),
-                                                    e
-                                                );
-                                            }
-                                        }
-                                    } else {
-                                        message = format!(
-                                            "Invalid path encoding: {}",
-                                            path_buf.display()
-                                        );
-                                        error!(
-                                            "Invalid path string for deletion: {}",
-                                            path_buf.display()
-                                        );
-                                    }
-                                }
-                                _ => {
-                                    warn!("Unhandled action type: {}", action);
-                                }
-                            }
-
-                            // Emit event to frontend
-                            let event_type = match action {
-                                "Upsert" => {
-                                    if path_buf.exists() {
-                                        "created"
-                                    } else {
-                                        "modified"
-                                    }
-                                }
-                                "Delete" => "deleted",
-                                _ => "unknown",
-                            };
-
-                            let watcher_event = FileWatcherEvent {
-                                event_type: event_type.to_string(),
-                                file_path: path_buf.to_string_lossy().to_string(),
-                                timestamp: std::time::SystemTime::now()
-                                    .duration_since(std::time::UNIX_EPOCH)
-                                    .unwrap_or_default()
-                                    .as_secs(),
-                                success,
-                                message,
-                            };
-
-                            // Emit event to frontend
-                            if let Err(e) = app_handle.emit("file-watcher-event", &watcher_event) {
-                                error!("Failed to emit watcher event to frontend: {}", e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Watcher error: {:?}", e);
-                        if matches!(
-                            e,
-                            notify::Error {
-                                kind: notify::ErrorKind::PathNotFound,
-                                ..
-                            }
-                        ) {
-                            warn!("Watched path seems to have been removed.");
-                        }
-                    }
+        let deadline = [debouncer.next_deadline(), renames.next_deadline()].into_iter().flatten().min();
+        let result = tokio::select! {
+            _ = &mut shutdown_rx => {
+                info!("Received shutdown signal, stopping event processing");
+                break;
+            }
+            result = rx.recv() => match result {
+                Some(result) => result,
+                None => {
+                    info!("Channel closed, exiting event processing loop");
+                    break;
+                }
+            },
+            _ = tokio::time::sleep_until(tokio::time::Instant::from_std(deadline.unwrap_or_else(Instant::now))),
+                if deadline.is_some() => {
+                let now = Instant::now();
+                for from in renames.take_expired(now) {
+                    debouncer.push(from, FileAction::Deleted, now);
+                }
+                let changes = debouncer.take_ready(now);
+                apply_changes(changes, &tables, &app_handle, config.batch_size).await;
+                continue;
+            }
+        };
+
+        let event = match result {
+            Ok(event) => event,
+            Err(e) => {
+                error!("Watcher error: {:?}", e);
+                if matches!(e.kind, notify::ErrorKind::PathNotFound) {
+                    warn!("Watched path seems to have been removed.");
                 }
+                continue;
             }
-            Err(std::sync::mpsc::TryRecvError::Empty) => {
-                // No messages available yet, yield to other tasks briefly
-                tokio::task::yield_now().await;
+        };
+        stats.record_event(now_secs());
+
+        let now = Instant::now();
+        let renamed = match rename_part(&event) {
+            Some(RenamePart::Both(from, to)) => Some((from, to)),
+            Some(RenamePart::From(from, tracker)) => {
+                renames.push_from(from, tracker, now);
+                continue;
             }
-            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                // Channel is closed (all senders dropped)
-                info!("Channel closed, exiting event processing loop");
-                break;
+            // Without a matching `From` the file was moved in from outside the root
+            Some(RenamePart::To(to, tracker)) => renames.match_to(tracker).map(|from| (from, to)),
+            None => None,
+        };
+        match renamed {
+            Some((from, to)) => apply_rename(&from, &to, &tables, &app_handle, &mut debouncer, now).await,
+            None => {
+                for (path, action) in classify_event(&event) {
+                    debouncer.push(path, action, now);
+                }
             }
         }
     }
 
+    let now = Instant::now();
+    for from in renames.take_all() {
+        debouncer.push(from, FileAction::Deleted, now);
+    }
+    if !debouncer.is_empty() {
+        apply_changes(debouncer.take_all(), &tables, &app_handle, config.batch_size).await;
+    }
     info!(
         "Event processing loop exited. Processed {} events",
-        events_processed
+        stats.events_processed()
     );
 }
 
-// Helper function to handle text extraction, embedding, and DB upsert for a file
-async fn process_file_upsert(path_buf: &Path, table: &Table) -> Result<(), DbError> {
-    // Extract content returns TextExtractionResult { text: String, language: DetectedLanguage }
-    let extraction_result = extract_text(path_buf)
-        .map_err(|e| DbError::Other(format!("Text extraction failed: {}", e)))?;
+/// Counts of a reconciliation pass, sent to the frontend as `watcher-reconciliation`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReconciliationSummary {
+    pub root: String,
+    pub created: usize,
+    pub modified: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+}
 
-    let trimmed_content = extraction_result.text.trim();
+/// Compares a root with what the index holds for it, to catch the changes made while no
+/// watcher was running
+async fn reconcile_root(
+    root: &Path,
+    tables: &WatcherTables,
+) -> Result<(Vec<(PathBuf, FileAction)>, ReconciliationSummary), String> {
+    let prefix = format!("{}{}", root.display(), std::path::MAIN_SEPARATOR);
+    let mut indexed: HashMap<PathBuf, IndexedFile> = HashMap::new();
+    // Images first: text rows written before they stored the file hash hold the hash of the OCR text
+    for table in [&tables.image_table, &tables.text_table, &tables.amharic_text_table] {
+        let files = list_indexed_files(table, &prefix).await.map_err(|e| e.to_string())?;
+        for (path, file) in files {
+            // Archive members are checked through their archive
+            let path = match path.split_once(ARCHIVE_MEMBER_SEPARATOR) {
+                Some((archive, _)) => archive.to_string(),
+                None => path,
+            };
+            indexed
+                .entry(PathBuf::from(path))
+                .and_modify(|stored| stored.indexed_at = stored.indexed_at.min(file.indexed_at))
+                .or_insert(file);
+        }
+    }
 
-    if trimmed_content.is_empty() {
-        warn!(
-            "Extracted empty or whitespace-only content for {}, skipping upsert.",
-            path_buf.display()
-        );
-        return Ok(());
+    let root = root.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let (on_disk, unreadable) = scan_root(&root);
+        let changes = diff_root(&on_disk, &unreadable, &indexed, |path, stored| {
+            calculate_file_hash(path).map_or(false, |hash| hash == stored.content_hash)
+        });
+        let count = |action: FileAction| changes.iter().filter(|(_, a)| *a == action).count();
+        let summary = ReconciliationSummary {
+            root: root.to_string_lossy().to_string(),
+            created: count(FileAction::Created),
+            modified: count(FileAction::Modified),
+            deleted: count(FileAction::Deleted),
+            unchanged: on_disk.len() - count(FileAction::Created) - count(FileAction::Modified),
+        };
+        (changes, summary)
+    })
+    .await
+    .map_err(|e| format!("Reconciliation task failed: {}", e))
+}
+
+/// Walks a root as `index_folder` does, passing every file it lists to `visit` with its
+/// metadata. Returns the paths that could not be read, e.g. a folder without permission or
+/// a share that dropped out: nothing is known about the files under them.
+fn walk_root(root: &Path, mut visit: impl FnMut(PathBuf, std::fs::Metadata)) -> Vec<PathBuf> {
+    let mut unreadable = Vec::new();
+    for entry in WalkDir::new(root).follow_links(false).into_iter().filter_entry(is_walked_entry) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("Failed to read under {}: {}", root.display(), e);
+                unreadable.push(e.path().unwrap_or(root).to_path_buf());
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match entry.metadata() {
+            Ok(metadata) => visit(entry.into_path(), metadata),
+            Err(_) => unreadable.push(entry.into_path()),
+        }
     }
+    unreadable
+}
 
-    // Hash the content
-    let hash = calculate_hash(trimmed_content);
-    info!(
-        "  -> Extracted text (lang: {:?}), Hash: {}",
-        extraction_result.language, hash
-    );
+/// Whether `path` is one of `folders` or lies under one of them
+fn is_under_any(path: &Path, folders: &[PathBuf]) -> bool {
+    folders.iter().any(|folder| path.starts_with(folder))
+}
 
-    // Convert the single string to a Vec<String> for embed_text
-    let content_vec = vec![trimmed_content.to_string()];
+/// Lists the files under a root the watcher would index, with their modification time in
+/// Unix seconds and their size in bytes, and the paths that could not be read
+fn scan_root(root: &Path) -> (HashMap<PathBuf, (i64, u64)>, Vec<PathBuf>) {
+    let mut files = HashMap::new();
+    let unreadable = walk_root(root, |path, metadata| {
+        if !is_relevant_file_for_upsert(&path) {
+            return;
+        }
+        let modified = metadata.modified().ok().and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok());
+        files.insert(path, (modified.unwrap_or_default().as_secs() as i64, metadata.len()));
+    });
+    (files, unreadable)
+}
 
-    // Use the new embedder function (now only uses Amharic model)
-    let embedding_vec = match embed_text(&content_vec, &extraction_result.language, false) {
-        Ok(vec) => vec,
-        Err(e) => {
-            error!(
-                "Embedding generation failed for {}: {}. Skipping upsert.",
-                path_buf.display(),
-                e
-            );
-            return Ok(());
+/// Works out the changes that bring the index in line with the disk: files the index
+/// does not have are created, files whose size differs from the indexed one or that were
+/// modified since they were indexed are modified (unless `same_content` finds their stored
+/// hash still matches), and indexed files no longer on disk are deleted, unless they are
+/// under a path the scan could not read. Changes are sorted by path.
+pub fn diff_root(
+    on_disk: &HashMap<PathBuf, (i64, u64)>,
+    unreadable: &[PathBuf],
+    indexed: &HashMap<PathBuf, IndexedFile>,
+    same_content: impl Fn(&Path, &IndexedFile) -> bool,
+) -> Vec<(PathBuf, FileAction)> {
+    let mut changes: Vec<(PathBuf, FileAction)> = on_disk
+        .iter()
+        .filter_map(|(path, (modified, size))| match indexed.get(path) {
+            None => Some((path.clone(), FileAction::Created)),
+            // A different size is a different file, whatever its modification time says
+            Some(stored) if stored.size.is_some_and(|stored_size| stored_size != *size) => {
+                Some((path.clone(), FileAction::Modified))
+            }
+            // Indexing times have second precision: a file written in the same second may be newer
+            Some(stored) if *modified >= stored.indexed_at && !same_content(path, stored) => {
+                Some((path.clone(), FileAction::Modified))
+            }
+            Some(_) => None,
+        })
+        .chain(
+            indexed
+                .keys()
+                .filter(|path| !on_disk.contains_key(*path) && !is_under_any(path, unreadable))
+                .map(|path| (path.clone(), FileAction::Deleted)),
+        )
+        .collect();
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+    changes
+}
+
+/// Applies coalesced changes in batches of `batch_size` and reports each to the frontend
+async fn apply_changes(
+    changes: Vec<(PathBuf, FileAction)>,
+    tables: &WatcherTables,
+    app_handle: &AppHandle,
+    batch_size: usize,
+) {
+    // The file may have changed again since its last event: act on its current state
+    let changes: Vec<(PathBuf, FileAction)> = changes
+        .into_iter()
+        .map(|(path, action)| {
+            let action = settle_action(&path, action);
+            (path, action)
+        })
+        .filter(|(path, action)| {
+            update_filename_index(path, *action);
+            let relevant = is_relevant_file_for_action(path, *action);
+            if !relevant {
+                debug!("Skipping non-relevant file for semantic index: {}", path.display());
+            }
+            relevant
+        })
+        .collect();
+    if changes.is_empty() {
+        return;
+    }
+    info!("Applying {} file changes", changes.len());
+
+    for batch in changes.chunks(batch_size.max(1)) {
+        let results = join_all(batch.iter().map(|(path, action)| apply_change(path, *action, tables))).await;
+        for watcher_event in results {
+            if let Err(e) = app_handle.emit("file-watcher-event", &watcher_event) {
+                error!("Failed to emit watcher event to frontend: {}", e);
+            }
         }
+    }
+}
+
+/// Applies a rename or move. Pending changes follow the file, and the rows stored for it
+/// are pointed at the new path instead of being embedded again.
+async fn apply_rename(
+    from: &Path,
+    to: &Path,
+    tables: &WatcherTables,
+    app_handle: &AppHandle,
+    debouncer: &mut EventDebouncer,
+    now: Instant,
+) {
+    debouncer.rename(from, to);
+    if !can_move_in_place(from, to) {
+        // e.g. an editor renaming its temporary file over the original, or a new extension
+        debug!("Re-indexing rename of {} to {}", from.display(), to.display());
+        debouncer.push(from.to_path_buf(), FileAction::Deleted, now);
+        debouncer.push(to.to_path_buf(), FileAction::Created, now);
+        return;
+    }
+
+    info!("Moved: {} -> {}", from.display(), to.display());
+    update_filename_index_for_move(from, to);
+    let (from_str, to_str) = (from.to_string_lossy(), to.to_string_lossy());
+    let (success, message) = match move_rows(&from_str, &to_str, tables).await {
+        Ok(()) => (true, format!("Moved {} to {} in index", from_str, to_str)),
+        Err(e) => (false, format!("Error moving {} to {} in index: {}", from_str, to_str, e)),
     };
+    if success {
+        info!("{}", message);
+    } else {
+        error!("{}", message);
+    }
 
-    if embedding_vec.is_empty() {
-        warn!(
-            "No embeddings generated for {}, likely due to content issues. Skipping upsert.",
-            path_buf.display()
-        );
-        return Ok(());
+    let watcher_event = FileWatcherEvent {
+        event_type: "renamed".to_string(),
+        file_path: to_str.to_string(),
+        timestamp: now_secs(),
+        success,
+        message,
+    };
+    if let Err(e) = app_handle.emit("file-watcher-event", &watcher_event) {
+        error!("Failed to emit watcher event to frontend: {}", e);
     }
+}
 
-    info!(
-        "  -> Successfully generated {} embeddings (chunks)",
-        embedding_vec.len()
-    );
+/// Whether the rows stored for `from` are still right for `to`: always for folders, and
+/// for files as long as neither name is hidden and the content type stays the same
+fn can_move_in_place(from: &Path, to: &Path) -> bool {
+    if to.is_dir() {
+        return true;
+    }
+    !is_hidden(from) && !is_hidden(to) && get_content_type(from) == get_content_type(to)
+}
 
-    if let Some(path_str) = path_buf.to_str() {
-        upsert_amharic_document(table, path_str, &hash, &embedding_vec).await?;
-        Ok(())
-    } else {
-        error!(
-            "Invalid file path encoding for {}. Cannot upsert.",
-            path_buf.display()
-        );
-        Err(DbError::Other("Invalid file path encoding".to_string()))
+/// Rewrites the stored paths of a moved file or folder in every table
+async fn move_rows(from: &str, to: &str, tables: &WatcherTables) -> Result<(), DbError> {
+    move_documents(&tables.text_table, from, to).await?;
+    move_documents(&tables.amharic_text_table, from, to).await?;
+    move_documents(&tables.image_table, from, to).await
+}
+
+/// Moves the filename index entries of a renamed file, or of every file in a moved folder
+fn update_filename_index_for_move(from: &Path, to: &Path) {
+    if !to.is_dir() {
+        update_filename_index(from, FileAction::Deleted);
+        update_filename_index(to, FileAction::Created);
+        return;
+    }
+    let (from, to) = (from.to_path_buf(), to.to_path_buf());
+    tokio::task::spawn_blocking(move || {
+        for entry in WalkDir::new(&to).into_iter().filter_map(Result::ok) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            if let Ok(rest) = entry.path().strip_prefix(&to) {
+                update_filename_index(&from.join(rest), FileAction::Deleted);
+                update_filename_index(entry.path(), FileAction::Created);
+            }
+        }
+    });
+}
+
+/// Reconciles a coalesced action with what is on disk now: a file that is gone is
+/// deleted, one that exists again after a delete is re-indexed
+fn settle_action(path: &Path, action: FileAction) -> FileAction {
+    match (action, path.exists()) {
+        (FileAction::Deleted, true) if path.is_file() => FileAction::Modified,
+        (_, false) => FileAction::Deleted,
+        (action, _) => action,
     }
 }
 
-/// Checks if a path points to a relevant file for indexing.
-/// Uses the extensions defined in extractor.rs
-/// For delete events, we can't check if the file exists, so we only check the extension
-fn is_relevant_file(path: &PathBuf) -> bool {
-    // Check if the file name itself starts with a dot (hidden files)
-    let filename_is_hidden = path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .map_or(false, |name_str| name_str.starts_with('.'));
+/// Applies one change to the semantic index with the same pipeline `index_folder` uses,
+/// and describes the outcome for the frontend
+async fn apply_change(path: &Path, action: FileAction, tables: &WatcherTables) -> FileWatcherEvent {
+    info!("Action [{:?}] detected for: {}", action, path.display());
+    let result = match action {
+        FileAction::Created | FileAction::Modified => {
+            index_file(path, &tables.text_table, &tables.amharic_text_table, &tables.image_table).await
+        }
+        // Nothing is left to tell a deleted folder from a deleted file, but only folders have
+        // no content type; a folder moved out of the root arrives as one unpaired rename
+        FileAction::Deleted if !is_indexed_content_type(path) => {
+            remove_folder(path, &tables.text_table, &tables.amharic_text_table, &tables.image_table).await
+        }
+        FileAction::Deleted => {
+            remove_file(path, &tables.text_table, &tables.amharic_text_table, &tables.image_table).await
+        }
+    };
 
-    if filename_is_hidden {
-        return false;
+    let (success, message) = match (&result, action) {
+        (Ok(()), FileAction::Deleted) => (true, format!("Successfully removed {} from index", path.display())),
+        (Ok(()), _) => (true, format!("Successfully indexed {}", path.display())),
+        (Err(e), FileAction::Deleted) => (false, format!("Error removing {} from index: {}", path.display(), e)),
+        (Err(e), _) => (false, format!("Error indexing {}: {}", path.display(), e)),
+    };
+    if success {
+        info!("{}", message);
+    } else {
+        error!("{}", message);
     }
 
-    // Check extension using the supported extensions from extractor
-    let extension_check = path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map_or(false, |ext_str| {
-            let lower_ext = ext_str.to_lowercase();
-            SUPPORTED_TEXT_EXTENSIONS.contains(&lower_ext.as_str())
-        });
+    FileWatcherEvent {
+        event_type: action.event_type().to_string(),
+        file_path: path.to_string_lossy().to_string(),
+        timestamp: now_secs(),
+        success,
+        message,
+    }
+}
+
+/// Keeps the filename index in step with the file system, for every file whatever its type
+fn update_filename_index(path: &Path, action: FileAction) {
+    let Some(path_str) = path.to_str().map(str::to_string) else {
+        error!("Invalid path string for filename index: {}", path.display());
+        return;
+    };
+    let path = path.to_path_buf();
+    tokio::spawn(async move {
+        let result = match action {
+            FileAction::Deleted => remove_file_from_index(path_str).await,
+            FileAction::Created | FileAction::Modified => match metadata(&path) {
+                Ok(meta) => {
+                    let last_modified = meta
+                        .modified()
+                        .map(|time| {
+                            time.duration_since(std::time::UNIX_EPOCH)
+                                .unwrap_or_default()
+                                .as_secs()
+                        })
+                        .unwrap_or(0);
+                    add_file_to_index(path_str, last_modified, meta.len()).await
+                }
+                Err(e) => Err(format!("Failed to get metadata: {}", e)),
+            },
+        };
+        if let Err(e) = result {
+            error!("Failed to update filename index for {}: {}", path.display(), e);
+        }
+    });
+}
 
-    extension_check
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Returns true for hidden files (name starting with a dot)
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map_or(false, |name_str| name_str.starts_with('.'))
+}
+
+/// Returns true for every type the indexer handles: text, code, images and archives
+fn is_indexed_content_type(path: &Path) -> bool {
+    get_content_type(path) != ContentType::Unsupported
+}
+
+/// Checks if a path points to a relevant file for indexing.
+/// For delete events, we can't check if the file exists, so we only check the extension
+fn is_relevant_file(path: &Path) -> bool {
+    !is_hidden(path) && is_indexed_content_type(path)
 }
 
 /// Checks if a path points to a relevant file for indexing (for upsert operations).
 /// This version checks if the file actually exists and is a file.
-fn is_relevant_file_for_upsert(path: &PathBuf) -> bool {
-    // Check if the file name itself starts with a dot (hidden files)
-    let filename_is_hidden = path
-        .file_name()
-        .and_then(|name| name.to_str())
-        .map_or(false, |name_str| name_str.starts_with('.'));
+fn is_relevant_file_for_upsert(path: &Path) -> bool {
+    !is_hidden(path) && path.is_file() && is_indexed_content_type(path)
+}
 
-    if filename_is_hidden {
-        return false;
+fn is_relevant_file_for_action(path: &Path, action: FileAction) -> bool {
+    match action {
+        // A deleted path without a content type may have been a folder of indexed files
+        FileAction::Deleted => is_relevant_file(path) || (!is_hidden(path) && !is_indexed_content_type(path)),
+        FileAction::Created | FileAction::Modified => is_relevant_file_for_upsert(path),
     }
+}
 
-    // Check if it's a file
-    let is_file = path.is_file();
+/// Resolves a root to the canonical path it is watched under, checking it is a directory
+fn canonical_root(path: &str) -> Result<String, WatcherError> {
+    let canonical = Path::new(path)
+        .canonicalize()
+        .map_err(|e| WatcherError::InvalidRoot(path.to_string(), e.to_string()))?;
+    if !canonical.is_dir() {
+        return Err(WatcherError::InvalidRoot(path.to_string(), "not a directory".to_string()));
+    }
+    Ok(canonical.to_string_lossy().to_string())
+}
 
-    // Check extension using the supported extensions from extractor
-    let extension_check = path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map_or(false, |ext_str| {
-            let lower_ext = ext_str.to_lowercase();
-            SUPPORTED_TEXT_EXTENSIONS.contains(&lower_ext.as_str())
-        });
+/// Returns the watched root that contains or lies inside `root`, if any. Nested roots
+/// would see every event twice and index the files in between twice.
+fn overlapping_root<'a>(root: &str, watched: impl IntoIterator<Item = &'a String>) -> Option<String> {
+    let root = Path::new(root);
+    watched
+        .into_iter()
+        .find(|other| {
+            let other = Path::new(other.as_str());
+            root.starts_with(other) || other.starts_with(root)
+        })
+        .cloned()
+}
 
-    is_file && extension_check
+/// Path of the indexed roots file in the app data directory
+fn indexed_roots_file(app_handle: &AppHandle) -> Result<PathBuf, WatcherError> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(INDEXED_ROOTS_FILE))
+        .map_err(|e| WatcherError::Storage(format!("Failed to get app data dir: {}", e)))
 }
 
-/// Start watching a directory
-#[tauri::command]
-pub async fn start_watcher_command(path: String, app_handle: AppHandle) -> Result<String, String> {
-    info!("Starting file watcher for path: {}", path);
+fn emit_watcher_event(app_handle: &AppHandle, event_type: &str, file_path: &str, message: String) {
+    let event = FileWatcherEvent {
+        event_type: event_type.to_string(),
+        file_path: file_path.to_string(),
+        timestamp: now_secs(),
+        success: true,
+        message,
+    };
+    if let Err(e) = app_handle.emit("file-watcher-event", &event) {
+        error!("Failed to emit {} event: {}", event_type, e);
+    }
+}
 
-    // Check if watcher is already running
+/// Starts a watcher for one root next to the ones already running; returns the canonical
+/// path the root is watched under. With `reconcile` set, changes made while the root was
+/// not watched are picked up first.
+pub async fn start_root(
+    path: &str,
+    backend: WatcherBackend,
+    app_handle: &AppHandle,
+    reconcile: bool,
+) -> Result<String, WatcherError> {
+    backend.validate()?;
+    let root = canonical_root(path)?;
     {
-        let state = WATCHER_STATE.lock().unwrap();
-        if state.is_some() {
-            return Err("File watcher is already running. Stop it first.".to_string());
+        let watchers = WATCHERS.lock().unwrap();
+        if watchers.contains_key(&root) {
+            return Err(WatcherError::AlreadyRunning(root));
+        }
+        if let Some(other) = overlapping_root(&root, watchers.keys()) {
+            return Err(WatcherError::OverlappingRoot(root, other));
         }
     }
 
-    // Connect to DB and open table
-    let conn = connect_db()
-        .await
-        .map_err(|e| format!("Database connection failed: {}", e))?;
+    // Connect to DB and open the tables the indexer writes to
+    let conn = connect_db().await?;
+    let text_table = open_or_create_text_table(&conn).await?;
+    let amharic_text_table = open_or_create_amharic_text_table(&conn).await?;
+    let image_table = open_or_create_image_table(&conn).await?;
+    let tables = Arc::new(WatcherTables { text_table, amharic_text_table, image_table });
 
-    let table = open_or_create_amharic_text_table(&conn)
-        .await
-        .map_err(|e| format!("Table creation failed: {}", e))?;
-
-    // Setup the watcher
-    let (watcher, rx, shutdown_tx) = setup_watcher(&path, app_handle.clone())
-        .await
-        .map_err(|e| format!("Watcher setup failed: {}", e))?;
-
-    let table_arc = Arc::new(table);
+    let (watcher, rx) = setup_watcher(&root, backend)?;
 
     // Start event processing in background
-    let app_handle_clone = app_handle.clone();
-    let (shutdown_sender, shutdown_receiver) = channel();
-
-    tokio::spawn(async move {
-        process_events(rx, table_arc, app_handle_clone, shutdown_receiver).await;
-    });
+    let stats = Arc::new(RootStats::default());
+    let (shutdown_sender, shutdown_receiver) = oneshot::channel();
+    let reconcile = reconcile.then(|| PathBuf::from(&root));
+    let task = tokio::spawn(process_events(rx, tables, app_handle.clone(), stats.clone(), reconcile, shutdown_receiver));
 
-    // Store watcher state
     {
-        let mut state = WATCHER_STATE.lock().unwrap();
-        *state = Some(WatcherState {
-            _watcher: watcher,
-            shutdown_sender: Some(shutdown_sender),
-        });
+        let mut watchers = WATCHERS.lock().unwrap();
+        // Another root may have been added while the tables were being opened
+        let conflict = if watchers.contains_key(&root) {
+            Some(WatcherError::AlreadyRunning(root.clone()))
+        } else {
+            overlapping_root(&root, watchers.keys()).map(|other| WatcherError::OverlappingRoot(root.clone(), other))
+        };
+        if let Some(e) = conflict {
+            drop(watcher);
+            task.abort();
+            return Err(e);
+        }
+        watchers.insert(
+            root.clone(),
+            WatchedRoot { _watcher: watcher, backend, shutdown_sender: Some(shutdown_sender), task, stats },
+        );
     }
 
-    // Emit start event to frontend
-    let start_event = FileWatcherEvent {
-        event_type: "watcher-started".to_string(),
-        file_path: path.clone(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
-        success: true,
-        message: format!("File watcher started for {}", path),
-    };
+    info!("Watching root: {} ({:?})", root, backend);
+    emit_watcher_event(app_handle, "watcher-started", &root, format!("File watcher started for {}", root));
+    Ok(root)
+}
 
-    if let Err(e) = app_handle.emit("file-watcher-event", &start_event) {
-        error!("Failed to emit watcher start event: {}", e);
+/// Stops the watcher of one root, applying the changes it still has pending
+pub async fn stop_root(path: &str, app_handle: &AppHandle) -> Result<String, WatcherError> {
+    // A root that no longer exists cannot be canonicalized; fall back to the path as given
+    let root = canonical_root(path).unwrap_or_else(|_| path.to_string());
+    let watched_root = WATCHERS
+        .lock()
+        .unwrap()
+        .remove(&root)
+        .ok_or_else(|| WatcherError::NotRunning(root.clone()))?;
+    let WatchedRoot { _watcher: watcher, shutdown_sender, task, .. } = watched_root;
+
+    // Stop receiving file system events, then let the loop finish the change it is applying
+    drop(watcher);
+    if let Some(sender) = shutdown_sender {
+        if sender.send(()).is_err() {
+            debug!("Event loop of {} had already stopped", root);
+        }
+    }
+    if let Err(e) = task.await {
+        warn!("Event processing task of {} ended abnormally: {}", root, e);
     }
 
-    Ok(format!("File watcher started successfully for: {}", path))
+    info!("Stopped watching root: {}", root);
+    emit_watcher_event(app_handle, "watcher-stopped", &root, format!("File watcher stopped for {}", root));
+    Ok(root)
 }
 
-/// Stop the file watcher
-#[tauri::command]
-pub async fn stop_watcher_command(app_handle: AppHandle) -> Result<String, String> {
-    info!("Stopping file watcher");
-
-    let shutdown_sender = {
-        let mut state = WATCHER_STATE.lock().unwrap();
-        match state.take() {
-            Some(watcher_state) => watcher_state.shutdown_sender,
-            None => return Err("File watcher is not running".to_string()),
+/// Starts watchers for every root in the indexed roots configuration, reconciling each
+/// with the index first. Roots that cannot be watched (e.g. an unplugged drive) are
+/// logged and left in the configuration.
+pub async fn restore_watched_roots(app_handle: AppHandle) {
+    let config = match indexed_roots_file(&app_handle) {
+        Ok(file) => IndexedRootsConfig::load(&file).await,
+        Err(e) => Err(e),
+    };
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load indexed roots: {}", e);
+            return;
         }
     };
 
-    // Send shutdown signal if available
-    if let Some(sender) = shutdown_sender {
-        if let Err(e) = sender.send(()) {
-            warn!("Failed to send shutdown signal: {}", e);
+    info!("Restoring watchers for {} indexed roots", config.roots.len());
+    for root in &config.roots {
+        if let Err(e) = start_root(&root.path, root.backend, &app_handle, true).await {
+            warn!("Failed to restore watcher for {}: {}", root.path, e);
         }
     }
+}
 
-    // Emit stop event to frontend
-    let stop_event = FileWatcherEvent {
-        event_type: "watcher-stopped".to_string(),
-        file_path: String::new(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs(),
-        success: true,
-        message: "File watcher stopped".to_string(),
+/// Records a folder that was just indexed as an indexed root and starts watching it, so it
+/// is kept up to date and reconciled with the index at every startup. Returns the root.
+pub async fn record_indexed_root(path: &str, app_handle: &AppHandle) -> Result<String, WatcherError> {
+    let root = canonical_root(path)?;
+    {
+        let _guard = INDEXED_ROOTS_LOCK.lock().await;
+        let file = indexed_roots_file(app_handle)?;
+        let mut config = IndexedRootsConfig::load(&file).await?;
+        let Some(replaced) = config.add_indexed_folder(&root) else {
+            return Ok(root);
+        };
+        config.save(&file).await?;
+        for nested in replaced {
+            match stop_root(&nested, app_handle).await {
+                Ok(_) | Err(WatcherError::NotRunning(_)) => {}
+                Err(e) => warn!("Failed to stop watcher of {}, now covered by {}: {}", nested, root, e),
+            }
+        }
+    }
+
+    // The folder was just indexed, so there is nothing to reconcile
+    match start_root(&root, WatcherBackend::default(), app_handle, false).await {
+        Ok(_) | Err(WatcherError::AlreadyRunning(_)) => {}
+        Err(e) => warn!("Failed to watch indexed root {}: {}", root, e),
+    }
+    Ok(root)
+}
+
+/// Adds a root: starts watching it and records it so it is watched again after a restart.
+/// The backend defaults to native notifications; use polling for network shares.
+#[tauri::command]
+pub async fn add_watched_root_command(
+    path: String,
+    backend: Option<WatcherBackend>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    let backend = backend.unwrap_or_default();
+    info!("Adding watched root: {} ({:?})", path, backend);
+    let root = start_root(&path, backend, &app_handle, false).await.map_err(|e| e.to_string())?;
+
+    let _guard = INDEXED_ROOTS_LOCK.lock().await;
+    let file = indexed_roots_file(&app_handle).map_err(|e| e.to_string())?;
+    let mut config = IndexedRootsConfig::load(&file).await.map_err(|e| e.to_string())?;
+    if config.add(&root, backend) {
+        config.save(&file).await.map_err(|e| e.to_string())?;
+    }
+
+    Ok(format!("Now watching: {}", root))
+}
+
+/// Removes a root: stops its watcher and drops it from the indexed roots configuration.
+/// Files already indexed from the root stay in the index.
+#[tauri::command]
+pub async fn remove_watched_root_command(path: String, app_handle: AppHandle) -> Result<String, String> {
+    info!("Removing watched root: {}", path);
+    let root = match stop_root(&path, &app_handle).await {
+        Ok(root) => root,
+        // A configured root whose watcher failed to start can still be removed
+        Err(WatcherError::NotRunning(root)) => root,
+        Err(e) => return Err(e.to_string()),
     };
 
-    if let Err(e) = app_handle.emit("file-watcher-event", &stop_event) {
-        error!("Failed to emit watcher stop event: {}", e);
+    let _guard = INDEXED_ROOTS_LOCK.lock().await;
+    let file = indexed_roots_file(&app_handle).map_err(|e| e.to_string())?;
+    let mut config = IndexedRootsConfig::load(&file).await.map_err(|e| e.to_string())?;
+    if !config.remove(&root) && !config.remove(&path) {
+        return Err(format!("'{}' is not a watched root", path));
+    }
+    config.save(&file).await.map_err(|e| e.to_string())?;
+
+    Ok(format!("Stopped watching: {}", root))
+}
+
+/// List the roots in the indexed roots configuration
+#[tauri::command]
+pub async fn get_watched_roots_command(app_handle: AppHandle) -> Result<Vec<IndexedRoot>, String> {
+    let file = indexed_roots_file(&app_handle).map_err(|e| e.to_string())?;
+    let config = IndexedRootsConfig::load(&file).await.map_err(|e| e.to_string())?;
+    Ok(config.roots)
+}
+
+/// Start watching a directory for this session, without adding it to the indexed roots
+#[tauri::command]
+pub async fn start_watcher_command(
+    path: String,
+    backend: Option<WatcherBackend>,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    info!("Starting file watcher for path: {}", path);
+    let root = start_root(&path, backend.unwrap_or_default(), &app_handle, false)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(format!("File watcher started successfully for: {}", root))
+}
+
+/// Stop the watcher of one root, or of every root when no path is given
+#[tauri::command]
+pub async fn stop_watcher_command(path: Option<String>, app_handle: AppHandle) -> Result<String, String> {
+    if let Some(path) = path {
+        let root = stop_root(&path, &app_handle).await.map_err(|e| e.to_string())?;
+        return Ok(format!("File watcher stopped for: {}", root));
     }
 
-    Ok("File watcher stopped successfully".to_string())
+    info!("Stopping all file watchers");
+    let roots: Vec<String> = WATCHERS.lock().unwrap().keys().cloned().collect();
+    if roots.is_empty() {
+        return Err("File watcher is not running".to_string());
+    }
+    for root in &roots {
+        if let Err(e) = stop_root(root, &app_handle).await {
+            warn!("Failed to stop watcher for {}: {}", root, e);
+        }
+    }
+    Ok(format!("Stopped {} file watchers", roots.len()))
 }
 
-/// Get watcher status
+/// Get the status and event counters of every watched root
 #[tauri::command]
 pub async fn get_watcher_status() -> Result<WatcherStatus, String> {
-    let state = WATCHER_STATE.lock().unwrap();
-    let is_running = state.is_some();
+    let watchers = WATCHERS.lock().unwrap();
+    let mut roots: Vec<RootStatus> = watchers
+        .iter()
+        .map(|(path, watched_root)| RootStatus {
+            path: path.clone(),
+            backend: watched_root.backend,
+            is_running: !watched_root.task.is_finished(),
+            events_processed: watched_root.stats.events_processed(),
+            last_event_time: watched_root.stats.last_event_time(),
+        })
+        .collect();
+    roots.sort_by(|a, b| a.path.cmp(&b.path));
 
     Ok(WatcherStatus {
-        is_running,
-        watched_path: if is_running {
-            Some("Active".to_string())
-        } else {
-            None
-        },
-        events_processed: 0, // Could be enhanced to track this
-        last_event_time: None,
+        is_running: roots.iter().any(|root| root.is_running),
+        events_processed: roots.iter().map(|root| root.events_processed).sum(),
+        last_event_time: roots.iter().filter_map(|root| root.last_event_time).max(),
+        roots,
     })
 }
 
+/// Get the watcher debouncing configuration
+#[tauri::command]
+pub fn get_watcher_config_command() -> Result<WatcherConfig, String> {
+    Ok(get_watcher_config())
+}
+
+/// Change the watcher debounce period and batch size; applies to watchers started afterwards
+#[tauri::command]
+pub async fn set_watcher_config_command(config: WatcherConfig, app_handle: AppHandle) -> Result<String, String> {
+    set_watcher_config(config).map_err(|e| e.to_string())?;
+    if let Err(e) = crate::settings::save_settings(&app_handle).await {
+        error!("Failed to save settings: {}", e);
+    }
+    Ok("Watcher configuration updated. Restart the watcher to apply it.".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::db::{connect_db_with_path, open_or_create_amharic_text_table, TestDb};
+    use crate::db::{connect_db_with_path, upsert_document, TestDb, TEXT_EMBEDDING_DIM};
     use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
@@ -662,9 +1474,9 @@ mod tests {
         assert!(is_relevant_file_for_upsert(&md_file));
         assert!(is_relevant_file_for_upsert(&pdf_file));
         assert!(is_relevant_file_for_upsert(&docx_file));
+        assert!(is_relevant_file_for_upsert(&jpg_file));
 
         // Test unsupported extensions
-        assert!(!is_relevant_file_for_upsert(&jpg_file));
         assert!(!is_relevant_file_for_upsert(&exe_file));
 
         // Test hidden files
@@ -675,13 +1487,381 @@ mod tests {
         assert!(is_relevant_file(&md_file));
         assert!(is_relevant_file(&pdf_file));
         assert!(is_relevant_file(&docx_file));
-        assert!(!is_relevant_file(&jpg_file));
+        assert!(is_relevant_file(&jpg_file));
         assert!(!is_relevant_file(&exe_file));
         assert!(!is_relevant_file(&hidden_file));
     }
 
+    #[test]
+    fn test_classify_event() {
+        let dir = tempdir().unwrap();
+        let existing = dir.path().join("notes.txt");
+        File::create(&existing).unwrap();
+        let gone = dir.path().join("old.txt");
+
+        let created = Event::new(EventKind::Create(CreateKind::File)).add_path(existing.clone());
+        assert_eq!(classify_event(&created), vec![(existing.clone(), FileAction::Created)]);
+
+        let modified = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Content))).add_path(existing.clone());
+        assert_eq!(classify_event(&modified), vec![(existing.clone(), FileAction::Modified)]);
+
+        // A rename reports both names: the old one is removed, the new one indexed
+        let renamed = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(gone.clone())
+            .add_path(existing.clone());
+        assert_eq!(
+            classify_event(&renamed),
+            vec![(gone.clone(), FileAction::Deleted), (existing.clone(), FileAction::Created)]
+        );
+
+        let removed = Event::new(EventKind::Remove(RemoveKind::File)).add_path(gone.clone());
+        assert_eq!(classify_event(&removed), vec![(gone, FileAction::Deleted)]);
+
+        // Content changes of folders and access events do not touch the index
+        let folder = Event::new(EventKind::Modify(ModifyKind::Data(DataChange::Any))).add_path(dir.path().to_path_buf());
+        assert!(classify_event(&folder).is_empty());
+        let access = Event::new(EventKind::Access(notify::event::AccessKind::Any)).add_path(existing);
+        assert!(classify_event(&access).is_empty());
+    }
+
+    #[test]
+    fn test_folder_moved_in_queues_its_files() {
+        let dir = tempdir().unwrap();
+        let folder = dir.path().join("projects");
+        std::fs::create_dir_all(folder.join("sub")).unwrap();
+        std::fs::create_dir_all(folder.join(".git")).unwrap();
+        File::create(folder.join("a.txt")).unwrap();
+        File::create(folder.join("sub").join("b.md")).unwrap();
+        File::create(folder.join(".git").join("config")).unwrap();
+        let expected = vec![
+            (folder.join("a.txt"), FileAction::Created),
+            (folder.join("sub").join("b.md"), FileAction::Created),
+        ];
+
+        // A `To` without a `From` is a folder moved in from outside the root
+        let moved_in = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::To))).add_path(folder.clone());
+        assert_eq!(classify_event(&moved_in), expected);
+        let created = Event::new(EventKind::Create(CreateKind::Folder)).add_path(folder.clone());
+        assert_eq!(classify_event(&created), expected);
+
+        let hidden = Event::new(EventKind::Create(CreateKind::Folder)).add_path(folder.join(".git"));
+        assert!(classify_event(&hidden).is_empty());
+    }
+
+    #[test]
+    fn test_debouncer_coalesces_events_per_path() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut debouncer = EventDebouncer::new(Duration::from_millis(100));
+        let saved = PathBuf::from("/docs/report.txt");
+        let temp = PathBuf::from("/docs/.report.txt.swp");
+
+        // Editor save: write a temp file, delete the original, rename the temp file over it
+        debouncer.push(temp.clone(), FileAction::Created, at(0));
+        debouncer.push(temp.clone(), FileAction::Modified, at(10));
+        debouncer.push(saved.clone(), FileAction::Deleted, at(20));
+        debouncer.push(temp.clone(), FileAction::Deleted, at(30));
+        debouncer.push(saved.clone(), FileAction::Created, at(30));
+        debouncer.push(saved.clone(), FileAction::Modified, at(40));
+
+        assert_eq!(debouncer.next_deadline(), Some(at(130)));
+        // Nothing is quiet yet
+        assert!(debouncer.take_ready(at(100)).is_empty());
+        let ready = debouncer.take_ready(at(140));
+        assert_eq!(ready, vec![(temp, FileAction::Deleted), (saved, FileAction::Modified)]);
+        assert!(debouncer.is_empty());
+        assert_eq!(debouncer.next_deadline(), None);
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_each_path() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let mut debouncer = EventDebouncer::new(Duration::from_millis(100));
+        let first = PathBuf::from("/repo/a.rs");
+        let second = PathBuf::from("/repo/b.rs");
+
+        debouncer.push(first.clone(), FileAction::Created, at(0));
+        debouncer.push(second.clone(), FileAction::Modified, at(0));
+        debouncer.push(second.clone(), FileAction::Modified, at(80));
+
+        assert_eq!(debouncer.take_ready(at(100)), vec![(first, FileAction::Created)]);
+        assert_eq!(debouncer.take_all(), vec![(second, FileAction::Modified)]);
+    }
+
+    #[test]
+    fn test_net_actions_and_config_validation() {
+        assert_eq!(FileAction::Created.then(FileAction::Modified), FileAction::Created);
+        assert_eq!(FileAction::Created.then(FileAction::Deleted), FileAction::Deleted);
+        assert_eq!(FileAction::Deleted.then(FileAction::Created), FileAction::Modified);
+        assert_eq!(FileAction::Modified.then(FileAction::Created), FileAction::Modified);
+
+        assert!(WatcherConfig::default().validate().is_ok());
+        assert!(WatcherConfig { batch_size: 0, ..WatcherConfig::default() }.validate().is_err());
+        assert!(WatcherConfig { debounce_ms: 120_000, ..WatcherConfig::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_rename_events_are_paired() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let old = PathBuf::from("/docs/draft.txt");
+        let new = PathBuf::from("/docs/final.txt");
+
+        let both = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            .add_path(old.clone())
+            .add_path(new.clone());
+        assert_eq!(rename_part(&both), Some(RenamePart::Both(old.clone(), new.clone())));
+        let from = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::From)))
+            .add_path(old.clone())
+            .set_tracker(7);
+        assert_eq!(rename_part(&from), Some(RenamePart::From(old.clone(), Some(7))));
+        let any = Event::new(EventKind::Modify(ModifyKind::Name(RenameMode::Any))).add_path(new.clone());
+        assert_eq!(rename_part(&any), None);
+
+        // Halves are matched by tracker, even when renames interleave
+        let mut renames = RenamePairer::default();
+        renames.push_from(old.clone(), Some(7), at(0));
+        renames.push_from(PathBuf::from("/docs/a.txt"), Some(8), at(0));
+        assert_eq!(renames.match_to(Some(7)), Some(old.clone()));
+        assert_eq!(renames.match_to(Some(9)), None);
+
+        // An unpaired half times out: the file left the watched root
+        assert_eq!(renames.next_deadline(), Some(at(0) + RENAME_PAIR_TIMEOUT));
+        assert!(renames.take_expired(at(100)).is_empty());
+        assert_eq!(renames.take_expired(at(600)), vec![PathBuf::from("/docs/a.txt")]);
+
+        // Backends without trackers pair the oldest untracked half
+        renames.push_from(old.clone(), None, at(700));
+        assert_eq!(renames.match_to(None), Some(old));
+        assert!(renames.take_all().is_empty());
+    }
+
+    #[test]
+    fn test_pending_changes_follow_renames() {
+        let now = Instant::now();
+        let mut debouncer = EventDebouncer::new(Duration::from_millis(100));
+        debouncer.push(PathBuf::from("/docs/draft.txt"), FileAction::Created, now);
+        debouncer.push(PathBuf::from("/docs/old/a.txt"), FileAction::Modified, now);
+        debouncer.push(PathBuf::from("/docs/old2/b.txt"), FileAction::Modified, now);
+        debouncer.push(PathBuf::from("/docs/final.txt"), FileAction::Deleted, now);
+
+        debouncer.rename(Path::new("/docs/draft.txt"), Path::new("/docs/final.txt"));
+        debouncer.rename(Path::new("/docs/old"), Path::new("/docs/new"));
+        assert_eq!(
+            debouncer.take_all(),
+            vec![
+                (PathBuf::from("/docs/final.txt"), FileAction::Created),
+                (PathBuf::from("/docs/new/a.txt"), FileAction::Modified),
+                (PathBuf::from("/docs/old2/b.txt"), FileAction::Modified),
+            ]
+        );
+
+        let dir = tempdir().unwrap();
+        assert!(can_move_in_place(Path::new("/docs/a.txt"), Path::new("/docs/b.txt")));
+        assert!(can_move_in_place(Path::new("/docs/old"), dir.path()));
+        // An editor's temporary file renamed over the original, and a changed type
+        assert!(!can_move_in_place(Path::new("/docs/.a.txt.swp"), Path::new("/docs/a.txt")));
+        assert!(!can_move_in_place(Path::new("/docs/a.txt"), Path::new("/docs/a.png")));
+    }
+
+    #[test]
+    fn test_reconciliation_finds_offline_changes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        for name in ["kept.txt", "edited.txt", "touched.txt", "restored.txt", "new.md", "photo.jpg"] {
+            writeln!(File::create(root.join(name)).unwrap(), "content of {}", name).unwrap();
+        }
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        File::create(root.join(".git").join("HEAD.txt")).unwrap();
+
+        let (on_disk, unreadable) = scan_root(root);
+        assert!(unreadable.is_empty());
+        let mut scanned: Vec<&str> = on_disk.keys().filter_map(|path| path.file_name()?.to_str()).collect();
+        scanned.sort();
+        // Hidden folders are skipped
+        assert_eq!(scanned, vec!["edited.txt", "kept.txt", "new.md", "photo.jpg", "restored.txt", "touched.txt"]);
+
+        let mtime = on_disk[&root.join("kept.txt")].0;
+        let size = |name: &str| Some(on_disk[&root.join(name)].1);
+        let stored = |indexed_at: i64, hash: &str, size: Option<u64>| IndexedFile {
+            content_hash: hash.to_string(),
+            size,
+            indexed_at,
+        };
+        let indexed = HashMap::from([
+            (root.join("kept.txt"), stored(mtime + 60, "kept", size("kept.txt"))),
+            (root.join("edited.txt"), stored(mtime - 60, "old", size("edited.txt"))),
+            (root.join("touched.txt"), stored(mtime - 60, "same", size("touched.txt"))),
+            // Replaced after indexing by an older copy of a different size
+            (root.join("restored.txt"), stored(mtime + 60, "restored", size("kept.txt"))),
+            (root.join("removed.txt"), stored(mtime - 60, "gone", None)),
+            // Under a folder the scan could not read: not known to be gone
+            (root.join("offline").join("report.txt"), stored(mtime - 60, "offline", None)),
+        ]);
+        let unreadable = vec![root.join("offline")];
+        let changes = diff_root(&on_disk, &unreadable, &indexed, |_, stored| stored.content_hash == "same");
+        assert_eq!(
+            changes,
+            vec![
+                (root.join("edited.txt"), FileAction::Modified),
+                (root.join("new.md"), FileAction::Created),
+                (root.join("photo.jpg"), FileAction::Created),
+                (root.join("removed.txt"), FileAction::Deleted),
+                (root.join("restored.txt"), FileAction::Modified),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_changes() {
+        let dir = tempdir().unwrap();
+        let kept = dir.path().join("kept.txt");
+        let edited = dir.path().join("edited.txt");
+        let removed = dir.path().join("removed.txt");
+        for path in [&kept, &edited, &removed] {
+            writeln!(File::create(path).unwrap(), "first version").unwrap();
+        }
+        std::fs::create_dir_all(dir.path().join("node_modules")).unwrap();
+        File::create(dir.path().join("node_modules").join("dep.js")).unwrap();
+
+        let before = Snapshot::scan(dir.path());
+        assert_eq!(before.len(), 3);
+        assert!(before.changes(&before).is_empty());
+
+        // The size changes even if the write lands within the mtime granularity
+        writeln!(File::options().append(true).open(&edited).unwrap(), "second version").unwrap();
+        std::fs::remove_file(&removed).unwrap();
+        let added = dir.path().join("sub").join("added.md");
+        std::fs::create_dir_all(added.parent().unwrap()).unwrap();
+        File::create(&added).unwrap();
+
+        let after = Snapshot::scan(dir.path());
+        let changes: Vec<(EventKind, PathBuf)> =
+            before.changes(&after).into_iter().map(|event| (event.kind, event.paths[0].clone())).collect();
+        assert_eq!(
+            changes,
+            vec![
+                (EventKind::Modify(ModifyKind::Data(DataChange::Any)), edited),
+                (EventKind::Remove(RemoveKind::File), removed),
+                (EventKind::Create(CreateKind::File), added),
+            ]
+        );
+
+        // Files under a folder the next poll cannot read are neither removed nor forgotten
+        let mut unreadable = Snapshot { files: HashMap::new(), unreadable: vec![dir.path().to_path_buf()] };
+        assert!(after.changes(&unreadable).is_empty());
+        unreadable.keep_unreadable(&after);
+        assert_eq!(unreadable.files, after.files);
+    }
+
     #[tokio::test]
-    async fn test_process_file_upsert() {
+    async fn test_polling_watcher_reports_changes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+        let backend = WatcherBackend::Polling { interval_ms: 100 };
+        let (watcher, mut rx) = setup_watcher(root, backend).unwrap();
+        assert!(matches!(watcher, RootWatcher::Polling(_)));
+
+        // Let the first snapshot be taken before the file appears
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        let file = dir.path().join("notes.txt");
+        File::create(&file).unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("No event from the polling watcher")
+            .unwrap()
+            .unwrap();
+        assert_eq!(classify_event(&event), vec![(file, FileAction::Created)]);
+
+        // Dropping the watcher stops the polling task and closes the channel
+        drop(watcher);
+        assert!(tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_backend_selection_is_persisted_per_root() {
+        assert!(WatcherBackend::Native.validate().is_ok());
+        assert!(WatcherBackend::Polling { interval_ms: 5_000 }.validate().is_ok());
+        assert!(WatcherBackend::Polling { interval_ms: 10 }.validate().is_err());
+
+        let mut config = IndexedRootsConfig::default();
+        assert!(config.add("/mnt/team-docs", WatcherBackend::Native));
+        assert!(config.add("/mnt/team-docs", WatcherBackend::Polling { interval_ms: 5_000 }));
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""backend":{"kind":"polling","interval_ms":5000}"#), "{}", json);
+
+        // Files written before backends existed load as native
+        let old: IndexedRootsConfig = serde_json::from_str(r#"{"roots":[{"path":"/home/user/Documents"}]}"#).unwrap();
+        assert_eq!(old.roots[0].backend, WatcherBackend::Native);
+    }
+
+    #[test]
+    fn test_roots_and_their_stats() {
+        let dir = tempdir().unwrap();
+        let root = canonical_root(dir.path().to_str().unwrap()).unwrap();
+        let file = dir.path().join("notes.txt");
+        File::create(&file).unwrap();
+        assert!(matches!(canonical_root(file.to_str().unwrap()), Err(WatcherError::InvalidRoot(..))));
+        assert!(canonical_root(dir.path().join("missing").to_str().unwrap()).is_err());
+
+        let watched = vec!["/home/user/Documents".to_string(), "/data/projects".to_string()];
+        assert_eq!(overlapping_root("/home/user/Documents/reports", &watched), Some(watched[0].clone()));
+        assert_eq!(overlapping_root("/data", &watched), Some(watched[1].clone()));
+        // Sibling folders sharing a name prefix do not overlap
+        assert_eq!(overlapping_root("/home/user/Documents2", &watched), None);
+        assert_eq!(overlapping_root(&root, &watched), None);
+
+        let stats = RootStats::default();
+        assert_eq!((stats.events_processed(), stats.last_event_time()), (0, None));
+        stats.record_event(1_700_000_000);
+        stats.record_event(1_700_000_005);
+        assert_eq!((stats.events_processed(), stats.last_event_time()), (2, Some(1_700_000_005)));
+    }
+
+    #[tokio::test]
+    async fn test_indexed_roots_config_persists() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("app_data").join(INDEXED_ROOTS_FILE);
+        assert_eq!(IndexedRootsConfig::load(&file).await.unwrap(), IndexedRootsConfig::default());
+
+        let mut config = IndexedRootsConfig::default();
+        assert!(config.add("/home/user/Documents", WatcherBackend::Native));
+        assert!(config.add("/data/projects", WatcherBackend::Native));
+        assert!(!config.add("/data/projects", WatcherBackend::Native));
+        config.save(&file).await.unwrap();
+
+        let mut loaded = IndexedRootsConfig::load(&file).await.unwrap();
+        assert_eq!(loaded, config);
+        assert!(loaded.remove("/home/user/Documents"));
+        assert!(!loaded.remove("/home/user/Documents"));
+        assert_eq!(
+            loaded.roots,
+            vec![IndexedRoot { path: "/data/projects".to_string(), backend: WatcherBackend::Native }]
+        );
+
+        std::fs::write(&file, "not json").unwrap();
+        assert!(matches!(IndexedRootsConfig::load(&file).await, Err(WatcherError::Storage(_))));
+    }
+
+    #[test]
+    fn test_indexed_folders_become_roots() {
+        let mut config = IndexedRootsConfig::default();
+        assert!(config.add("/data/projects/app", WatcherBackend::Polling { interval_ms: 5_000 }));
+        assert_eq!(config.add_indexed_folder("/home/user/Documents"), Some(Vec::new()));
+        // Folders inside a root are already covered by it
+        assert_eq!(config.add_indexed_folder("/home/user/Documents/reports"), None);
+        assert_eq!(config.add_indexed_folder("/home/user/Documents"), None);
+
+        // A folder around roots replaces them
+        assert_eq!(config.add_indexed_folder("/data"), Some(vec!["/data/projects/app".to_string()]));
+        let paths: Vec<&str> = config.roots.iter().map(|root| root.path.as_str()).collect();
+        assert_eq!(paths, vec!["/home/user/Documents", "/data"]);
+    }
+
+    #[tokio::test]
+    async fn test_apply_change() {
         // Create a temporary file
         let dir = tempdir().unwrap();
         let file_path = dir.path().join("test.txt");
@@ -691,15 +1871,56 @@ mod tests {
         // Create a temporary database
         let test_db = TestDb::new();
         let conn = connect_db_with_path(&test_db.path).await.unwrap();
-        let table = open_or_create_amharic_text_table(&conn).await.unwrap();
-
-        // Test the upsert function
-        let result = process_file_upsert(&file_path, &table).await;
+        let tables = WatcherTables {
+            text_table: open_or_create_text_table(&conn).await.unwrap(),
+            amharic_text_table: open_or_create_amharic_text_table(&conn).await.unwrap(),
+            image_table: open_or_create_image_table(&conn).await.unwrap(),
+        };
+
+        // Indexing may fail in a test environment without the embedding models
+        let event = apply_change(&file_path, FileAction::Created, &tables).await;
+        assert_eq!(event.event_type, "created");
+        assert_eq!(event.file_path, file_path.to_string_lossy());
+        info!("Upsert result: {}", event.message);
+
+        // Removing works whether or not the file was indexed
+        let event = apply_change(&file_path, FileAction::Deleted, &tables).await;
+        assert_eq!(event.event_type, "deleted");
+        assert!(event.success, "{}", event.message);
+
+        // Images go through the image pipeline and are removed from the image table
+        let image_path = dir.path().join("photo.png");
+        image::RgbImage::new(8, 8).save(&image_path).unwrap();
+        let event = apply_change(&image_path, FileAction::Created, &tables).await;
+        assert_eq!(event.event_type, "created");
+        info!("Image upsert result: {}", event.message);
+        let event = apply_change(&image_path, FileAction::Deleted, &tables).await;
+        assert!(event.success, "{}", event.message);
+        assert_eq!(tables.image_table.count_rows(None).await.unwrap(), 0);
+    }
 
-        // Should succeed (or handle gracefully if embedding fails in test environment)
-        match result {
-            Ok(_) => info!("Upsert succeeded"),
-            Err(e) => info!("Upsert failed (expected in test environment): {}", e),
+    #[tokio::test]
+    async fn test_folder_moved_out_is_removed_by_prefix() {
+        let dir = tempdir().unwrap();
+        let test_db = TestDb::new();
+        let conn = connect_db_with_path(&test_db.path).await.unwrap();
+        let tables = WatcherTables {
+            text_table: open_or_create_text_table(&conn).await.unwrap(),
+            amharic_text_table: open_or_create_amharic_text_table(&conn).await.unwrap(),
+            image_table: open_or_create_image_table(&conn).await.unwrap(),
+        };
+        let folder = dir.path().join("projects");
+        let embedding = vec![0.1f32; TEXT_EMBEDDING_DIM as usize];
+        for path in [folder.join("a.txt"), folder.join("sub").join("b.md"), dir.path().join("projects-old.txt")] {
+            upsert_document(&tables.text_table, &path.to_string_lossy(), "hash", &[embedding.clone()])
+                .await
+                .unwrap();
         }
+
+        // The folder is gone and has no content type: everything under it is removed
+        assert!(is_relevant_file_for_action(&folder, FileAction::Deleted));
+        let event = apply_change(&folder, FileAction::Deleted, &tables).await;
+        assert!(event.success, "{}", event.message);
+        assert_eq!(tables.text_table.count_rows(None).await.unwrap(), 1);
     }
 }
@@ -45,6 +45,8 @@ import { open } from '@tauri-apps/plugin-dialog';
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert";
 import { AlertCircle } from "lucide-react";
+import { Checkbox } from "@/components/ui/checkbox";
+import { getCrossLingualConfig, setCrossLingualConfig } from '../services/commands';
 
 const IndexingStatus = () => {
     // Semantic indexing state
@@ -81,6 +83,14 @@ const IndexingStatus = () => {
     const [selectedFilenameTab, setSelectedFilenameTab] = useState("initialize");
     const [clearSuccess, setClearSuccess] = useState<string | null>(null);
     const [clearFilenameSuccess, setClearFilenameSuccess] = useState<string | null>(null);
+    const [indexEnglishCrossLingual, setIndexEnglishCrossLingual] = useState(true);
+
+    // Load the cross-lingual indexing setting on mount
+    useEffect(() => {
+        getCrossLingualConfig()
+            .then((config) => setIndexEnglishCrossLingual(config.index_english))
+            .catch(() => {});
+    }, []);
 
     // Fetch semantic indexing stats on mount and every 2 seconds while indexing
     useEffect(() => {
@@ -183,6 +193,15 @@ const IndexingStatus = () => {
         }
     };
 
+    const handleCrossLingualChange = async (checked: boolean) => {
+        setIndexEnglishCrossLingual(checked);
+        try {
+            await setCrossLingualConfig({ index_english: checked });
+        } catch {
+            setIndexEnglishCrossLingual(!checked);
+        }
+    };
+
     const handleClearIndex = async () => {
         setShowConfirmClear(false);
         await clearIndex();
@@ -388,6 +407,16 @@ const IndexingStatus = () => {
                     </Card>
                 </TabsContent>
             </Tabs>
+            <div className="flex items-center gap-2 -mt-6 mb-8 text-sm text-gray-300">
+                <Checkbox
+                    id="cross-lingual-english"
+                    checked={indexEnglishCrossLingual}
+                    onCheckedChange={(checked: boolean | 'indeterminate') => handleCrossLingualChange(checked === true)}
+                />
+                <label htmlFor="cross-lingual-english">
+                    Make English documents findable by "Any language" searches in other languages (slower indexing; applies to folders indexed afterwards)
+                </label>
+            </div>
             
             {/* Filename Indexing Options */}
             <h2 className="text-xl font-bold text-white mb-3 mt-8">Filename Indexing</h2>
@@ -754,8 +783,9 @@ const IndexingStatus = () => {
                                 <ul className="space-y-1">
                                     {indexingStats.failed_files.map((file, index) => (
                                         <li key={index} className="truncate text-sm text-gray-300">
-                                            <span className="text-red-400 mr-2">•</span> {getFileName(file)}
-                                            <span className="block ml-5 text-xs text-gray-500 truncate">{file}</span>
+                                            <span className="text-red-400 mr-2">•</span> {getFileName(file.file_path)}
+                                            <span className="block ml-5 text-xs text-gray-500 truncate">{file.file_path}</span>
+                                            <span className="block ml-5 text-xs text-red-400/80 truncate" title={file.reason}>{file.reason}</span>
                                         </li>
                                     ))}
                                 </ul>
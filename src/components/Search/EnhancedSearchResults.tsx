@@ -2,7 +2,7 @@ import React from 'react';
 import { useAtomValue, useSetAtom } from 'jotai';
 import { FileIcon, FileTextIcon, FileImageIcon, FileArchiveIcon, FileCodeIcon, ExternalLink } from 'lucide-react';
 import { searchResultsAtom, searchQueryAtom, isSearchingAtom, searchErrorAtom, hasSearchedAtom, navigateAtom, selectedFileAtom } from '../../store/atoms';
-import { SearchResult, FilenameSearchResult } from '@/types/search';
+import { SearchResult, FilenameSearchResult, ImageData } from '@/types/search';
 import { Button } from '../ui/button';
 import { Skeleton } from '../ui/skeleton';
 import { formatDistance } from 'date-fns';
@@ -50,24 +50,66 @@ const getFileIcon = (filePath: string) => {
   }
 };
 
+// Separates an archive from the member path in virtual paths such as `bundle.zip!/docs/spec.pdf`
+const ARCHIVE_MEMBER_SEPARATOR = '!/';
+
 // Helper function to format file path for display
 const formatFilePath = (filePath: string) => {
   // Extract file name and directory
   const parts = filePath.split('/');
   const fileName = parts.pop() || '';
-  const directory = parts.join('/');
-  
-  return { fileName, directory };
+
+  // Members of an archive live in the folder of the (outermost) archive file
+  const separatorIndex = filePath.indexOf(ARCHIVE_MEMBER_SEPARATOR);
+  const containerPath = separatorIndex >= 0 ? filePath.slice(0, separatorIndex) : filePath;
+  const containerParts = containerPath.split('/');
+  const containerName = containerParts.pop() || '';
+  const directory = containerParts.join('/');
+  const archiveName = separatorIndex >= 0 ? containerName : null;
+
+  return { fileName, directory, containerName, containerPath, archiveName };
+};
+
+// Highlights the matched characters of a filename search result; ranges count code points, not UTF-16 units
+const highlightMatches = (text: string, ranges: [number, number][] = []) => {
+  if (ranges.length === 0) return text;
+  const chars = Array.from(text);
+  const parts: React.ReactNode[] = [];
+  let cursor = 0;
+  ranges.forEach(([start, end], index) => {
+    if (start > cursor) parts.push(chars.slice(cursor, start).join(''));
+    parts.push(
+      <mark key={index} className="bg-transparent text-purple-300 font-semibold">
+        {chars.slice(start, end).join('')}
+      </mark>
+    );
+    cursor = end;
+  });
+  if (cursor < chars.length) parts.push(chars.slice(cursor).join(''));
+  return parts;
 };
 
 // Helper function to truncate hash
+// One line of image details: dimensions, orientation, capture date, camera and GPS position
+const formatImageDetails = (image: ImageData) => {
+  const parts: string[] = [];
+  if (image.width != null && image.height != null) parts.push(`${image.width}×${image.height}`);
+  if (image.orientation) parts.push(image.orientation);
+  if (image.taken_at) parts.push(`taken ${image.taken_at.replace('T', ' ')}`);
+  if (image.camera_model) parts.push(image.camera_model);
+  if (image.latitude != null && image.longitude != null) {
+    parts.push(`${image.latitude.toFixed(4)}, ${image.longitude.toFixed(4)}`);
+  }
+  return parts.join(' · ');
+};
+
 const truncateHash = (hash: string, length: number) => {
   return hash.substring(0, length) + '...';
 };
 
 // Component for a single search result
 const SearchResultItem = ({ result }: { result: SearchResult | FilenameSearchResult }) => {
-  const { fileName, directory } = formatFilePath(result.file_path);
+  const { fileName, directory, containerName, containerPath, archiveName } = formatFilePath(result.file_path);
   const lastModified = new Date(result.last_modified * 1000); // Convert Unix timestamp to JS Date
   
   // Determine if this is a semantic or filename search result
@@ -82,12 +124,12 @@ const SearchResultItem = ({ result }: { result: SearchResult | FilenameSearchRes
     // Navigate to the file's directory
     navigate(directory);
     
-    // Create a simulated file info object to highlight the selected file
+    // Create a simulated file info object to highlight the selected file (the archive for archive members)
     setSelectedFile({
-      name: fileName,
-      path: result.file_path,
+      name: containerName,
+      path: containerPath,
       is_directory: false,
-      file_type: fileName.split('.').pop() || 'Unknown',
+      file_type: containerName.split('.').pop() || 'Unknown',
       size: null, // We don't have this information from the search result
       modified: result.last_modified,
     });
@@ -101,7 +143,8 @@ const SearchResultItem = ({ result }: { result: SearchResult | FilenameSearchRes
     e.stopPropagation(); // Prevent the parent click handler from firing
     
     try {
-      await openPath(result.file_path);
+      const page = isSemanticResult ? (result as SearchResult).page_number : null;
+      await openPath(result.file_path, page);
     } catch (error) {
       console.error('Failed to open file:', error);
     }
@@ -116,17 +159,71 @@ const SearchResultItem = ({ result }: { result: SearchResult | FilenameSearchRes
         {getFileIcon(result.file_path)}
         <div className="flex-1 min-w-0">
           <div className="flex justify-between items-start">
-            <p className="font-medium text-gray-200 truncate">{fileName}</p>
+            <div className="min-w-0">
+              <p className="font-medium text-gray-200 truncate">
+                {isSemanticResult
+                  ? fileName
+                  : highlightMatches((result as FilenameSearchResult).name, (result as FilenameSearchResult).match_ranges)}
+              </p>
+              {archiveName && (
+                <p className="text-xs text-gray-500 truncate">in {archiveName}</p>
+              )}
+              {isSemanticResult && (result as SearchResult).heading_path && (
+                <p className="text-xs text-gray-500 truncate">{(result as SearchResult).heading_path}</p>
+              )}
+              {isSemanticResult && (result as SearchResult).page_number != null && (
+                <p className="text-xs text-gray-500">Page {(result as SearchResult).page_number}</p>
+              )}
+              {isSemanticResult && (result as SearchResult).sheet_name && (
+                <p className="text-xs text-gray-500 truncate">
+                  {(result as SearchResult).sheet_name}
+                  {(result as SearchResult).row_start != null &&
+                    ` · rows ${(result as SearchResult).row_start}–${(result as SearchResult).row_end ?? (result as SearchResult).row_start}`}
+                </p>
+              )}
+              {isSemanticResult && (result as SearchResult).line_start != null && (
+                <p className="text-xs text-gray-500 truncate">
+                  {(result as SearchResult).symbol_name ? `${(result as SearchResult).symbol_name} · ` : ''}
+                  lines {(result as SearchResult).line_start}–{(result as SearchResult).line_end ?? (result as SearchResult).line_start}
+                </p>
+              )}
+              {isSemanticResult && (result as SearchResult).message_subject && (
+                <p className="text-xs text-gray-500 truncate">
+                  {(result as SearchResult).message_index != null && `Message ${(result as SearchResult).message_index} · `}
+                  {(result as SearchResult).message_subject}
+                  {(result as SearchResult).message_from && ` · ${(result as SearchResult).message_from}`}
+                  {(result as SearchResult).message_date && ` · ${(result as SearchResult).message_date}`}
+                </p>
+              )}
+              {isSemanticResult && !(result as SearchResult).message_subject &&
+                ((result as SearchResult).title || (result as SearchResult).author || (result as SearchResult).created) && (
+                <p className="text-xs text-gray-500 truncate">
+                  {[(result as SearchResult).title, (result as SearchResult).author, (result as SearchResult).created]
+                    .filter(Boolean)
+                    .join(' · ')}
+                </p>
+              )}
+              {isSemanticResult && (result as SearchResult).image_data && (
+                <p className="text-xs text-gray-500 truncate">
+                  {formatImageDetails((result as SearchResult).image_data!)}
+                </p>
+              )}
+              {isSemanticResult && ((result as SearchResult).tags?.length ?? 0) > 0 && (
+                <p className="text-xs text-gray-500 truncate">
+                  {(result as SearchResult).tags!.map(tag => `#${tag}`).join(' ')}
+                </p>
+              )}
+            </div>
             <div className="text-right text-gray-500 text-xs mt-2">
               {/* Different format based on type of result */}
               {isSemanticResult && (
                 <code className="text-xs">{truncateHash((result as SearchResult).content_hash, 6)}</code>
               )}
               <div>Score: {result.score.toFixed(2)}</div>
-              <div>{formatDistance(lastModified, new Date(), { addSuffix: true })}</div>
-              {!isSemanticResult && (
-                <div>Distance: {(result as FilenameSearchResult).distance}</div>
+              {isSemanticResult && (result as SearchResult).language && (result as SearchResult).language !== 'und' && (
+                <div className="uppercase">{(result as SearchResult).language}</div>
               )}
+              <div>{formatDistance(lastModified, new Date(), { addSuffix: true })}</div>
             </div>
           </div>
         </div>
@@ -106,11 +106,6 @@ const SearchResultsDisplay = () => {
                   <CardTitle className="text-sm font-medium text-purple-300 flex items-center">
                     {getCategoryIcon(result.category)}
                     <span className="truncate flex-grow" title={result.file_path}>{result.file_path}</span>
-                    {result.distance > 0 && (
-                      <span className="text-xs px-1.5 py-0.5 rounded-full bg-purple-900/50 text-purple-200 ml-2">
-                        Distance: {result.distance}
-                      </span>
-                    )}
                   </CardTitle>
                 </CardHeader>
                 <CardContent className="p-3 pt-0 text-xs grid grid-cols-2 gap-x-4 text-gray-400">
@@ -14,7 +14,8 @@ import {
   LayoutGrid, 
   List, 
   Maximize,
-  Filter
+  Filter,
+  Languages
 } from "lucide-react";
 import { useAtomValue, useSetAtom, useAtom } from 'jotai';
 import {
@@ -31,7 +32,8 @@ import {
   searchModeAtom,
   availableFileCategoriesAtom,
   selectedFileCategoriesAtom,
-  maxDistanceAtom
+  maxDistanceAtom,
+  searchLanguageAtom
 } from '../../store/atoms'; // Adjust path if needed
 import { Window } from "@tauri-apps/api/window"; // Import Window
 
@@ -55,6 +57,7 @@ const Navbar = () => {
   const availableFileCategories = useAtomValue(availableFileCategoriesAtom);
   const [selectedFileCategories, setSelectedFileCategories] = useAtom(selectedFileCategoriesAtom);
   const [maxDistance, setMaxDistance] = useAtom(maxDistanceAtom);
+  const [searchLanguage, setSearchLanguage] = useAtom(searchLanguageAtom);
 
   // Get current path
   const currentPath = useAtomValue(currentPathAtom);
@@ -189,6 +192,20 @@ const Navbar = () => {
             </Button>
           </div>
 
+          {/* Cross-lingual toggle - Only show for semantic search */}
+          {searchMode === 'semantic' && (
+            <Button
+              variant="ghost"
+              size="sm"
+              className={`h-7 px-2 text-xs ${searchLanguage === 'any' ? 'bg-purple-700 text-purple-50' : 'text-gray-400 hover:text-gray-300'}`}
+              onClick={() => setSearchLanguage(searchLanguage === 'any' ? 'auto' : 'any')}
+              title={searchLanguage === 'any' ? 'Searching documents in any language' : 'Searching documents in the query language'}
+            >
+              <Languages className="h-4 w-4 mr-1" />
+              Any language
+            </Button>
+          )}
+
           {/* File Type Filter - Only show for filename search */}
           {searchMode === 'filename' && (
             <div className="flex items-center">
@@ -202,3 +202,45 @@ export async function getVectorDatabaseStats(): Promise<{
     return { text_documents_count: 0, image_documents_count: 0, total_documents_count: 0 };
   }
 }
+
+/**
+ * Cross-lingual indexing settings.
+ */
+export interface CrossLingualConfig {
+  index_english: boolean;
+}
+
+/**
+ * Gets the cross-lingual indexing configuration.
+ * 
+ * @returns A promise that resolves with the current configuration.
+ */
+export async function getCrossLingualConfig(): Promise<CrossLingualConfig> {
+  try {
+    console.log("Invoking get_cross_lingual_config_command");
+    const response = await invoke<CrossLingualConfig>("get_cross_lingual_config_command");
+    console.log("Received from get_cross_lingual_config_command:", response);
+    return response;
+  } catch (error) {
+    console.error("Error invoking get_cross_lingual_config_command:", error);
+    throw error;
+  }
+}
+
+/**
+ * Chooses whether English documents are also indexed for cross-lingual search.
+ * 
+ * @param config - The new configuration.
+ * @returns A promise that resolves with the operation result.
+ */
+export async function setCrossLingualConfig(config: CrossLingualConfig): Promise<{success: boolean, message: string}> {
+  try {
+    console.log("Invoking set_cross_lingual_config_command with:", config);
+    const response = await invoke<{success: boolean, message: string}>("set_cross_lingual_config_command", { config });
+    console.log("Received from set_cross_lingual_config_command:", response);
+    return response;
+  } catch (error) {
+    console.error("Error invoking set_cross_lingual_config_command:", error);
+    throw error;
+  }
+}
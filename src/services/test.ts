@@ -61,10 +61,11 @@ export async function getHomeDir(): Promise<string> {
 
 /**
  * Calls the Tauri backend to open a given file or directory path using the default system application.
+ * For PDFs, `page` asks the backend to open the document at that page when a supporting viewer is available.
  */
-export async function openPath(path: string): Promise<void> {
+export async function openPath(path: string, page?: number | null): Promise<void> {
   try {
-    await invoke("open_path_command", { path });
+    await invoke("open_path_command", { path, page: page ?? null });
     console.log(`Attempted to open path: ${path}`);
   } catch (error) {
     console.error(`Error opening path "${path}":`, error);
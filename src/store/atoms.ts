@@ -1,7 +1,7 @@
 import { atom } from "jotai";
 import { FileInfo, ViewMode } from "../types/file";
 import { CustomLocation } from "../types/location";
-import { SearchResponse, SearchResult, FilenameSearchResult, FileCategory } from "../types/search"; 
+import { SearchResponse, SearchResult, FilenameSearchResult, FileCategory, SearchLanguage } from "../types/search"; 
 import { atomWithStorage } from 'jotai/utils';
 import {
   fetchDirectoryContents,
@@ -24,6 +24,11 @@ import {
 } from "../services/commands";
 
 // --- Indexing State Atoms ---
+export interface FailedFile {
+  file_path: string;
+  reason: string;
+}
+
 export interface IndexingStats {
   files_processed: number;
   files_indexed: number;
@@ -33,7 +38,7 @@ export interface IndexingStats {
   success: boolean;
   message: string;
   indexed_files: string[];
-  failed_files: string[];
+  failed_files: FailedFile[];
   
   // New fields for multimodal indexing
   db_inserts?: number;
@@ -132,6 +137,9 @@ export const availableFileCategoriesAtom = atom<FileCategory[]>([
 
 export const selectedFileCategoriesAtom = atom<FileCategory[]>([]);
 
+// --- Semantic Search Language Mode ---
+export const searchLanguageAtom = atom<SearchLanguage>('auto');
+
 // --- Filename Search Max Distance ---
 export const maxDistanceAtom = atom<number>(2); // Default Levenshtein distance
 
@@ -190,7 +198,8 @@ export const triggerSearchAtom = atom(
       if (searchMode === 'semantic') {
         // Semantic search
         console.log(`Triggering semantic search for: "${query}"`);
-        const response: SearchResponse = await semanticSearch({ query }); 
+        const language = _get(searchLanguageAtom);
+        const response: SearchResponse = await semanticSearch({ query, language }); 
         console.log("Semantic search response:", response);
         set(semanticSearchResultsAtom, response.results);
       } else {
@@ -13,6 +13,21 @@ export type FileCategory =
   | 'Code'
   | 'Other';
 
+/**
+ * Image properties of an image search result.
+ * Matches the Rust ImageData struct.
+ */
+export interface ImageData {
+  width?: number | null;
+  height?: number | null;
+  thumbnail_path?: string | null;
+  taken_at?: string | null; // EXIF capture date (YYYY-MM-DDTHH:MM:SS)
+  camera_model?: string | null; // EXIF camera make and model
+  orientation?: 'landscape' | 'portrait' | 'square' | null;
+  latitude?: number | null; // GPS position in decimal degrees
+  longitude?: number | null;
+}
+
 /**
  * Represents a single semantic search result from the backend.
  * Matches the Rust SearchResult struct.
@@ -22,6 +37,26 @@ export interface SearchResult {
   score: number;
   content_hash: string;
   last_modified: number; // Assuming Rust i64 (timestamp) maps to number
+  content_type?: 'Text' | 'Code' | 'Image' | 'Unsupported'; // Matches the Rust ContentType enum
+  image_data?: ImageData | null; // Dimensions and EXIF data for image results
+  heading_path?: string | null; // Section breadcrumb of the best matching chunk, e.g. "Guide > Install > Linux"
+  page_number?: number | null; // Page of the best matching chunk for paged documents (PDF)
+  sheet_name?: string | null; // Worksheet of the best matching chunk for spreadsheets
+  row_start?: number | null; // First row of the best matching spreadsheet chunk (file row for CSV/TSV, data row below the header for workbooks)
+  row_end?: number | null; // Last row of the best matching spreadsheet chunk
+  symbol_name?: string | null; // Top-level symbol of the best matching source code chunk
+  line_start?: number | null; // First line of the best matching source code chunk
+  line_end?: number | null; // Last line of the best matching source code chunk
+  message_index?: number | null; // Position of the matching message inside a mail archive (mbox)
+  message_subject?: string | null; // Subject of the matching email message
+  message_from?: string | null; // Sender of the matching email message
+  message_to?: string | null; // Recipients of the matching email message
+  message_date?: string | null; // Date of the matching email message (RFC 3339 when parseable)
+  title?: string | null; // Document title from its metadata
+  author?: string | null; // Document author from its metadata
+  created?: string | null; // Document creation date from its metadata
+  tags?: string[]; // Document keywords from its metadata
+  language?: string | null; // Language code of the best matching chunk, e.g. "en", "am"
 }
 
 /**
@@ -35,9 +70,16 @@ export interface FilenameSearchResult {
   last_modified: number;
   size: number;
   score: number;
-  distance: number; // Levenshtein distance
+  match_ranges: [number, number][]; // Matched characters of name as [start, end) character (code point) ranges
 }
 
+/**
+ * Language mode of a semantic search: 'auto' searches content in the query's own
+ * language, 'any' finds matches in every language (cross-lingual).
+ * Matches the Rust SearchLanguage enum.
+ */
+export type SearchLanguage = 'auto' | 'any';
+
 /**
  * Represents the request payload for the semantic search command.
  * Matches the Rust SearchRequest struct.
@@ -48,6 +90,7 @@ export interface SearchRequest {
   min_score?: number;
   db_uri?: string;
   table_name?: string;
+  language?: SearchLanguage;
 }
 
 /**